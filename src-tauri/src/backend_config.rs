@@ -0,0 +1,140 @@
+use once_cell::sync::OnceCell;
+
+use crate::settings::load_settings;
+
+pub const DEFAULT_BACKEND_URL: &str = "https://white-bush-0ea25dc03.3.azurestaticapps.net";
+pub const BACKEND_URL_ENV: &str = "LANGRA_BACKEND_URL";
+const BACKEND_URL_ARG: &str = "--backend-url";
+
+static OVERRIDE_URL: OnceCell<Option<(String, &'static str)>> = OnceCell::new();
+
+pub fn validate_backend_url(raw: &str) -> Result<String, String> {
+    let url = url::Url::parse(raw.trim())
+        .map_err(|e| format!("Invalid backend URL '{}': {}", raw.trim(), e))?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!(
+            "Invalid backend URL '{}': scheme must be http or https",
+            url
+        ));
+    }
+    if url.host_str().is_none() {
+        return Err(format!("Invalid backend URL '{}': missing host", url));
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(format!(
+            "Invalid backend URL '{}': query strings and fragments are not allowed",
+            url
+        ));
+    }
+
+    Ok(url.as_str().trim_end_matches('/').to_string())
+}
+
+fn url_from_args(args: &[String]) -> Option<String> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == BACKEND_URL_ARG {
+            return iter.next().cloned();
+        }
+        if let Some(value) = arg
+            .strip_prefix(BACKEND_URL_ARG)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+fn override_url() -> Option<&'static (String, &'static str)> {
+    OVERRIDE_URL
+        .get_or_init(|| {
+            let args: Vec<String> = std::env::args().collect();
+            if let Some(url) = url_from_args(&args) {
+                return Some((url, "command line"));
+            }
+            std::env::var(BACKEND_URL_ENV)
+                .ok()
+                .filter(|url| !url.trim().is_empty())
+                .map(|url| (url, "environment"))
+        })
+        .as_ref()
+}
+
+fn configured_url() -> (String, &'static str) {
+    if let Some((url, source)) = override_url() {
+        return (url.clone(), source);
+    }
+
+    let settings_url = load_settings().backend_url;
+    if !settings_url.trim().is_empty() {
+        return (settings_url, "settings");
+    }
+
+    (DEFAULT_BACKEND_URL.to_string(), "default")
+}
+
+pub fn init_backend_config() -> Result<String, String> {
+    let (url, source) = configured_url();
+    let url = validate_backend_url(&url).map_err(|e| format!("{} (from {})", e, source))?;
+    log_line!("[BACKEND] Using backend URL {} (from {})", url, source);
+    Ok(url)
+}
+
+pub fn backend_url() -> Result<String, String> {
+    let (url, source) = configured_url();
+    validate_backend_url(&url).map_err(|e| {
+        log_line!("[BACKEND] {} (from {})", e, source);
+        format!("{} (from {})", e, source)
+    })
+}
+
+pub fn backend_endpoint(path: &str) -> Result<String, String> {
+    Ok(format!("{}{}", backend_url()?, path))
+}
+
+#[tauri::command]
+pub fn get_backend_url() -> Result<String, String> {
+    backend_url()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn backend_url_is_read_from_either_argument_form() {
+        assert_eq!(
+            url_from_args(&args(&["langra", "--backend-url", "http://localhost:3000"])).as_deref(),
+            Some("http://localhost:3000")
+        );
+        assert_eq!(
+            url_from_args(&args(&["langra", "--backend-url=https://example.com"])).as_deref(),
+            Some("https://example.com")
+        );
+        assert_eq!(url_from_args(&args(&["langra", "--backend-url"])), None);
+        assert_eq!(url_from_args(&args(&["langra", "--backend-urls=x"])), None);
+        assert_eq!(url_from_args(&args(&["langra"])), None);
+    }
+
+    #[test]
+    fn backend_urls_are_validated_and_normalized() {
+        assert_eq!(
+            validate_backend_url(" https://example.com/api/ ").as_deref(),
+            Ok("https://example.com/api")
+        );
+        assert_eq!(
+            validate_backend_url("http://localhost:3000").as_deref(),
+            Ok("http://localhost:3000")
+        );
+        assert!(validate_backend_url("example.com").is_err());
+        assert!(validate_backend_url("ftp://example.com").is_err());
+        assert!(validate_backend_url("https://example.com/?a=1").is_err());
+        assert!(validate_backend_url("https://example.com/#top").is_err());
+    }
+}
@@ -0,0 +1,499 @@
+use clap::{Parser, Subcommand};
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::future::Future;
+use std::io::{self, IsTerminal, Read, Write};
+use std::process::ExitCode;
+
+use crate::backend_config;
+use crate::logging::{set_log_sink, LogSink};
+use crate::settings::{load_settings, Settings};
+use crate::translator::batch::{self, BatchOptions};
+use crate::translator::{self, EventSink, TranslatorError};
+
+const EXIT_USAGE: u8 = 64;
+const EXIT_DATA: u8 = 65;
+const EXIT_NO_INPUT: u8 = 66;
+const EXIT_UNAVAILABLE: u8 = 69;
+const EXIT_SOFTWARE: u8 = 70;
+const EXIT_IO: u8 = 74;
+const EXIT_TEMPORARY: u8 = 75;
+const EXIT_NO_PERMISSION: u8 = 77;
+const EXIT_CONFIG: u8 = 78;
+const EXIT_INTERRUPTED: u8 = 130;
+
+const STYLES: [&str; 3] = ["formal", "friendly", "casual"];
+
+const EXIT_CODES: &str = "Exit codes:
+  0    success
+  64   invalid arguments
+  65   the input was rejected or the result failed validation
+  66   no input text or file
+  69   quota or budget exhausted, or the provider failed
+  74   reading input or writing output failed
+  75   network error, timeout or rate limit, try again later
+  77   not logged in, the access token was rejected or the plan does not allow it
+  78   invalid backend or provider configuration
+  130  interrupted";
+
+#[derive(Parser)]
+#[command(
+    name = "langra",
+    version,
+    about = "Translate and fix text with Langra without the desktop app",
+    after_help = EXIT_CODES
+)]
+struct Cli {
+    #[arg(long, global = true, help = "Print diagnostic logs to stderr")]
+    verbose: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Write the result once it is complete instead of streaming it"
+    )]
+    no_stream: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(about = "Translate text from the arguments or stdin")]
+    Translate {
+        #[arg(long, short, help = "Target language, defaults to your language rules")]
+        to: Option<String>,
+        #[arg(long, short, help = "Source language, detected when omitted")]
+        from: Option<String>,
+        #[arg(long, short, value_parser = STYLES, help = "Tone of the translation")]
+        style: Option<String>,
+        text: Vec<String>,
+    },
+    #[command(about = "Fix grammar and style of text from the arguments or stdin")]
+    Enhance {
+        #[arg(long, short, value_parser = STYLES, help = "Tone of the corrected text")]
+        style: Option<String>,
+        #[arg(long, short, help = "Language of the text, detected when omitted")]
+        language: Option<String>,
+        #[arg(long, short, help = "Custom instruction such as \"make it shorter\"")]
+        instruction: Option<String>,
+        text: Vec<String>,
+    },
+    #[command(about = "Print the language code of text from the arguments or stdin")]
+    Detect { text: Vec<String> },
+    #[command(
+        about = "Translate documents, subtitles, catalogs and locale files next to the originals"
+    )]
+    File {
+        #[arg(required = true)]
+        paths: Vec<String>,
+        #[arg(long, short, help = "Target language, defaults to your language rules")]
+        to: Option<String>,
+        #[arg(long, help = "Retranslate fuzzy entries in PO and XLIFF catalogs")]
+        include_fuzzy: bool,
+    },
+}
+
+struct CliError {
+    code: u8,
+    message: String,
+}
+
+impl CliError {
+    fn new(code: u8, message: impl Into<String>) -> Self {
+        CliError {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<anyhow::Error> for CliError {
+    fn from(error: anyhow::Error) -> Self {
+        let error = TranslatorError::from(error);
+        CliError::new(exit_code(error.code()), error.message())
+    }
+}
+
+fn exit_code(error_code: &str) -> u8 {
+    match error_code {
+        "unauthorized" | "forbidden" => EXIT_NO_PERMISSION,
+        "quota_exceeded" | "budget_exceeded" | "backend_error" => EXIT_UNAVAILABLE,
+        "rate_limited" | "network" | "timeout" => EXIT_TEMPORARY,
+        "invalid_input" | "validation_failed" => EXIT_DATA,
+        "configuration" => EXIT_CONFIG,
+        _ => EXIT_SOFTWARE,
+    }
+}
+
+struct CliOutput {
+    writer: Mutex<Box<dyn Write + Send>>,
+    stream: bool,
+    received: Mutex<Vec<u16>>,
+    printed: Mutex<String>,
+}
+
+impl CliOutput {
+    fn new(writer: Box<dyn Write + Send>, stream: bool) -> Self {
+        CliOutput {
+            writer: Mutex::new(writer),
+            stream,
+            received: Mutex::new(Vec::new()),
+            printed: Mutex::new(String::new()),
+        }
+    }
+
+    fn write(&self, text: &str) {
+        let mut writer = self.writer.lock();
+        let _ = writer.write_all(text.as_bytes());
+        let _ = writer.flush();
+    }
+
+    fn apply_delta(&self, payload: &Value) {
+        let offset = payload["offset"].as_u64().unwrap_or_default() as usize;
+        let delta = payload["delta"].as_str().unwrap_or_default();
+        let current = {
+            let mut received = self.received.lock();
+            received.truncate(offset);
+            received.extend(delta.encode_utf16());
+            String::from_utf16_lossy(&received)
+        };
+
+        let stable = current.trim_end();
+        let mut printed = self.printed.lock();
+        if stable.len() > printed.len() && stable.starts_with(printed.as_str()) {
+            self.write(&stable[printed.len()..]);
+            *printed = stable.to_string();
+        }
+    }
+
+    fn finish(&self, result: &str) {
+        let printed = self.printed.lock().clone();
+        match result.strip_prefix(printed.as_str()) {
+            Some(rest) => self.write(rest),
+            None => {
+                eprintln!("langra: the streamed output was revised, the final result follows");
+                self.write("\n");
+                self.write(result);
+            }
+        }
+        if !result.ends_with('\n') {
+            self.write("\n");
+        }
+    }
+
+    fn report_progress(&self, payload: &Value) {
+        let path = payload["path"].as_str().unwrap_or_default();
+        match payload["stage"].as_str().unwrap_or_default() {
+            "started" => eprintln!(
+                "{}: translating {} segments",
+                path, payload["total_segments"]
+            ),
+            "completed" => {
+                let added = payload["added_keys"].as_array().map(Vec::len);
+                match added {
+                    Some(added) => eprintln!("{}: added {} keys", path, added),
+                    None => eprintln!("{}: done", path),
+                }
+            }
+            "failed" => eprintln!(
+                "{}: {}",
+                path,
+                payload["error"]["message"].as_str().unwrap_or("failed")
+            ),
+            _ => {}
+        }
+    }
+}
+
+impl EventSink for CliOutput {
+    fn emit_value(&self, event: &str, payload: Value) {
+        match event {
+            "translation-delta" | "enhancement-delta" if self.stream => self.apply_delta(&payload),
+            "batch-progress" => self.report_progress(&payload),
+            "glossary-check" => {
+                if let Some(violations) = payload.as_array().filter(|v| !v.is_empty()) {
+                    eprintln!(
+                        "langra: {} glossary terms were not applied",
+                        violations.len()
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn read_input(text: Vec<String>) -> Result<String, CliError> {
+    if !text.is_empty() {
+        return Ok(text.join(" "));
+    }
+    if io::stdin().is_terminal() {
+        return Err(CliError::new(
+            EXIT_NO_INPUT,
+            "No input text, pass it as arguments or pipe it to stdin",
+        ));
+    }
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|e| CliError::new(EXIT_IO, format!("Failed to read stdin: {}", e)))?;
+    if input.trim().is_empty() {
+        return Err(CliError::new(EXIT_NO_INPUT, "The input text is empty"));
+    }
+    Ok(input)
+}
+
+fn ensure_credentials() -> Result<(), CliError> {
+    if translator::requires_login() && crate::get_access_token().is_err() {
+        return Err(CliError::new(
+            EXIT_NO_PERMISSION,
+            "Not logged in, sign in with the Langra app or configure an API key provider",
+        ));
+    }
+    Ok(())
+}
+
+fn style_settings(style: Option<String>) -> Settings {
+    let mut settings = load_settings();
+    if let Some(style) = style {
+        settings.style = style;
+        settings.language_styles.clear();
+    }
+    settings
+}
+
+async fn source_language(text: &str, declared: Option<String>) -> Result<String, CliError> {
+    match declared {
+        Some(language) => Ok(language),
+        None => Ok(translator::detect_language(text).await?),
+    }
+}
+
+async fn run_job<'a, F>(
+    output: &'a CliOutput,
+    work: impl FnOnce(u64) -> F,
+) -> Result<String, CliError>
+where
+    F: Future<Output = anyhow::Result<String>> + 'a,
+{
+    let job = translator::begin_job(output);
+    let future = work(job.id);
+    match job.run(output, future).await {
+        Some(result) => Ok(result?),
+        None => Err(CliError::new(EXIT_INTERRUPTED, "Cancelled")),
+    }
+}
+
+async fn execute(command: Command, output: &CliOutput) -> Result<(), CliError> {
+    match command {
+        Command::Translate {
+            to,
+            from,
+            style,
+            text,
+        } => {
+            let text = read_input(text)?;
+            ensure_credentials()?;
+            let settings = style_settings(style);
+            let source = source_language(&text, from).await?;
+            let pair = translator::resolve_with_override(&source, to.as_deref(), &settings);
+            let style = translator::resolve_style(&pair.target, &settings);
+            let result = run_job(output, |job_id| {
+                translator::translate_stream(&text, &pair, &style, job_id, output)
+            })
+            .await?;
+            output.finish(&result);
+        }
+        Command::Enhance {
+            style,
+            language,
+            instruction,
+            text,
+        } => {
+            let text = read_input(text)?;
+            ensure_credentials()?;
+            let settings = style_settings(style);
+            let language = source_language(&text, language).await?;
+            let style = translator::resolve_style(&language, &settings);
+            let result = match instruction {
+                Some(instruction) => {
+                    run_job(output, |job_id| {
+                        translator::enhance_stream_with_instruction(
+                            &text,
+                            &language,
+                            &instruction,
+                            &style,
+                            job_id,
+                            output,
+                        )
+                    })
+                    .await?
+                }
+                None => {
+                    run_job(output, |job_id| {
+                        translator::enhance_stream(&text, &language, &style, job_id, output)
+                    })
+                    .await?
+                }
+            };
+            output.finish(&result);
+        }
+        Command::Detect { text } => {
+            let text = read_input(text)?;
+            let language = translator::detect_language(&text).await?;
+            output.write(&format!("{}\n", language));
+        }
+        Command::File {
+            paths,
+            to,
+            include_fuzzy,
+        } => {
+            if let Some(missing) = paths
+                .iter()
+                .find(|path| !std::path::Path::new(path).exists())
+            {
+                return Err(CliError::new(
+                    EXIT_NO_INPUT,
+                    format!("File not found: {}", missing),
+                ));
+            }
+            ensure_credentials()?;
+            let options = BatchOptions { include_fuzzy };
+            let results = batch::translate_paths(output, &paths, to.as_deref(), &options).await;
+            let mut failed = Vec::new();
+            for result in &results {
+                match (&result.output_path, &result.error) {
+                    (Some(path), _) => output.write(&format!("{}\n", path)),
+                    (None, Some(error)) => failed.push(exit_code(error.code)),
+                    (None, None) => {}
+                }
+            }
+            if let Some(code) = failed.first() {
+                return Err(CliError::new(
+                    *code,
+                    format!("{} of {} files failed", failed.len(), results.len()),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn run() -> ExitCode {
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => {
+            let _ = e.print();
+            return match e.use_stderr() {
+                true => ExitCode::from(EXIT_USAGE),
+                false => ExitCode::SUCCESS,
+            };
+        }
+    };
+
+    set_log_sink(match cli.verbose {
+        true => LogSink::Stderr,
+        false => LogSink::Silent,
+    });
+    if let Err(e) = backend_config::init_backend_config() {
+        eprintln!("langra: {}", e);
+        return ExitCode::from(EXIT_CONFIG);
+    }
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("langra: failed to start runtime: {}", e);
+            return ExitCode::from(EXIT_SOFTWARE);
+        }
+    };
+
+    let output = CliOutput::new(Box::new(io::stdout()), !cli.no_stream);
+    let result = runtime.block_on(async {
+        tokio::select! {
+            result = execute(cli.command, &output) => result,
+            _ = tokio::signal::ctrl_c() => {
+                translator::cancel_active_job();
+                Err(CliError::new(EXIT_INTERRUPTED, "Interrupted"))
+            }
+        }
+    });
+    translator::cache::flush();
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("langra: {}", error.message);
+            ExitCode::from(error.code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.lock().extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn capture(stream: bool, run: impl FnOnce(&CliOutput)) -> String {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let output = CliOutput::new(Box::new(Buffer(written.clone())), stream);
+        run(&output);
+        let bytes = written.lock().clone();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    fn delta(offset: usize, delta: &str) -> Value {
+        json!({ "offset": offset, "delta": delta })
+    }
+
+    #[test]
+    fn streamed_deltas_are_written_once() {
+        let written = capture(true, |output| {
+            output.emit_value("translation-delta", delta(0, "Hallo "));
+            output.emit_value("translation-delta", delta(6, "Welt"));
+            output.emit_value("translation-delta", delta(5, " Welt!"));
+            output.finish("Hallo Welt!");
+        });
+        assert_eq!(written, "Hallo Welt!\n");
+    }
+
+    #[test]
+    fn revised_stream_prints_final_result() {
+        let written = capture(true, |output| {
+            output.emit_value("enhancement-delta", delta(0, "Grüße"));
+            output.emit_value("enhancement-delta", delta(3, "sse"));
+            output.finish("Grüsse\n");
+        });
+        assert_eq!(written, "Grüße\nGrüsse\n");
+    }
+
+    #[test]
+    fn buffered_output_ignores_deltas() {
+        let written = capture(false, |output| {
+            output.emit_value("translation-delta", delta(0, "Hal"));
+            output.finish("Hallo");
+        });
+        assert_eq!(written, "Hallo\n");
+    }
+
+    #[test]
+    fn error_codes_map_to_sysexits() {
+        assert_eq!(exit_code("unauthorized"), EXIT_NO_PERMISSION);
+        assert_eq!(exit_code("budget_exceeded"), EXIT_UNAVAILABLE);
+        assert_eq!(exit_code("timeout"), EXIT_TEMPORARY);
+        assert_eq!(exit_code("invalid_input"), EXIT_DATA);
+        assert_eq!(exit_code("unknown"), EXIT_SOFTWARE);
+    }
+}
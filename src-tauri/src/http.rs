@@ -0,0 +1,267 @@
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use std::error::Error as StdError;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::settings::{load_settings, NetworkSettings};
+
+const INITIAL_BACKOFF_MS: u64 = 500;
+const MAX_BACKOFF_MS: u64 = 8000;
+
+static CLIENT: Mutex<Option<(NetworkSettings, reqwest::Client)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Retry {
+    Never,
+    Idempotent,
+}
+
+#[derive(Debug)]
+pub struct FirstByteTimeout(Duration);
+
+impl std::fmt::Display for FirstByteTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Server did not respond within {} seconds",
+            self.0.as_secs()
+        )
+    }
+}
+
+impl StdError for FirstByteTimeout {}
+
+fn load_certificates(path: &str) -> Result<Vec<reqwest::Certificate>> {
+    let bytes =
+        fs::read(path).with_context(|| format!("Failed to read CA certificate {}", path))?;
+
+    let is_der = Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| matches!(extension.to_lowercase().as_str(), "der" | "cer"))
+        && !bytes.starts_with(b"-----BEGIN");
+
+    let certificates = if is_der {
+        vec![reqwest::Certificate::from_der(&bytes)?]
+    } else {
+        reqwest::Certificate::from_pem_bundle(&bytes)?
+    };
+
+    if certificates.is_empty() {
+        anyhow::bail!("No certificates found in {}", path);
+    }
+    Ok(certificates)
+}
+
+fn build_proxy(proxy_url: &str) -> Result<reqwest::Proxy> {
+    let url = url::Url::parse(proxy_url.trim()).context("Proxy URL is not a valid URL")?;
+    match url.scheme() {
+        "http" | "https" | "socks5" | "socks5h" => {}
+        scheme => anyhow::bail!("Unsupported proxy scheme: {}", scheme),
+    }
+    if url.host_str().is_none() {
+        anyhow::bail!("Proxy URL has no host");
+    }
+    Ok(reqwest::Proxy::all(url.as_str())?)
+}
+
+pub fn build_client(network: &NetworkSettings) -> Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(network.connect_timeout_secs.max(1)))
+        .tcp_keepalive(Duration::from_secs(60));
+
+    match network.proxy_mode.as_str() {
+        "" | "system" => {}
+        "none" => builder = builder.no_proxy(),
+        "manual" => builder = builder.proxy(build_proxy(&network.proxy_url)?),
+        other => anyhow::bail!("Unknown proxy mode: {}", other),
+    }
+
+    for path in network
+        .ca_certificates
+        .iter()
+        .map(|path| path.trim())
+        .filter(|path| !path.is_empty())
+    {
+        for certificate in load_certificates(path)? {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    Ok(builder.build()?)
+}
+
+pub fn client() -> Result<reqwest::Client> {
+    let network = load_settings().network;
+    let mut client_lock = CLIENT.lock();
+
+    if let Some((cached_network, client)) = client_lock.as_ref() {
+        if *cached_network == network {
+            return Ok(client.clone());
+        }
+    }
+
+    log_line!("[HTTP] Building HTTP client");
+    let client = build_client(&network)?;
+    *client_lock = Some((network, client.clone()));
+    Ok(client)
+}
+
+fn is_transient_error(error: &anyhow::Error) -> bool {
+    if error.downcast_ref::<FirstByteTimeout>().is_some() {
+        return true;
+    }
+
+    let Some(error) = error.downcast_ref::<reqwest::Error>() else {
+        return false;
+    };
+    if error.is_connect() || error.is_timeout() {
+        return true;
+    }
+
+    let mut source = error.source();
+    while let Some(cause) = source {
+        if let Some(io_error) = cause.downcast_ref::<io::Error>() {
+            return matches!(
+                io_error.kind(),
+                io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::UnexpectedEof
+                    | io::ErrorKind::TimedOut
+            );
+        }
+        source = cause.source();
+    }
+    false
+}
+
+fn is_transient_status(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+fn backoff(attempt: u32) -> Duration {
+    let delay = INITIAL_BACKOFF_MS.saturating_mul(1 << attempt.min(16));
+    Duration::from_millis(delay.min(MAX_BACKOFF_MS))
+}
+
+pub fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<u64> {
+    let value = headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+    if let Ok(seconds) = value.parse() {
+        return Some(seconds);
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let seconds = date.timestamp() - chrono::Utc::now().timestamp();
+    Some(seconds.max(0) as u64)
+}
+
+fn retry_delay(attempt: u32, retry_after: Option<u64>, timeout: Duration) -> Duration {
+    match retry_after {
+        Some(seconds) => Duration::from_secs(seconds).min(timeout),
+        None => backoff(attempt),
+    }
+}
+
+async fn send_once(
+    request: reqwest::RequestBuilder,
+    timeout: Duration,
+) -> Result<reqwest::Response> {
+    match tokio::time::timeout(timeout, request.send()).await {
+        Ok(response) => Ok(response?),
+        Err(_) => Err(FirstByteTimeout(timeout).into()),
+    }
+}
+
+pub async fn send(request: reqwest::RequestBuilder, retry: Retry) -> Result<reqwest::Response> {
+    let network = load_settings().network;
+    let timeout = Duration::from_secs(network.first_byte_timeout_secs.max(1));
+    let max_retries = match retry {
+        Retry::Never => 0,
+        Retry::Idempotent => network.max_retries,
+    };
+
+    let mut attempt = 0;
+    loop {
+        let retry_request = if attempt < max_retries {
+            request.try_clone()
+        } else {
+            None
+        };
+        let Some(next_request) = retry_request else {
+            return send_once(request, timeout).await;
+        };
+
+        let retry_after = match send_once(next_request, timeout).await {
+            Ok(response) if !is_transient_status(response.status()) => return Ok(response),
+            Ok(response) => {
+                log_line!(
+                    "[HTTP] Attempt {} failed with status {}, retrying",
+                    attempt + 1,
+                    response.status()
+                );
+                retry_after(response.headers())
+            }
+            Err(e) if is_transient_error(&e) => {
+                log_line!("[HTTP] Attempt {} failed: {}, retrying", attempt + 1, e);
+                None
+            }
+            Err(e) => return Err(e),
+        };
+
+        tokio::time::sleep(retry_delay(attempt, retry_after, timeout)).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+    use reqwest::StatusCode;
+
+    #[test]
+    fn server_errors_and_rate_limits_are_retried() {
+        assert!(is_transient_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_transient_status(StatusCode::BAD_GATEWAY));
+        assert!(!is_transient_status(StatusCode::BAD_REQUEST));
+        assert!(!is_transient_status(StatusCode::UNAUTHORIZED));
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum() {
+        assert_eq!(backoff(0), Duration::from_millis(500));
+        assert_eq!(backoff(1), Duration::from_millis(1000));
+        assert_eq!(backoff(3), Duration::from_millis(4000));
+        assert_eq!(backoff(10), Duration::from_millis(MAX_BACKOFF_MS));
+    }
+
+    #[test]
+    fn retry_after_overrides_backoff_up_to_the_timeout() {
+        let timeout = Duration::from_secs(30);
+        assert_eq!(retry_delay(0, Some(5), timeout), Duration::from_secs(5));
+        assert_eq!(retry_delay(0, Some(120), timeout), timeout);
+        assert_eq!(retry_delay(2, None, timeout), backoff(2));
+    }
+
+    #[test]
+    fn retry_after_header_is_read_as_seconds() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, HeaderValue::from_static(" 7 "));
+        assert_eq!(retry_after(&headers), Some(7));
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(0));
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("soon"));
+        assert_eq!(retry_after(&headers), None);
+    }
+}
@@ -10,19 +10,19 @@ fn is_translator_process(window: &ActiveWindow) -> bool {
 }
 
 pub fn remember_active_window() {
-    println!("[INSERTION] Attempting to remember active window...");
+    log_line!("[INSERTION] Attempting to remember active window...");
     match get_active_window() {
         Ok(window) => {
-            println!("[INSERTION] Got active window: app='{}', process_id={}", window.app_name, window.process_id);
+            log_line!("[INSERTION] Got active window: app='{}', process_id={}", window.app_name, window.process_id);
             if is_translator_process(&window) {
-                println!("[INSERTION] ⚠️ Active window is translator itself, not saving");
+                log_line!("[INSERTION] ⚠️ Active window is translator itself, not saving");
             } else {
-                println!("[INSERTION] ✅ Saved previous window: '{}'", window.app_name);
+                log_line!("[INSERTION] ✅ Saved previous window: '{}'", window.app_name);
                 *PREVIOUS_ACTIVE_WINDOW.lock() = Some(window);
             }
         }
         Err(e) => {
-            println!("[INSERTION] ❌ Failed to get active window: {:?}", e);
+            log_line!("[INSERTION] ❌ Failed to get active window: {:?}", e);
         }
     }
 }
@@ -182,13 +182,13 @@ fn focus_window(window: &ActiveWindow) -> Result<(), String> {
 }
 
 fn focus_previous_window() -> Result<(), String> {
-    println!("[INSERTION] Checking for previous window...");
+    log_line!("[INSERTION] Checking for previous window...");
     if let Some(window) = PREVIOUS_ACTIVE_WINDOW.lock().clone() {
-        println!("[INSERTION] Found previous window: '{}'", window.app_name);
+        log_line!("[INSERTION] Found previous window: '{}'", window.app_name);
         let result = focus_window(&window);
         result
     } else {
-        println!("[INSERTION] No previous window in memory");
+        log_line!("[INSERTION] No previous window in memory");
         Err("no previous window recorded".to_string())
     }
 }
@@ -199,7 +199,7 @@ fn instant_paste_command() -> Result<(), String> {
     use core_graphics::event::{CGEvent, CGEventFlags, CGEventTapLocation};
     use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
 
-    println!("[INSERTION] Instant paste via CGEvent");
+    log_line!("[INSERTION] Instant paste via CGEvent");
 
     let source = CGEventSource::new(CGEventSourceStateID::CombinedSessionState)
         .map_err(|_| "Failed to create CGEventSource")?;
@@ -223,7 +223,7 @@ fn instant_paste_command() -> Result<(), String> {
         VK_CONTROL, VK_V,
     };
 
-    println!("[INSERTION] Instant paste via SendInput");
+    log_line!("[INSERTION] Instant paste via SendInput");
 
     let mut inputs: [INPUT; 4] = unsafe { std::mem::zeroed() };
 
@@ -277,7 +277,7 @@ fn instant_paste_command() -> Result<(), String> {
 fn instant_paste_command() -> Result<(), String> {
     use enigo::{Enigo, Key, Direction, Keyboard, Settings};
 
-    println!("[INSERTION] Instant paste via enigo");
+    log_line!("[INSERTION] Instant paste via enigo");
 
     let mut enigo = Enigo::new(&Settings::default()).map_err(|e| e.to_string())?;
     enigo.key(Key::Control, Direction::Press).map_err(|e| e.to_string())?;
@@ -297,40 +297,41 @@ fn replace_input_with_text(text: &str) -> Result<(), String> {
     let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
     clipboard.set_text(text).map_err(|e| e.to_string())?;
 
-    println!("[INSERTION] Text in clipboard, ready to paste");
+    log_line!("[INSERTION] Text in clipboard, ready to paste");
 
     thread::sleep(Duration::from_millis(50));
 
     instant_paste_command()?;
-    println!("[INSERTION] ✅ Paste complete");
+    log_line!("[INSERTION] ✅ Paste complete");
 
     Ok(())
 }
 
 #[tauri::command]
 pub async fn insert_translation_into_previous_input(text: String) -> Result<(), String> {
-    println!("[INSERTION] Starting replace with text: '{}'", text);
+    log_line!("[INSERTION] Starting replace with text: '{}'", text);
+    crate::translator::memory::confirm_translation(&text);
 
-    println!("[INSERTION] Focusing previous window...");
+    log_line!("[INSERTION] Focusing previous window...");
     match focus_previous_window() {
-        Ok(_) => println!("[INSERTION] ✅ Window focused successfully"),
+        Ok(_) => log_line!("[INSERTION] ✅ Window focused successfully"),
         Err(e) => {
-            println!("[INSERTION] ❌ Failed to focus window: {}", e);
+            log_line!("[INSERTION] ❌ Failed to focus window: {}", e);
             return Err(e);
         }
     }
 
-    println!("[INSERTION] Waiting 100ms for window focus...");
+    log_line!("[INSERTION] Waiting 100ms for window focus...");
     thread::sleep(Duration::from_millis(100));
 
-    println!("[INSERTION] Replacing input with text...");
+    log_line!("[INSERTION] Replacing input with text...");
     match replace_input_with_text(&text) {
         Ok(_) => {
-            println!("[INSERTION] ✅ Text inserted successfully");
+            log_line!("[INSERTION] ✅ Text inserted successfully");
             Ok(())
         }
         Err(e) => {
-            println!("[INSERTION] ❌ Failed to insert text: {}", e);
+            log_line!("[INSERTION] ❌ Failed to insert text: {}", e);
             Err(e)
         }
     }
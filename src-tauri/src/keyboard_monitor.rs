@@ -9,7 +9,7 @@ struct KeyboardState {
 }
 
 pub fn start_listener(app: AppHandle) {
-    println!("[KEYBOARD] Starting keyboard listener...");
+    log_line!("[KEYBOARD] Starting keyboard listener...");
 
     let state = Arc::new(Mutex::new(KeyboardState {
         cmd_ctrl_pressed: false,
@@ -19,13 +19,13 @@ pub fn start_listener(app: AppHandle) {
 
     #[cfg(target_os = "macos")]
     {
-        println!("[KEYBOARD] Starting macOS listener...");
+        log_line!("[KEYBOARD] Starting macOS listener...");
         start_macos_listener(app, state);
     }
 
     #[cfg(not(target_os = "macos"))]
     {
-        println!("[KEYBOARD] Starting rdev listener...");
+        log_line!("[KEYBOARD] Starting rdev listener...");
         start_rdev_listener(app, state);
     }
 }
@@ -68,21 +68,21 @@ fn start_macos_listener(app: AppHandle, state: Arc<Mutex<KeyboardState>>) {
 
                         if keycode == 8 && state_lock.cmd_ctrl_pressed {
                             let now = Instant::now();
-                            println!("[KEYBOARD] Cmd+C detected, count: {}", state_lock.c_press_count + 1);
+                            log_line!("[KEYBOARD] Cmd+C detected, count: {}", state_lock.c_press_count + 1);
 
                             if let Some(last_time) = state_lock.last_c_press_time {
                                 let elapsed = now.duration_since(last_time);
 
                                 if elapsed > Duration::from_millis(500) {
-                                    println!("[KEYBOARD] Too slow, resetting count");
+                                    log_line!("[KEYBOARD] Too slow, resetting count");
                                     state_lock.c_press_count = 1;
                                 } else {
                                     state_lock.c_press_count += 1;
 
                                     if state_lock.c_press_count >= 2 {
-                                        println!("[KEYBOARD] Double Cmd+C detected! Triggering translation...");
+                                        log_line!("[KEYBOARD] Double Cmd+C detected! Triggering translation...");
 
-                                        println!("[KEYBOARD] Remembering active window before translation...");
+                                        log_line!("[KEYBOARD] Remembering active window before translation...");
                                         crate::insertion::remember_active_window();
 
                                         let app_clone = app_for_callback.clone();
@@ -1,4 +1,9 @@
+#[macro_use]
+mod logging;
 mod translator;
+pub mod cli;
+mod http;
+mod backend_config;
 mod utils;
 mod windows;
 mod insertion;
@@ -16,8 +21,11 @@ static CURRENT_MODE: Mutex<String> = Mutex::new(String::new());
 
 #[tauri::command]
 fn set_mode(mode: String) {
-    println!("[MODE] Setting mode to: {}", mode);
-    *CURRENT_MODE.lock() = mode;
+    log_line!("[MODE] Setting mode to: {}", mode);
+    let previous_mode = std::mem::replace(&mut *CURRENT_MODE.lock(), mode.clone());
+    if previous_mode != mode {
+        translator::cancel_active_job();
+    }
 }
 
 #[tauri::command]
@@ -33,6 +41,7 @@ fn get_mode() -> String {
 #[tauri::command]
 fn copy_to_clipboard(text: String) -> Result<(), String> {
     use arboard::Clipboard;
+    translator::memory::confirm_translation(&text);
     let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
     clipboard.set_text(text).map_err(|e| e.to_string())?;
     Ok(())
@@ -67,10 +76,10 @@ fn request_input_monitoring_permission() -> bool {
             fn CGRequestListenEventAccess() -> u8;
         }
 
-        println!("[PERMISSIONS] Requesting Input Monitoring permission...");
+        log_line!("[PERMISSIONS] Requesting Input Monitoring permission...");
         unsafe {
             let result = CGRequestListenEventAccess();
-            println!("[PERMISSIONS] Request result: {}", result);
+            log_line!("[PERMISSIONS] Request result: {}", result);
             result != 0
         }
     }
@@ -96,7 +105,7 @@ fn open_input_monitoring_settings() {
 fn restart_app() {
     use std::process::Command;
 
-    println!("[APP] Restarting application...");
+    log_line!("[APP] Restarting application...");
 
     #[cfg(target_os = "macos")]
     {
@@ -149,22 +158,22 @@ fn open_url(url: String) -> Result<(), String> {
 
 #[tauri::command]
 async fn verify_access_token(token: String) -> Result<serde_json::Value, String> {
-    println!("[AUTH] Verifying access token...");
+    log_line!("[AUTH] Verifying access token...");
 
-    let client = reqwest::Client::new();
-    let response = client
-        .post("https://white-bush-0ea25dc03.3.azurestaticapps.net/api/tokens/verify")
+    let client = http::client().map_err(|e| format!("Network error: {:#}", e))?;
+    let request = client
+        .post(backend_config::backend_endpoint("/api/tokens/verify")?)
         .json(&serde_json::json!({
             "token": token
-        }))
-        .send()
+        }));
+    let response = http::send(request, http::Retry::Idempotent)
         .await
         .map_err(|e| format!("Network error: {}", e))?;
 
     if response.status().is_success() {
         let result: serde_json::Value = response.json().await
             .map_err(|e| format!("Failed to parse response: {}", e))?;
-        println!("[AUTH] Token verified successfully");
+        log_line!("[AUTH] Token verified successfully");
         Ok(result)
     } else {
         Err("Invalid token".to_string())
@@ -173,7 +182,7 @@ async fn verify_access_token(token: String) -> Result<serde_json::Value, String>
 
 #[tauri::command]
 async fn save_access_token(token: String) -> Result<(), String> {
-    println!("[AUTH] Saving access token...");
+    log_line!("[AUTH] Saving access token...");
 
     use std::fs;
     use std::path::PathBuf;
@@ -186,7 +195,7 @@ async fn save_access_token(token: String) -> Result<(), String> {
     fs::create_dir_all(&app_dir).map_err(|e| e.to_string())?;
 
     let token_path = app_dir.join("access_token");
-    println!("[AUTH] Token path: {:?}", token_path);
+    log_line!("[AUTH] Token path: {:?}", token_path);
 
     let timestamp = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -199,7 +208,7 @@ async fn save_access_token(token: String) -> Result<(), String> {
     });
 
     fs::write(&token_path, token_data.to_string()).map_err(|e| e.to_string())?;
-    println!("[AUTH] Access token saved successfully");
+    log_line!("[AUTH] Access token saved successfully");
 
     Ok(())
 }
@@ -243,7 +252,7 @@ fn get_access_token() -> Result<String, String> {
     let three_months_in_seconds = 90 * 24 * 60 * 60;
 
     if now - created_at > three_months_in_seconds {
-        println!("[AUTH] Token expired (older than 3 months)");
+        log_line!("[AUTH] Token expired (older than 3 months)");
         fs::remove_file(&token_path).ok();
         return Err("Token expired".to_string());
     }
@@ -253,9 +262,12 @@ fn get_access_token() -> Result<String, String> {
 
 #[tauri::command]
 fn logout() -> Result<(), String> {
-    use std::fs;
+    log_line!("[AUTH] Logging out...");
+    clear_access_token()
+}
 
-    println!("[AUTH] Logging out...");
+pub fn clear_access_token() -> Result<(), String> {
+    use std::fs;
 
     let app_dir = dirs::data_dir()
         .ok_or("Failed to get app data directory")?
@@ -266,9 +278,9 @@ fn logout() -> Result<(), String> {
     if token_path.exists() {
         fs::remove_file(&token_path)
             .map_err(|e| format!("Failed to delete access token: {}", e))?;
-        println!("[AUTH] Access token deleted successfully");
+        log_line!("[AUTH] Access token deleted successfully");
     } else {
-        println!("[AUTH] No access token found to delete");
+        log_line!("[AUTH] No access token found to delete");
     }
 
     Ok(())
@@ -276,17 +288,17 @@ fn logout() -> Result<(), String> {
 
 #[tauri::command]
 async fn login_and_get_token(email: String, password: String) -> Result<serde_json::Value, String> {
-    println!("[AUTH] Logging in with email: {}", email);
+    log_line!("[AUTH] Logging in with email: {}", email);
 
-    let client = reqwest::Client::new();
+    let client = http::client().map_err(|e| format!("Network error: {:#}", e))?;
 
-    let login_response = client
-        .post("https://white-bush-0ea25dc03.3.azurestaticapps.net/api/auth/login")
+    let login_request = client
+        .post(backend_config::backend_endpoint("/api/auth/login")?)
         .json(&serde_json::json!({
             "email": email,
             "password": password
-        }))
-        .send()
+        }));
+    let login_response = http::send(login_request, http::Retry::Never)
         .await
         .map_err(|e| format!("Network error: {}", e))?;
 
@@ -301,15 +313,15 @@ async fn login_and_get_token(email: String, password: String) -> Result<serde_js
         .and_then(|t| t.as_str())
         .ok_or("No access token in login response")?;
 
-    println!("[AUTH] Login successful, generating token...");
+    log_line!("[AUTH] Login successful, generating token...");
 
-    let token_response = client
-        .post("https://white-bush-0ea25dc03.3.azurestaticapps.net/api/tokens/generate")
+    let token_request = client
+        .post(backend_config::backend_endpoint("/api/tokens/generate")?)
         .header("Authorization", format!("Bearer {}", session_token))
         .json(&serde_json::json!({
             "deviceName": "Langra Desktop"
-        }))
-        .send()
+        }));
+    let token_response = http::send(token_request, http::Retry::Never)
         .await
         .map_err(|e| format!("Failed to generate token: {}", e))?;
 
@@ -326,23 +338,23 @@ async fn login_and_get_token(email: String, password: String) -> Result<serde_js
 
     save_access_token(token.to_string()).await?;
 
-    println!("[AUTH] Token saved successfully");
+    log_line!("[AUTH] Token saved successfully");
     Ok(serde_json::json!({"success": true}))
 }
 
 #[tauri::command]
 async fn signup_and_get_token(email: String, password: String) -> Result<serde_json::Value, String> {
-    println!("[AUTH] Signing up with email: {}", email);
+    log_line!("[AUTH] Signing up with email: {}", email);
 
-    let client = reqwest::Client::new();
+    let client = http::client().map_err(|e| format!("Network error: {:#}", e))?;
 
-    let signup_response = client
-        .post("https://white-bush-0ea25dc03.3.azurestaticapps.net/api/auth/signup")
+    let signup_request = client
+        .post(backend_config::backend_endpoint("/api/auth/signup")?)
         .json(&serde_json::json!({
             "email": email,
             "password": password
-        }))
-        .send()
+        }));
+    let signup_response = http::send(signup_request, http::Retry::Never)
         .await
         .map_err(|e| format!("Network error: {}", e))?;
 
@@ -359,15 +371,15 @@ async fn signup_and_get_token(email: String, password: String) -> Result<serde_j
         .and_then(|t| t.as_str())
         .ok_or("No access token in signup response")?;
 
-    println!("[AUTH] Signup successful, generating token...");
+    log_line!("[AUTH] Signup successful, generating token...");
 
-    let token_response = client
-        .post("https://white-bush-0ea25dc03.3.azurestaticapps.net/api/tokens/generate")
+    let token_request = client
+        .post(backend_config::backend_endpoint("/api/tokens/generate")?)
         .header("Authorization", format!("Bearer {}", session_token))
         .json(&serde_json::json!({
             "deviceName": "Langra Desktop"
-        }))
-        .send()
+        }));
+    let token_response = http::send(token_request, http::Retry::Never)
         .await
         .map_err(|e| format!("Failed to generate token: {}", e))?;
 
@@ -384,22 +396,21 @@ async fn signup_and_get_token(email: String, password: String) -> Result<serde_j
 
     save_access_token(token.to_string()).await?;
 
-    println!("[AUTH] Token saved successfully");
+    log_line!("[AUTH] Token saved successfully");
     Ok(serde_json::json!({"success": true}))
 }
 
 #[tauri::command]
 fn oauth_login(provider: String) -> Result<serde_json::Value, String> {
-    println!("[AUTH] Starting OAuth with provider: {}", provider);
+    log_line!("[AUTH] Starting OAuth with provider: {}", provider);
 
-    let auth_url = format!(
-        "https://white-bush-0ea25dc03.3.azurestaticapps.net/api/auth/oauth?provider={}",
-        provider
-    );
+    let mut auth_url = url::Url::parse(&backend_config::backend_endpoint("/api/auth/oauth")?)
+        .map_err(|e| format!("Invalid backend URL: {}", e))?;
+    auth_url.query_pairs_mut().append_pair("provider", &provider);
 
-    open_url(auth_url)?;
+    open_url(auth_url.to_string())?;
 
-    println!("[AUTH] OAuth flow initiated in browser. User will authenticate and token will be sent via deep link.");
+    log_line!("[AUTH] OAuth flow initiated in browser. User will authenticate and token will be sent via deep link.");
 
     Ok(serde_json::json!({"success": true}))
 }
@@ -411,132 +422,175 @@ async fn enhance_text_with_instruction(
     instruction: String,
     app: AppHandle,
 ) {
-    use tauri::Emitter;
-
-    println!("[ENHANCE_CUSTOM] Starting custom enhancement with instruction: {}", instruction);
-    match translator::enhance_stream_with_instruction(&text, &language, &instruction, &app).await {
-        Ok(enhanced) => {
-            println!("[ENHANCE_CUSTOM] ✅ Enhancement complete: '{}'", enhanced);
-            let _ = app.emit("translation-complete", ());
+    log_line!("[ENHANCE_CUSTOM] Starting custom enhancement with instruction: {}", instruction);
+    let job = translator::begin_job(&app);
+    let job_id = job.id;
+    let style = translator::resolve_style(&language, &settings::load_settings());
+    let enhancement = translator::enhance_stream_with_instruction(
+        &text,
+        &language,
+        &instruction,
+        &style,
+        job_id,
+        &app,
+    );
+    match job.run(&app, enhancement).await {
+        Some(Ok(enhanced)) => {
+            log_line!("[ENHANCE_CUSTOM] ✅ Enhancement complete: '{}'", enhanced);
+            translator::emit_job_complete(&app, job_id);
         }
-        Err(e) => {
-            println!("[ENHANCE_CUSTOM] ❌ Enhancement error: {:?}", e);
-            let _ = app.emit("translation-error", e.to_string());
+        Some(Err(e)) => {
+            log_line!("[ENHANCE_CUSTOM] ❌ Enhancement error: {:?}", e);
+            translator::emit_job_error(&app, job_id, e.into());
         }
+        None => log_line!("[ENHANCE_CUSTOM] Enhancement cancelled"),
     }
 }
 
 pub async fn trigger_translation(app: &AppHandle) {
-    use tauri::Emitter;
+    log_line!("[TRIGGER] Translation triggered");
+
+    let job = translator::begin_job(app);
+    let job_id = job.id;
+    if job.run(app, run_triggered_translation(app, job_id)).await.is_none() {
+        log_line!("[TRIGGER] Job {} cancelled", job_id);
+    }
+}
 
-    println!("[TRIGGER] Translation triggered");
+async fn run_triggered_translation(app: &AppHandle, job_id: u64) {
+    use tauri::Emitter;
 
-    println!("[TRIGGER] Resizing to popup mode before showing...");
+    log_line!("[TRIGGER] Resizing to popup mode before showing...");
     crate::resize::resize_window_to_popup();
 
-    println!("[TRIGGER] Opening window immediately...");
+    log_line!("[TRIGGER] Opening window immediately...");
     windows::show_translator_window(true);
 
     tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
 
-    println!("[TRIGGER] Checking if user is logged in...");
+    log_line!("[TRIGGER] Checking if user is logged in...");
 
-    if get_access_token().is_err() {
-        println!("[TRIGGER] Not logged in, showing welcome screen");
+    if translator::requires_login() && get_access_token().is_err() {
+        log_line!("[TRIGGER] Not logged in, showing welcome screen");
         let _ = app.emit("credentials-missing", ());
         return;
     }
 
-    println!("[TRIGGER] User logged in, proceeding with translation");
+    log_line!("[TRIGGER] User logged in, proceeding with translation");
+
+    log_line!("[TRIGGER] Reading clipboard (first Cmd+C already copied it)");
 
-    println!("[TRIGGER] Reading clipboard (first Cmd+C already copied it)");
+    let current_mode = get_mode();
 
     use arboard::Clipboard;
     let selected_text = match Clipboard::new() {
         Ok(mut clipboard) => match clipboard.get_text() {
             Ok(text) => {
-                println!("[TRIGGER] Got text from clipboard: '{}'", text);
-                text
+                log_line!("[TRIGGER] Got text from clipboard: '{}'", text);
+                let html_source = if current_mode == "enhance" || text.trim().is_empty() {
+                    None
+                } else {
+                    clipboard.get().html().ok().and_then(|html| translator::html_flavor_text(&html))
+                };
+                match html_source {
+                    Some(structured) => {
+                        log_line!("[TRIGGER] Using HTML clipboard flavor for block structure ({} chars)", structured.len());
+                        structured
+                    }
+                    None => text,
+                }
             },
             Err(e) => {
-                println!("[TRIGGER] Error reading clipboard: {:?}", e);
+                log_line!("[TRIGGER] Error reading clipboard: {:?}", e);
                 String::new()
             }
         },
         Err(e) => {
-            println!("[TRIGGER] Error creating clipboard: {:?}", e);
+            log_line!("[TRIGGER] Error creating clipboard: {:?}", e);
             String::new()
         }
     };
 
     if !selected_text.is_empty() {
-        println!("[TRIGGER] Translating text: '{}'", selected_text);
+        log_line!("[TRIGGER] Translating text: '{}'", selected_text);
 
         let lang = match translator::detect_language(&selected_text).await {
             Ok(detected_lang) => {
-                println!("[TRIGGER] Detected language: {}", detected_lang);
+                log_line!("[TRIGGER] Detected language: {}", detected_lang);
                 detected_lang
             },
             Err(e) => {
-                println!("[TRIGGER] Language detection failed: {:?}, falling back to 'en'", e);
-                "en".to_string()
+                log_line!("[TRIGGER] Language detection failed: {:?}", e);
+                translator::emit_job_error(app, job_id, e.into());
+                return;
             }
         };
 
+        let settings = settings::load_settings();
+        let pair = translator::resolve_language_pair(&lang, &settings);
+        log_line!("[TRIGGER] Resolved language pair: {} -> {}", pair.source, pair.target);
+
+        log_line!("[TRIGGER] Current mode: {}", current_mode);
+
+        let style_language = if current_mode == "enhance" { &lang } else { &pair.target };
+        let style = translator::resolve_style(style_language, &settings);
+        log_line!("[TRIGGER] Applying style: {:?}", style);
+
         #[derive(serde::Serialize, Clone)]
         struct TranslationStartPayload {
+            job_id: u64,
             detected_language: String,
+            source_language: String,
+            target_language: String,
             original_text: String,
+            style: translator::AppliedStyle,
+            format: translator::TextFormat,
         }
 
         let _ = app.emit("translation-start", TranslationStartPayload {
+            job_id,
             detected_language: lang.clone(),
+            source_language: pair.source.clone(),
+            target_language: pair.target.clone(),
             original_text: selected_text.clone(),
+            style: style.clone(),
+            format: translator::detect_format(&selected_text),
         });
 
         tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
 
-        let current_mode = get_mode();
-        println!("[TRIGGER] Current mode: {}", current_mode);
-
         if current_mode == "enhance" {
-            println!("[TRIGGER] Using fix mode");
-            match translator::enhance_stream(&selected_text, &lang, app).await {
+            log_line!("[TRIGGER] Using fix mode");
+            match translator::enhance_stream(&selected_text, &lang, &style, job_id, app).await {
                 Ok(enhanced) => {
-                    println!("[TRIGGER] ✅ Fix complete: '{}'", enhanced);
-                    let _ = app.emit("translation-complete", ());
+                    log_line!("[TRIGGER] ✅ Fix complete: '{}'", enhanced);
+                    translator::emit_job_complete(app, job_id);
                 }
                 Err(e) => {
-                    println!("[TRIGGER] ❌ Fix error: {:?}", e);
-                    let _ = app.emit("translation-error", e.to_string());
+                    log_line!("[TRIGGER] ❌ Fix error: {:?}", e);
+                    translator::emit_job_error(app, job_id, e.into());
                 }
             }
         } else {
-            println!("[TRIGGER] Using translate mode");
-            match translator::translate_stream(&selected_text, &lang, app).await {
+            log_line!("[TRIGGER] Using translate mode");
+            match translator::translate_stream(&selected_text, &pair, &style, job_id, app).await {
                 Ok(translation) => {
-                    println!("[TRIGGER] ✅ Translation complete: '{}'", translation);
-                    let _ = app.emit("translation-complete", ());
+                    log_line!("[TRIGGER] ✅ Translation complete: '{}'", translation);
+                    translator::emit_job_complete(app, job_id);
                 }
                 Err(e) => {
-                    let error_msg = e.to_string();
-                    println!("[TRIGGER] ❌ Translation error: {:?}", e);
-
-                    if error_msg.contains("relative URL without a base")
-                        || error_msg.contains("Invalid Azure credentials")
-                        || error_msg.contains("Invalid OpenAI credentials")
-                        || error_msg.contains("401")
-                        || error_msg.contains("403") {
-                        println!("[TRIGGER] ⚠️ Invalid credentials detected");
-                    }
-
-                    let _ = app.emit("translation-error", error_msg);
+                    log_line!("[TRIGGER] ❌ Translation error: {:?}", e);
+                    translator::emit_job_error(app, job_id, e.into());
                 }
             }
         }
     } else {
-        println!("[TRIGGER] No text selected, closing window");
-        let _ = app.emit("translation-error", "No text selected".to_string());
+        log_line!("[TRIGGER] No text selected, closing window");
+        translator::emit_job_error(
+            app,
+            job_id,
+            translator::TranslatorError::InvalidInput("No text selected".to_string()),
+        );
     }
 }
 
@@ -547,73 +601,134 @@ async fn show_translator_with_selected_text() {
 }
 
 #[tauri::command]
-async fn retranslate(text: String, source_lang: String) {
-    use tauri::Emitter;
+async fn retranslate(
+    text: String,
+    source_lang: String,
+    target_lang: Option<String>,
+    alternatives: Option<usize>,
+) {
     let app = APP_HANDLE.get().unwrap();
 
-    println!("[RETRANSLATE] Retranslating text with source_lang: {}", source_lang);
+    let pair = translator::resolve_with_override(
+        &source_lang,
+        target_lang.as_deref(),
+        &settings::load_settings(),
+    );
+    log_line!("[RETRANSLATE] Retranslating text: {} -> {}", pair.source, pair.target);
+    let style = translator::resolve_style(&pair.target, &settings::load_settings());
+
+    let job = translator::begin_job(app);
+    let job_id = job.id;
+    if let Some(count) = alternatives.filter(|count| *count > 1) {
+        let request = translator::translate_alternatives(&text, &pair, &style, count, job_id, app);
+        match job.run(app, request).await {
+            Some(Ok(alternatives)) => {
+                log_line!("[RETRANSLATE] ✅ {} alternatives ready", alternatives.len());
+                translator::emit_job_complete(app, job_id);
+            }
+            Some(Err(e)) => {
+                log_line!("[RETRANSLATE] ❌ Alternatives error: {:?}", e);
+                translator::emit_job_error(app, job_id, e.into());
+            }
+            None => log_line!("[RETRANSLATE] Alternatives cancelled"),
+        }
+        return;
+    }
 
-    match translator::translate_stream(&text, &source_lang, app).await {
-        Ok(translation) => {
-            println!("[RETRANSLATE] ✅ Translation complete: '{}'", translation);
-            let _ = app.emit("translation-complete", ());
+    match job.run(app, translator::translate_stream(&text, &pair, &style, job_id, app)).await {
+        Some(Ok(translation)) => {
+            log_line!("[RETRANSLATE] ✅ Translation complete: '{}'", translation);
+            translator::emit_job_complete(app, job_id);
         }
-        Err(e) => {
-            let error_msg = e.to_string();
-            println!("[RETRANSLATE] ❌ Translation error: {:?}", e);
-
-            if error_msg.contains("relative URL without a base")
-                || error_msg.contains("Invalid Azure credentials")
-                || error_msg.contains("Invalid OpenAI credentials")
-                || error_msg.contains("401")
-                || error_msg.contains("403") {
-                println!("[RETRANSLATE] ⚠️ Invalid credentials detected");
-            }
+        Some(Err(e)) => {
+            log_line!("[RETRANSLATE] ❌ Translation error: {:?}", e);
+            translator::emit_job_error(app, job_id, e.into());
+        }
+        None => log_line!("[RETRANSLATE] Translation cancelled"),
+    }
+}
+
+#[tauri::command]
+async fn enhance_text_with_corrections(text: String, language: String, ui_language: String) {
+    let app = APP_HANDLE.get().unwrap();
 
-            let _ = app.emit("translation-error", error_msg);
+    log_line!("[CORRECT] Correcting {} text, explanations in {}", language, ui_language);
+
+    let style = translator::resolve_style(&language, &settings::load_settings());
+    let job = translator::begin_job(app);
+    let job_id = job.id;
+    let corrections =
+        translator::enhance_with_corrections(&text, &language, &ui_language, &style, job_id, app);
+    match job.run(app, corrections).await {
+        Some(Ok(report)) => {
+            log_line!("[CORRECT] ✅ {} corrections found", report.corrections.len());
+            translator::emit_job_complete(app, job_id);
+        }
+        Some(Err(e)) => {
+            log_line!("[CORRECT] ❌ Correction error: {:?}", e);
+            translator::emit_job_error(app, job_id, e.into());
         }
+        None => log_line!("[CORRECT] Correction cancelled"),
     }
 }
 
 #[tauri::command]
 async fn enhance_text(text: String, language: String) {
-    use tauri::Emitter;
     let app = APP_HANDLE.get().unwrap();
 
     let sample: String = text.chars().take(100).collect();
-    println!("[FIX] Fixing {} text: '{}'", language, sample);
-
-    match translator::enhance_stream(&text, &language, app).await {
-        Ok(enhanced) => {
-            println!("[FIX] ✅ Fix complete: '{}'", enhanced);
-            let _ = app.emit("translation-complete", ());
+    log_line!("[FIX] Fixing {} text: '{}'", language, sample);
+
+    let style = translator::resolve_style(&language, &settings::load_settings());
+    let job = translator::begin_job(app);
+    let job_id = job.id;
+    match job.run(app, translator::enhance_stream(&text, &language, &style, job_id, app)).await {
+        Some(Ok(enhanced)) => {
+            log_line!("[FIX] ✅ Fix complete: '{}'", enhanced);
+            translator::emit_job_complete(app, job_id);
         }
-        Err(e) => {
-            println!("[FIX] ❌ Fix error: {:?}", e);
-            let _ = app.emit("translation-error", e.to_string());
+        Some(Err(e)) => {
+            log_line!("[FIX] ❌ Fix error: {:?}", e);
+            translator::emit_job_error(app, job_id, e.into());
         }
+        None => log_line!("[FIX] Fix cancelled"),
     }
 }
 
+#[tauri::command]
+fn cancel_translation() -> Option<u64> {
+    translator::cancel_active_job()
+}
+
 #[tauri::command]
 async fn detect_language(text: String) -> Result<String, String> {
     let sample: String = text.chars().take(100).collect();
-    println!("[DETECT_LANG_CMD] Detecting language for text sample: '{}'", sample);
+    log_line!("[DETECT_LANG_CMD] Detecting language for text sample: '{}'", sample);
 
     match translator::detect_language(&text).await {
         Ok(lang) => {
-            println!("[DETECT_LANG_CMD] Detected language: {}", lang);
+            log_line!("[DETECT_LANG_CMD] Detected language: {}", lang);
             Ok(lang)
         }
         Err(e) => {
-            println!("[DETECT_LANG_CMD] Detection failed: {:?}", e);
+            log_line!("[DETECT_LANG_CMD] Detection failed: {:?}", e);
             Err(e.to_string())
         }
     }
 }
 
+#[tauri::command]
+fn detect_language_candidates(text: String) -> Vec<translator::LanguageCandidate> {
+    translator::detect_local(&text)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    if let Err(e) = backend_config::init_backend_config() {
+        eprintln!("[BACKEND] {}", e);
+        std::process::exit(2);
+    }
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_deep_link::init())
@@ -625,11 +740,11 @@ pub fn run() {
             let handle_clone = handle.clone();
             app.listen("deep-link://new-url", move |event| {
                 let payload = event.payload();
-                println!("[DEEP_LINK] Received event, payload: {}", payload);
+                log_line!("[DEEP_LINK] Received event, payload: {}", payload);
 
                 if let Ok(urls) = serde_json::from_str::<Vec<String>>(payload) {
                     for url_str in urls {
-                        println!("[DEEP_LINK] Received URL: {}", url_str);
+                        log_line!("[DEEP_LINK] Received URL: {}", url_str);
 
                         if let Ok(url) = url::Url::parse(&url_str) {
                             if url.scheme() == "langra" {
@@ -639,28 +754,28 @@ pub fn run() {
                                             .find(|(key, _)| key == "token")
                                             .map(|(_, value)| value.to_string())
                                         {
-                                            println!("[DEEP_LINK] Received token, saving...");
+                                            log_line!("[DEEP_LINK] Received token, saving...");
 
                                             let handle_for_save = handle_clone.clone();
                                             tauri::async_runtime::spawn(async move {
                                                 match save_access_token(token).await {
                                                     Ok(_) => {
-                                                        println!("[DEEP_LINK] Token saved successfully");
+                                                        log_line!("[DEEP_LINK] Token saved successfully");
                                                         let _ = handle_for_save.emit("auth-success", ());
                                                     }
                                                     Err(e) => {
-                                                        println!("[DEEP_LINK] Failed to save token: {}", e);
+                                                        log_line!("[DEEP_LINK] Failed to save token: {}", e);
                                                     }
                                                 }
                                             });
                                         }
                                     }
                                     Some("open") => {
-                                        println!("[DEEP_LINK] Opening Langra app...");
+                                        log_line!("[DEEP_LINK] Opening Langra app...");
                                         windows::show_translator_window(false);
                                     }
                                     _ => {
-                                        println!("[DEEP_LINK] Unknown host: {:?}", url.host_str());
+                                        log_line!("[DEEP_LINK] Unknown host: {:?}", url.host_str());
                                     }
                                 }
                             }
@@ -669,23 +784,23 @@ pub fn run() {
                 }
             });
 
-            println!("[SETUP] Creating translator window at startup...");
+            log_line!("[SETUP] Creating translator window at startup...");
             let window = windows::get_translator_window(false);
 
             let window_clone = window.clone();
             window.on_window_event(move |event| {
                 if let tauri::WindowEvent::CloseRequested { api, .. } = event {
-                    println!("[WINDOW] Close requested, hiding instead of quitting");
+                    log_line!("[WINDOW] Close requested, hiding instead of quitting");
                     window_clone.hide().unwrap();
                     api.prevent_close();
                 }
             });
 
             window.hide().unwrap();
-            println!("[SETUP] Translator window created and hidden");
+            log_line!("[SETUP] Translator window created and hidden");
 
             if let Err(e) = tray::create_tray(app.handle()) {
-                println!("[SETUP] Failed to create tray: {}", e);
+                log_line!("[SETUP] Failed to create tray: {}", e);
             }
 
             keyboard_monitor::start_listener(handle);
@@ -701,9 +816,11 @@ pub fn run() {
             show_translator_with_selected_text,
             retranslate,
             enhance_text,
+            cancel_translation,
             set_mode,
             get_mode,
             detect_language,
+            detect_language_candidates,
             insertion::insert_translation_into_previous_input,
             windows::hide_translator_window,
             windows::set_always_on_top,
@@ -711,7 +828,31 @@ pub fn run() {
             settings::get_settings,
             settings::save_settings,
             settings::save_api_keys,
+            settings::get_api_key_status,
+            settings::delete_api_keys,
+            backend_config::get_backend_url,
+            translator::glossary::get_glossaries,
+            translator::glossary::save_glossary,
+            translator::glossary::delete_glossary,
+            translator::glossary::import_glossary,
+            translator::glossary::export_glossary,
+            translator::cache::get_cache_stats,
+            translator::cache::get_cache_entries,
+            translator::cache::set_cache_limit,
+            translator::cache::clear_cache,
+            translator::stream_events::get_stream_snapshot,
+            translator::diff::apply_diff_hunks,
+            translator::memory::import_translation_memory,
+            translator::memory::export_translation_memory,
+            translator::memory::get_translation_memory_stats,
+            translator::memory::clear_translation_memory,
+            translator::usage::get_usage_summary,
+            translator::usage::clear_usage_records,
+            translator::batch::translate_files,
+            translator::batch::translate_locale_file,
+            translator::batch::cancel_batch_translation,
             enhance_text_with_instruction,
+            enhance_text_with_corrections,
             verify_access_token,
             save_access_token,
             get_access_token,
@@ -0,0 +1,28 @@
+use parking_lot::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogSink {
+    Stdout,
+    Stderr,
+    Silent,
+}
+
+static LOG_SINK: Mutex<LogSink> = Mutex::new(LogSink::Stdout);
+
+pub fn set_log_sink(sink: LogSink) {
+    *LOG_SINK.lock() = sink;
+}
+
+pub fn log_sink() -> LogSink {
+    *LOG_SINK.lock()
+}
+
+macro_rules! log_line {
+    ($($arg:tt)*) => {
+        match $crate::logging::log_sink() {
+            $crate::logging::LogSink::Stdout => println!($($arg)*),
+            $crate::logging::LogSink::Stderr => eprintln!($($arg)*),
+            $crate::logging::LogSink::Silent => {}
+        }
+    };
+}
@@ -4,7 +4,7 @@ use crate::APP_HANDLE;
 
 #[tauri::command]
 pub fn resize_window_to_normal() {
-    println!("[RESIZE] Resizing to normal mode (1000x800)");
+    log_line!("[RESIZE] Resizing to normal mode (1000x800)");
     if let Some(handle) = APP_HANDLE.get() {
         if let Some(window) = handle.get_webview_window(TRANSLATOR_WIN_NAME) {
             let _ = window.set_size(LogicalSize::new(1000.0, 800.0));
@@ -16,7 +16,7 @@ pub fn resize_window_to_normal() {
 
 #[tauri::command]
 pub fn resize_window_to_popup() {
-    println!("[RESIZE] Resizing to popup mode (420x280)");
+    log_line!("[RESIZE] Resizing to popup mode (420x280)");
     if let Some(handle) = APP_HANDLE.get() {
         if let Some(window) = handle.get_webview_window(TRANSLATOR_WIN_NAME) {
             let _ = window.set_size(LogicalSize::new(420.0, 280.0));
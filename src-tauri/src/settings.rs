@@ -1,41 +1,215 @@
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::io::Write;
 use std::path::PathBuf;
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageRule {
+    pub source: String,
+    pub target: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageStyle {
+    pub language: String,
+    pub style: String,
+    #[serde(default)]
+    pub register: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkSettings {
+    #[serde(default = "default_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+    #[serde(default = "default_first_byte_timeout_secs")]
+    pub first_byte_timeout_secs: u64,
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    #[serde(default = "default_proxy_mode")]
+    pub proxy_mode: String,
+    #[serde(default)]
+    pub proxy_url: String,
+    #[serde(default)]
+    pub ca_certificates: Vec<String>,
+}
+
+fn default_connect_timeout_secs() -> u64 {
+    10
+}
+
+fn default_first_byte_timeout_secs() -> u64 {
+    30
+}
+
+fn default_max_retries() -> u32 {
+    2
+}
+
+fn default_proxy_mode() -> String {
+    "system".to_string()
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        NetworkSettings {
+            connect_timeout_secs: default_connect_timeout_secs(),
+            first_byte_timeout_secs: default_first_byte_timeout_secs(),
+            max_retries: default_max_retries(),
+            proxy_mode: default_proxy_mode(),
+            proxy_url: String::new(),
+            ca_certificates: Vec::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
     pub style: String,
+    #[serde(default = "default_my_languages")]
+    pub my_languages: Vec<String>,
+    #[serde(default)]
+    pub language_rules: Vec<LanguageRule>,
+    #[serde(default)]
+    pub language_styles: Vec<LanguageStyle>,
+    #[serde(default = "default_cache_max_entries")]
+    pub cache_max_entries: usize,
+    #[serde(default)]
+    pub network: NetworkSettings,
+    #[serde(default)]
+    pub backend_url: String,
+    #[serde(default = "default_glossary_mode")]
+    pub glossary_mode: String,
+    #[serde(default = "default_mask_protected_text")]
+    pub mask_protected_text: bool,
+    #[serde(default)]
+    pub protected_patterns: Vec<String>,
+    #[serde(default = "default_chunk_max_chars")]
+    pub chunk_max_chars: usize,
+    #[serde(default = "default_max_parallel_requests")]
+    pub max_parallel_requests: usize,
+    #[serde(default = "default_translation_memory_enabled")]
+    pub translation_memory_enabled: bool,
+    #[serde(default = "default_memory_fuzzy_threshold")]
+    pub memory_fuzzy_threshold: u8,
+    #[serde(default)]
+    pub budget_limit_usd: f64,
+    #[serde(default = "default_budget_period")]
+    pub budget_period: String,
+    #[serde(default = "default_subtitle_max_line_chars")]
+    pub subtitle_max_line_chars: usize,
+}
+
+fn default_my_languages() -> Vec<String> {
+    vec!["de".to_string(), "en".to_string()]
+}
+
+fn default_cache_max_entries() -> usize {
+    1000
+}
+
+fn default_glossary_mode() -> String {
+    "fix".to_string()
+}
+
+fn default_mask_protected_text() -> bool {
+    true
+}
+
+fn default_chunk_max_chars() -> usize {
+    2000
+}
+
+fn default_max_parallel_requests() -> usize {
+    3
+}
+
+fn default_translation_memory_enabled() -> bool {
+    true
+}
+
+fn default_memory_fuzzy_threshold() -> u8 {
+    75
+}
+
+fn default_budget_period() -> String {
+    "monthly".to_string()
+}
+
+fn default_subtitle_max_line_chars() -> usize {
+    42
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Settings {
             style: "friendly".to_string(),
+            my_languages: default_my_languages(),
+            language_rules: Vec::new(),
+            language_styles: Vec::new(),
+            cache_max_entries: default_cache_max_entries(),
+            network: NetworkSettings::default(),
+            backend_url: String::new(),
+            glossary_mode: default_glossary_mode(),
+            mask_protected_text: default_mask_protected_text(),
+            protected_patterns: Vec::new(),
+            chunk_max_chars: default_chunk_max_chars(),
+            max_parallel_requests: default_max_parallel_requests(),
+            translation_memory_enabled: default_translation_memory_enabled(),
+            memory_fuzzy_threshold: default_memory_fuzzy_threshold(),
+            budget_limit_usd: 0.0,
+            budget_period: default_budget_period(),
+            subtitle_max_line_chars: default_subtitle_max_line_chars(),
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ApiKeySettings {
     pub provider: String,
     pub openai_api_key: String,
     pub azure_endpoint: String,
     pub azure_api_key: String,
     pub azure_deployment: String,
+    #[serde(default)]
+    pub openai_base_url: String,
+    #[serde(default)]
+    pub openai_model: String,
+    #[serde(default)]
+    pub deepl_api_key: String,
+    #[serde(default)]
+    pub libretranslate_url: String,
+    #[serde(default)]
+    pub libretranslate_api_key: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiKeyStatus {
+    pub provider: String,
+    pub has_credentials: bool,
 }
 
 static SETTINGS: Mutex<Option<Settings>> = Mutex::new(None);
+static API_KEYS: Mutex<Option<ApiKeySettings>> = Mutex::new(None);
 
-fn get_settings_path() -> PathBuf {
+pub fn get_config_file_path(file_name: &str) -> PathBuf {
     let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
     path.push("langra");
     fs::create_dir_all(&path).ok();
-    path.push("settings.json");
-    println!("[SETTINGS] Settings path: {:?}", path);
+    path.push(file_name);
     path
 }
 
+fn get_settings_path() -> PathBuf {
+    let path = get_config_file_path("settings.json");
+    log_line!("[SETTINGS] Settings path: {:?}", path);
+    path
+}
+
+fn get_api_keys_path() -> PathBuf {
+    get_config_file_path("api_keys.json")
+}
+
 pub fn load_settings() -> Settings {
     let mut settings_lock = SETTINGS.lock();
 
@@ -47,19 +221,17 @@ pub fn load_settings() -> Settings {
 
     let settings = if path.exists() {
         match fs::read_to_string(&path) {
-            Ok(content) => {
-                serde_json::from_str(&content).unwrap_or_else(|e| {
-                    println!("[SETTINGS] Failed to parse settings: {:?}", e);
-                    Settings::default()
-                })
-            }
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log_line!("[SETTINGS] Failed to parse settings: {:?}", e);
+                Settings::default()
+            }),
             Err(e) => {
-                println!("[SETTINGS] Failed to read settings file: {:?}", e);
+                log_line!("[SETTINGS] Failed to read settings file: {:?}", e);
                 Settings::default()
             }
         }
     } else {
-        println!("[SETTINGS] No settings file found, using defaults");
+        log_line!("[SETTINGS] No settings file found, using defaults");
         Settings::default()
     };
 
@@ -73,13 +245,12 @@ pub fn save_settings_to_disk(settings: &Settings) -> Result<(), String> {
     let json = serde_json::to_string_pretty(settings)
         .map_err(|e| format!("Failed to serialize settings: {}", e))?;
 
-    fs::write(&path, json)
-        .map_err(|e| format!("Failed to write settings: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write settings: {}", e))?;
 
     let mut settings_lock = SETTINGS.lock();
     *settings_lock = Some(settings.clone());
 
-    println!("[SETTINGS] Settings saved successfully");
+    log_line!("[SETTINGS] Settings saved successfully");
     Ok(())
 }
 
@@ -89,46 +260,111 @@ pub fn get_settings() -> Settings {
 }
 
 #[tauri::command]
-pub async fn save_settings(settings: Settings) -> Result<(), String> {
+pub async fn save_settings(mut settings: Settings) -> Result<(), String> {
+    if !settings.backend_url.trim().is_empty() {
+        settings.backend_url = crate::backend_config::validate_backend_url(&settings.backend_url)?;
+    }
+    crate::translator::protect::compile_user_patterns(&settings.protected_patterns)?;
+    if settings.chunk_max_chars < 200 {
+        return Err("Chunk size must be at least 200 characters".to_string());
+    }
+    if !(1..=8).contains(&settings.max_parallel_requests) {
+        return Err("Parallel requests must be between 1 and 8".to_string());
+    }
+    if !(50..=99).contains(&settings.memory_fuzzy_threshold) {
+        return Err("Fuzzy match threshold must be between 50% and 99%".to_string());
+    }
+    if !settings.budget_limit_usd.is_finite() || settings.budget_limit_usd < 0.0 {
+        return Err("Budget limit must be zero or a positive amount".to_string());
+    }
+    if !matches!(settings.budget_period.as_str(), "daily" | "monthly") {
+        return Err("Budget period must be daily or monthly".to_string());
+    }
+    if !(20..=80).contains(&settings.subtitle_max_line_chars) {
+        return Err("Subtitle line length must be between 20 and 80 characters".to_string());
+    }
+    crate::http::build_client(&settings.network)
+        .map_err(|e| format!("Invalid network settings: {:#}", e))?;
     save_settings_to_disk(&settings)?;
     Ok(())
 }
 
+pub fn load_api_keys() -> ApiKeySettings {
+    let mut api_keys_lock = API_KEYS.lock();
+
+    if let Some(cached_keys) = api_keys_lock.as_ref() {
+        return cached_keys.clone();
+    }
+
+    let path = get_api_keys_path();
+    let api_keys = match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log_line!("[SETTINGS] Failed to parse API keys: {:?}", e);
+            ApiKeySettings::default()
+        }),
+        Err(_) => ApiKeySettings::default(),
+    };
+
+    *api_keys_lock = Some(api_keys.clone());
+    api_keys
+}
+
+fn write_api_keys(api_settings: &ApiKeySettings) -> Result<(), String> {
+    let path = get_api_keys_path();
+
+    let json = serde_json::to_string_pretty(api_settings)
+        .map_err(|e| format!("Failed to serialize API keys: {}", e))?;
+
+    let temp_path = path.with_extension("json.tmp");
+    let _ = fs::remove_file(&temp_path);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let written = options.open(&temp_path).and_then(|mut file| {
+        file.write_all(json.as_bytes())?;
+        file.sync_all()
+    });
+    if let Err(e) = written.and_then(|_| fs::rename(&temp_path, &path)) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(format!("Failed to write API keys: {}", e));
+    }
+
+    *API_KEYS.lock() = Some(api_settings.clone());
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn save_api_keys(api_settings: ApiKeySettings) -> Result<(), String> {
-    let token = crate::get_access_token()
-        .map_err(|_| "You must be logged in to save API keys".to_string())?;
-
-    println!("[SETTINGS] Sending API credentials to backend");
+    log_line!(
+        "[SETTINGS] Validating {} credentials",
+        api_settings.provider
+    );
+    crate::translator::provider_from_settings(&api_settings).map_err(|e| e.to_string())?;
 
-    let client = reqwest::Client::new();
+    write_api_keys(&api_settings)?;
+    log_line!("[SETTINGS] ✅ API keys saved locally");
+    Ok(())
+}
 
-    let json_body = if api_settings.provider == "azure" {
-        serde_json::json!({
-            "azure_endpoint": api_settings.azure_endpoint,
-            "azure_api_key": api_settings.azure_api_key,
-            "azure_deployment": api_settings.azure_deployment,
-        })
-    } else {
-        serde_json::json!({
-            "openai_api_key": api_settings.openai_api_key,
-        })
+#[tauri::command]
+pub fn get_api_key_status() -> ApiKeyStatus {
+    let api_keys = load_api_keys();
+    let provider = match api_keys.provider.as_str() {
+        "" => "backend".to_string(),
+        provider => provider.to_string(),
     };
-
-    let response = client
-        .post("https://white-bush-0ea25dc03.3.azurestaticapps.net/api/credentials")
-        .header("Authorization", format!("Bearer {}", token))
-        .json(&json_body)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to connect to backend: {}", e))?;
-
-    if response.status().is_success() {
-        println!("[SETTINGS] ✅ API keys saved to backend");
-        Ok(())
-    } else {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        Err(format!("Failed to save API keys: {}", error_text))
+    ApiKeyStatus {
+        has_credentials: provider != "backend",
+        provider,
     }
 }
 
+#[tauri::command]
+pub fn delete_api_keys() -> Result<(), String> {
+    log_line!("[SETTINGS] Deleting local API keys");
+    write_api_keys(&ApiKeySettings::default())
+}
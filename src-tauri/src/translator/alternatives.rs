@@ -0,0 +1,108 @@
+use anyhow::Result;
+use serde::Serialize;
+
+use super::events::EventSink;
+use super::glossary;
+use super::memory;
+use super::protect;
+use super::providers::{active_provider, TranslateRequest, TranslationAlternative};
+use super::stream_events::{DeltaEmitter, StreamKind};
+use super::{AppliedStyle, LanguagePair, TranslatorError};
+use crate::settings::load_settings;
+
+const MIN_ALTERNATIVES: usize = 2;
+const MAX_ALTERNATIVES: usize = 5;
+
+#[derive(Debug, Clone, Serialize)]
+struct AlternativesPayload {
+    job_id: u64,
+    alternatives: Vec<TranslationAlternative>,
+}
+
+pub async fn translate_alternatives(
+    text: &str,
+    pair: &LanguagePair,
+    style: &AppliedStyle,
+    count: usize,
+    job_id: u64,
+    events: &dyn EventSink,
+) -> Result<Vec<TranslationAlternative>> {
+    let count = count.clamp(MIN_ALTERNATIVES, MAX_ALTERNATIVES);
+    log_line!(
+        "[ALTERNATIVES] Requesting {} alternatives for {} -> {}",
+        count,
+        pair.source,
+        pair.target
+    );
+
+    let glossary_mode = load_settings().glossary_mode;
+    let glossary_terms = match glossary_mode.as_str() {
+        "off" => Vec::new(),
+        _ => glossary::matching_terms(text, pair),
+    };
+    let masked = protect::mask_with_settings(text, &[]);
+
+    let provider = active_provider()?;
+    let candidates = provider
+        .alternatives(
+            TranslateRequest {
+                text: masked.text.clone(),
+                pair: pair.clone(),
+                style: style.clone(),
+                glossary: glossary_terms.clone(),
+                references: Vec::new(),
+                context: None,
+                notes: None,
+            },
+            count,
+        )
+        .await?;
+
+    let mut alternatives: Vec<TranslationAlternative> = Vec::new();
+    let mut last_error = None;
+    for candidate in candidates {
+        let (checked, _) =
+            glossary::enforce(&candidate.text, &glossary_terms, glossary_mode == "fix");
+        let restored = match masked.unmask(&checked) {
+            Ok(restored) => restored,
+            Err(e) => {
+                log_line!("[ALTERNATIVES] Dropping candidate: {}", e);
+                last_error = Some(e);
+                continue;
+            }
+        };
+        let checked = restored.trim().to_string();
+        if checked.is_empty() || alternatives.iter().any(|existing| existing.text == checked) {
+            continue;
+        }
+        alternatives.push(TranslationAlternative {
+            text: checked,
+            note: candidate.note.trim().to_string(),
+        });
+    }
+    alternatives.truncate(count);
+
+    let Some(best) = alternatives.first() else {
+        return Err(last_error
+            .unwrap_or_else(|| TranslatorError::BackendError {
+                status: None,
+                message: "The provider returned no alternatives".to_string(),
+            })
+            .into());
+    };
+    log_line!(
+        "[ALTERNATIVES] Received {} alternatives",
+        alternatives.len()
+    );
+
+    DeltaEmitter::new(events, job_id, StreamKind::Translation).finish(&best.text);
+    memory::remember_pending(job_id, text, pair);
+    events.emit(
+        "translation-alternatives",
+        AlternativesPayload {
+            job_id,
+            alternatives: alternatives.clone(),
+        },
+    );
+    Ok(alternatives)
+}
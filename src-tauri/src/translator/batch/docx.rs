@@ -0,0 +1,424 @@
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use regex::Regex;
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Write};
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use super::{split_joined, BatchDocument, Segment};
+
+static TRANSLATABLE_PART: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^word/(?:document|header\d*|footer\d*|footnotes|endnotes)\.xml$")
+        .expect("valid docx part pattern")
+});
+
+#[derive(Debug, Default)]
+struct RunGroup {
+    slots: Vec<usize>,
+    text: String,
+}
+
+#[derive(Debug, Default)]
+struct DocxParagraph {
+    groups: Vec<RunGroup>,
+}
+
+#[derive(Debug, Default)]
+struct RunPiece {
+    properties: Vec<u8>,
+    slots: Vec<usize>,
+    text: String,
+    break_before: bool,
+}
+
+struct OpenRun {
+    depth: usize,
+    properties: Vec<u8>,
+    pieces: Vec<RunPiece>,
+}
+
+struct DocxPart {
+    name: String,
+    xml: String,
+    paragraphs: Vec<DocxParagraph>,
+}
+
+pub(super) struct DocxDocument {
+    archive: Vec<u8>,
+    parts: Vec<DocxPart>,
+}
+
+fn is_translatable(paragraph: &DocxParagraph) -> bool {
+    paragraph
+        .groups
+        .iter()
+        .any(|group| group.text.chars().any(char::is_alphabetic))
+}
+
+fn group_runs(pieces: Vec<RunPiece>) -> DocxParagraph {
+    let mut groups: Vec<RunGroup> = Vec::new();
+    let mut previous: Option<Vec<u8>> = None;
+    let mut sealed = true;
+    for piece in pieces {
+        if piece.slots.is_empty() {
+            sealed = true;
+            continue;
+        }
+        let merge = !sealed && !piece.break_before && previous.as_ref() == Some(&piece.properties);
+        if !merge {
+            groups.push(RunGroup::default());
+        }
+        let group = groups.last_mut().expect("group pushed");
+        group.slots.extend(piece.slots);
+        group.text.push_str(&piece.text);
+        previous = Some(piece.properties);
+        sealed = false;
+    }
+    DocxParagraph { groups }
+}
+
+fn is_slot(name: &[u8]) -> bool {
+    name == b"w:t"
+}
+
+fn is_run_break(name: &[u8]) -> bool {
+    matches!(name, b"w:tab" | b"w:br" | b"w:cr" | b"w:ptab")
+}
+
+fn parse_part(xml: &str) -> Result<Vec<DocxParagraph>> {
+    let mut reader = Reader::from_str(xml);
+    let mut paragraphs = Vec::new();
+    let mut open_paragraphs: Vec<Vec<RunPiece>> = Vec::new();
+    let mut runs: Vec<OpenRun> = Vec::new();
+    let mut capture: Option<(usize, Writer<Vec<u8>>)> = None;
+    let mut in_text = false;
+    let mut depth = 0;
+    let mut slot = 0;
+
+    loop {
+        let event = reader.read_event()?;
+        if let Some((capture_depth, writer)) = capture.as_mut() {
+            writer.write_event(event.borrow())?;
+            match &event {
+                Event::Start(_) => *capture_depth += 1,
+                Event::End(_) => {
+                    *capture_depth -= 1;
+                    if *capture_depth == 0 {
+                        let (_, writer) = capture.take().expect("capture active");
+                        if let Some(run) = runs.last_mut() {
+                            run.properties = writer.into_inner();
+                        }
+                    }
+                    depth -= 1;
+                }
+                _ => {}
+            }
+            if let Event::Start(_) = &event {
+                depth += 1;
+            }
+            continue;
+        }
+
+        match &event {
+            Event::Start(element) => {
+                depth += 1;
+                let name = element.name();
+                match name.as_ref() {
+                    b"w:p" => open_paragraphs.push(Vec::new()),
+                    b"w:r" => runs.push(OpenRun {
+                        depth,
+                        properties: Vec::new(),
+                        pieces: vec![RunPiece::default()],
+                    }),
+                    b"w:rPr" if runs.last().is_some_and(|run| run.depth + 1 == depth) => {
+                        let mut writer = Writer::new(Vec::new());
+                        writer.write_event(event.borrow())?;
+                        capture = Some((1, writer));
+                    }
+                    name if is_slot(name) => {
+                        if let Some(run) = runs.last_mut() {
+                            run.pieces.last_mut().expect("run piece").slots.push(slot);
+                            in_text = true;
+                        }
+                        slot += 1;
+                    }
+                    name if is_run_break(name) => {
+                        if let Some(run) = runs.last_mut() {
+                            run.pieces.push(RunPiece {
+                                break_before: true,
+                                ..RunPiece::default()
+                            });
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Empty(element) => {
+                let name = element.name();
+                if is_slot(name.as_ref()) {
+                    if let Some(run) = runs.last_mut() {
+                        run.pieces.last_mut().expect("run piece").slots.push(slot);
+                    }
+                    slot += 1;
+                } else if is_run_break(name.as_ref()) {
+                    if let Some(run) = runs.last_mut() {
+                        run.pieces.push(RunPiece {
+                            break_before: true,
+                            ..RunPiece::default()
+                        });
+                    }
+                }
+            }
+            Event::Text(text) if in_text => {
+                if let Some(run) = runs.last_mut() {
+                    let piece = run.pieces.last_mut().expect("run piece");
+                    piece.text.push_str(&text.unescape()?);
+                }
+            }
+            Event::CData(text) if in_text => {
+                if let Some(run) = runs.last_mut() {
+                    let piece = run.pieces.last_mut().expect("run piece");
+                    piece.text.push_str(&String::from_utf8_lossy(text));
+                }
+            }
+            Event::End(element) => {
+                depth -= 1;
+                match element.name().as_ref() {
+                    b"w:t" => in_text = false,
+                    b"w:r" => {
+                        if let Some(run) = runs.pop() {
+                            if let Some(paragraph) = open_paragraphs.last_mut() {
+                                paragraph.extend(run.pieces.into_iter().map(|mut piece| {
+                                    piece.properties = run.properties.clone();
+                                    piece
+                                }));
+                            }
+                        }
+                    }
+                    b"w:p" => {
+                        if let Some(pieces) = open_paragraphs.pop() {
+                            paragraphs.push(group_runs(pieces));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(paragraphs)
+}
+
+fn paragraph_segment(paragraph: &DocxParagraph) -> Segment {
+    let pieces: Vec<&str> = paragraph
+        .groups
+        .iter()
+        .filter(|group| !group.text.is_empty())
+        .map(|group| group.text.as_str())
+        .collect();
+    Segment::joined(&pieces)
+}
+
+fn preserve_space(element: &BytesStart) -> BytesStart<'static> {
+    let mut element = element.to_owned();
+    let has_space = element
+        .attributes()
+        .flatten()
+        .any(|attribute| attribute.key.as_ref() == b"xml:space");
+    if !has_space {
+        element.push_attribute(("xml:space", "preserve"));
+    }
+    element
+}
+
+fn rewrite_part(xml: &str, replacements: &HashMap<usize, String>) -> Result<String> {
+    let mut reader = Reader::from_str(xml);
+    let mut writer = Writer::new(Vec::with_capacity(xml.len()));
+    let mut skipping = false;
+    let mut slot = 0;
+
+    loop {
+        let event = reader.read_event()?;
+        match &event {
+            Event::Eof => break,
+            Event::Start(element) if is_slot(element.name().as_ref()) => {
+                if let Some(text) = replacements.get(&slot) {
+                    writer.write_event(Event::Start(preserve_space(element)))?;
+                    writer.write_event(Event::Text(BytesText::new(text)))?;
+                    skipping = true;
+                } else {
+                    writer.write_event(event.borrow())?;
+                }
+                slot += 1;
+            }
+            Event::Empty(element) if is_slot(element.name().as_ref()) => {
+                if let Some(text) = replacements.get(&slot) {
+                    writer.write_event(Event::Start(preserve_space(element)))?;
+                    writer.write_event(Event::Text(BytesText::new(text)))?;
+                    writer.write_event(Event::End(BytesEnd::new("w:t")))?;
+                } else {
+                    writer.write_event(event.borrow())?;
+                }
+                slot += 1;
+            }
+            Event::End(element) if skipping && is_slot(element.name().as_ref()) => {
+                skipping = false;
+                writer.write_event(event.borrow())?;
+            }
+            _ if skipping => {}
+            _ => writer.write_event(event.borrow())?,
+        }
+    }
+    Ok(String::from_utf8(writer.into_inner())?)
+}
+
+impl DocxDocument {
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let mut archive =
+            ZipArchive::new(Cursor::new(bytes)).context("File is not a valid DOCX archive")?;
+        let mut parts = Vec::new();
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index)?;
+            let name = file.name().to_string();
+            if !TRANSLATABLE_PART.is_match(&name) {
+                continue;
+            }
+            let mut xml = String::new();
+            file.read_to_string(&mut xml)?;
+            let paragraphs =
+                parse_part(&xml).with_context(|| format!("Failed to parse {}", name))?;
+            parts.push(DocxPart {
+                name,
+                xml,
+                paragraphs,
+            });
+        }
+        if !parts.iter().any(|part| part.name == "word/document.xml") {
+            anyhow::bail!("DOCX archive has no word/document.xml");
+        }
+        Ok(DocxDocument {
+            archive: bytes.to_vec(),
+            parts,
+        })
+    }
+}
+
+impl BatchDocument for DocxDocument {
+    fn segments(&self) -> Vec<Segment> {
+        self.parts
+            .iter()
+            .flat_map(|part| part.paragraphs.iter())
+            .filter(|paragraph| is_translatable(paragraph))
+            .map(paragraph_segment)
+            .collect()
+    }
+
+    fn render(&self, translations: &[String]) -> Result<Vec<u8>> {
+        let mut translations = translations.iter();
+        let mut rewritten: HashMap<&str, String> = HashMap::new();
+        for part in &self.parts {
+            let mut replacements = HashMap::new();
+            for paragraph in part.paragraphs.iter().filter(|p| is_translatable(p)) {
+                let Some(translated) = translations.next() else {
+                    break;
+                };
+                let groups: Vec<&RunGroup> = paragraph
+                    .groups
+                    .iter()
+                    .filter(|group| !group.text.is_empty())
+                    .collect();
+                for (group, text) in groups.iter().zip(split_joined(translated, groups.len())) {
+                    for (position, slot) in group.slots.iter().enumerate() {
+                        let text = if position == 0 {
+                            text.clone()
+                        } else {
+                            String::new()
+                        };
+                        replacements.insert(*slot, text);
+                    }
+                }
+            }
+            rewritten.insert(&part.name, rewrite_part(&part.xml, &replacements)?);
+        }
+
+        let mut archive = ZipArchive::new(Cursor::new(self.archive.as_slice()))?;
+        let mut writer = ZipWriter::new(Cursor::new(Vec::with_capacity(self.archive.len())));
+        for index in 0..archive.len() {
+            let file = archive.by_index_raw(index)?;
+            match rewritten.get(file.name()) {
+                Some(xml) => {
+                    let options =
+                        SimpleFileOptions::default().compression_method(file.compression());
+                    writer.start_file(file.name(), options)?;
+                    writer.write_all(xml.as_bytes())?;
+                }
+                None => writer.raw_copy_file(file)?,
+            }
+        }
+        Ok(writer.finish()?.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::marker;
+    use super::*;
+
+    const DOCUMENT: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body><w:p><w:r><w:t xml:space="preserve">Hello </w:t></w:r><w:r><w:rPr><w:b/></w:rPr><w:t>bold</w:t></w:r><w:r><w:t xml:space="preserve"> wor</w:t></w:r><w:r><w:t>ld</w:t></w:r></w:p><w:tbl><w:tr><w:tc><w:p><w:r><w:t>Cell</w:t></w:r></w:p></w:tc></w:tr></w:tbl><w:p><w:r><w:t>42</w:t></w:r></w:p></w:body></w:document>"#;
+
+    fn docx(document: &str) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file("[Content_Types].xml", SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"<Types/>").unwrap();
+        writer
+            .start_file("word/document.xml", SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(document.as_bytes()).unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn runs_with_same_formatting_are_merged() {
+        let document = DocxDocument::parse(&docx(DOCUMENT)).unwrap();
+        let segments = document.segments();
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(
+            segments[0].text,
+            format!("Hello {}bold{} world", marker(1), marker(2))
+        );
+        assert_eq!(segments[0].protected.len(), 2);
+        assert_eq!(segments[1].text, "Cell");
+    }
+
+    #[test]
+    fn render_keeps_runs_and_tables() {
+        let document = DocxDocument::parse(&docx(DOCUMENT)).unwrap();
+        let translations = vec![
+            format!("Hallo {}fette{} Welt", marker(1), marker(2)),
+            "Zelle".to_string(),
+        ];
+        let rendered = document.render(&translations).unwrap();
+
+        let mut archive = ZipArchive::new(Cursor::new(rendered)).unwrap();
+        let mut xml = String::new();
+        archive
+            .by_name("word/document.xml")
+            .unwrap()
+            .read_to_string(&mut xml)
+            .unwrap();
+        assert!(xml.contains(r#"<w:t xml:space="preserve">Hallo </w:t>"#));
+        assert!(xml.contains(r#"<w:rPr><w:b/></w:rPr><w:t xml:space="preserve">fette</w:t>"#));
+        assert!(xml.contains(r#"<w:t xml:space="preserve"> Welt</w:t>"#));
+        assert!(xml.contains(r#"<w:t xml:space="preserve"></w:t>"#));
+        assert!(xml.contains(r#"<w:tc><w:p><w:r><w:t xml:space="preserve">Zelle</w:t>"#));
+        assert!(xml.contains("<w:t>42</w:t>"));
+        assert!(archive.by_name("[Content_Types].xml").is_ok());
+    }
+}
@@ -0,0 +1,1193 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use super::{
+    output_path, placeholder_ranges, translate_segments, BatchStage, FileProgress, Segment,
+};
+use crate::settings::load_settings;
+use crate::translator::languages::normalize_code;
+use crate::translator::{detect_language, resolve_style, resolve_with_override, TranslatorError};
+
+const DETECTION_SAMPLE_CHARS: usize = 2000;
+
+static LOCALE_CODE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[a-z]{2}(?:[-_][A-Za-z]{2,4})?$").expect("valid locale code pattern")
+});
+
+static FLUENT_ENTRY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(-?[A-Za-z][A-Za-z0-9_-]*)[ \t]*=").expect("valid fluent entry pattern")
+});
+
+static FLUENT_SYNTAX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\n[ \t]*(?:\.[A-Za-z][A-Za-z0-9_-]*[ \t]*=[ \t]*|\*?\[[^\]\n]*\][ \t]*|\}[ \t]*)?|\{[^{}\n]*->[ \t]*|\{[^{}]*\}",
+    )
+    .expect("valid fluent syntax pattern")
+});
+
+static YAML_KEY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"^( *)("(?:[^"\\]|\\.)*"|'(?:[^']|'')*'|[^\s#'"\[\]{}&*!|>%@`-][^#]*?|-[^\s#][^#]*?)[ \t]*(:)(?:[ \t]|$)"#,
+    )
+    .expect("valid yaml key pattern")
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LocaleFormat {
+    Json,
+    Yaml,
+    Fluent,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LocaleKeyChange {
+    pub key: String,
+    pub source: String,
+    pub translation: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LocaleFileResult {
+    pub output_path: String,
+    pub added: Vec<LocaleKeyChange>,
+    pub kept: usize,
+}
+
+#[derive(Debug, Clone)]
+struct MissingKey {
+    key: String,
+    pointer: String,
+    segment: Segment,
+}
+
+#[derive(Debug, Clone)]
+enum FluentItem {
+    Entry { id: String, lines: Vec<String> },
+    Other(String),
+}
+
+enum Resource {
+    Tree(Value),
+    Fluent(Vec<FluentItem>),
+}
+
+struct MergedResource {
+    resource: Resource,
+    root: Option<String>,
+    missing: Vec<MissingKey>,
+    kept: usize,
+    indent: String,
+    existing_yaml: Option<String>,
+}
+
+#[derive(Debug)]
+struct YamlKey {
+    path: Vec<String>,
+    line: usize,
+    indent: usize,
+    colon: usize,
+    end: usize,
+}
+
+struct YamlText {
+    lines: Vec<String>,
+    keys: Vec<YamlKey>,
+    unit: usize,
+}
+
+struct YamlEdits {
+    inserts: Vec<(usize, Vec<String>)>,
+    replacements: Vec<(usize, Vec<String>)>,
+}
+
+fn locale_format(path: &Path) -> Option<LocaleFormat> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    match extension.as_str() {
+        "json" => Some(LocaleFormat::Json),
+        "yaml" | "yml" => Some(LocaleFormat::Yaml),
+        "ftl" => Some(LocaleFormat::Fluent),
+        _ => None,
+    }
+}
+
+pub(super) fn is_locale_file(path: &Path) -> bool {
+    locale_format(path).is_some()
+}
+
+fn path_language(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    LOCALE_CODE.is_match(stem).then(|| stem.to_string())
+}
+
+fn target_path(path: &Path, source_lang: &str, language: &str) -> PathBuf {
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if path_language(path).is_some() {
+        return path.with_file_name(format!("{}.{}", language, extension));
+    }
+    let parent = path.parent();
+    let directory = parent
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let is_locale_directory = LOCALE_CODE.is_match(&directory)
+        && normalize_code(&directory) == normalize_code(source_lang);
+    match (parent.and_then(Path::parent), path.file_name()) {
+        (Some(root), Some(name)) if is_locale_directory => root.join(language).join(name),
+        _ => output_path(path, language, None),
+    }
+}
+
+fn icu_close(text: &str, from: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, byte) in text.bytes().enumerate().skip(from) {
+        match byte {
+            b'{' => depth += 1,
+            b'}' if depth <= 1 => return Some(index),
+            b'}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn icu_message(
+    text: &str,
+    mut index: usize,
+    plural: bool,
+    ranges: &mut Vec<Range<usize>>,
+) -> usize {
+    let bytes = text.as_bytes();
+    while index < bytes.len() {
+        match bytes[index] {
+            b'{' => index = icu_argument(text, index, ranges),
+            b'}' => return index,
+            b'#' if plural => {
+                ranges.push(index..index + 1);
+                index += 1;
+            }
+            b'\'' => match bytes.get(index + 1) {
+                Some(b'{' | b'}' | b'#') => {
+                    let close = text[index + 1..]
+                        .find('\'')
+                        .map(|offset| index + 1 + offset);
+                    index = close.map_or(bytes.len(), |close| close + 1);
+                }
+                _ => index += 1,
+            },
+            _ => index += 1,
+        }
+    }
+    index
+}
+
+fn icu_argument(text: &str, open: usize, ranges: &mut Vec<Range<usize>>) -> usize {
+    let Some(header_end) = text[open + 1..]
+        .find(['{', '}'])
+        .map(|offset| open + 1 + offset)
+    else {
+        ranges.push(open..text.len());
+        return text.len();
+    };
+    if text.as_bytes()[header_end] == b'}' {
+        ranges.push(open..header_end + 1);
+        return header_end + 1;
+    }
+
+    let kind = text[open + 1..header_end]
+        .split(',')
+        .nth(1)
+        .map(str::trim)
+        .unwrap_or_default();
+    if !matches!(kind, "plural" | "select" | "selectordinal") {
+        let close = icu_close(text, open).unwrap_or(text.len() - 1);
+        ranges.push(open..close + 1);
+        return close + 1;
+    }
+
+    let mut range_start = open;
+    let mut submessage = header_end;
+    loop {
+        ranges.push(range_start..submessage + 1);
+        let close = icu_message(text, submessage + 1, kind != "select", ranges);
+        if close >= text.len() {
+            return text.len();
+        }
+        range_start = close;
+        match text[close + 1..]
+            .find(['{', '}'])
+            .map(|offset| close + 1 + offset)
+        {
+            Some(next) if text.as_bytes()[next] == b'{' => submessage = next,
+            Some(next) => {
+                ranges.push(range_start..next + 1);
+                return next + 1;
+            }
+            None => {
+                ranges.push(range_start..text.len());
+                return text.len();
+            }
+        }
+    }
+}
+
+fn message_segment(text: &str) -> Segment {
+    let mut protected = Vec::new();
+    icu_message(text, 0, false, &mut protected);
+    let placeholders: Vec<Range<usize>> = placeholder_ranges(text)
+        .into_iter()
+        .filter(|range| {
+            !protected
+                .iter()
+                .any(|icu: &Range<usize>| icu.start < range.end && range.start < icu.end)
+        })
+        .collect();
+    protected.extend(placeholders);
+    protected.retain(|range| !range.is_empty());
+    protected.sort_by_key(|range| range.start);
+    Segment {
+        text: text.to_string(),
+        protected,
+        notes: None,
+    }
+}
+
+fn fluent_segment(text: &str) -> Segment {
+    Segment {
+        text: text.to_string(),
+        protected: FLUENT_SYNTAX
+            .find_iter(text)
+            .map(|syntax| syntax.range())
+            .collect(),
+        notes: None,
+    }
+}
+
+fn needs_translation(segment: &Segment) -> bool {
+    let mut visible = segment.text.clone();
+    for range in segment.protected.iter().rev() {
+        visible.replace_range(range.clone(), " ");
+    }
+    visible.chars().any(char::is_alphabetic)
+}
+
+fn pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn child_key(parent: &str, key: &str) -> String {
+    match parent.is_empty() {
+        true => key.to_string(),
+        false => format!("{}.{}", parent, key),
+    }
+}
+
+fn is_missing(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => true,
+        Some(Value::String(text)) => text.trim().is_empty(),
+        Some(_) => false,
+    }
+}
+
+fn merge_tree(
+    source: &Value,
+    target: Option<&Value>,
+    pointer: &str,
+    key: &str,
+    missing: &mut Vec<MissingKey>,
+    kept: &mut usize,
+) -> Value {
+    let target = target.filter(|target| !target.is_null());
+    match (source, target) {
+        (Value::Object(source), Some(Value::Object(target))) => {
+            let mut merged = Map::new();
+            for (name, value) in source {
+                let child = merge_tree(
+                    value,
+                    target.get(name),
+                    &format!("{}/{}", pointer, pointer_token(name)),
+                    &child_key(key, name),
+                    missing,
+                    kept,
+                );
+                merged.insert(name.clone(), child);
+            }
+            for (name, value) in target {
+                if !merged.contains_key(name) {
+                    merged.insert(name.clone(), value.clone());
+                }
+            }
+            Value::Object(merged)
+        }
+        (Value::Object(source), None) => Value::Object(
+            source
+                .iter()
+                .map(|(name, value)| {
+                    let child = merge_tree(
+                        value,
+                        None,
+                        &format!("{}/{}", pointer, pointer_token(name)),
+                        &child_key(key, name),
+                        missing,
+                        kept,
+                    );
+                    (name.clone(), child)
+                })
+                .collect(),
+        ),
+        (Value::Array(source), None) => Value::Array(
+            source
+                .iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    merge_tree(
+                        value,
+                        None,
+                        &format!("{}/{}", pointer, index),
+                        &format!("{}[{}]", key, index),
+                        missing,
+                        kept,
+                    )
+                })
+                .collect(),
+        ),
+        (Value::String(text), target) if is_missing(target) => {
+            let segment = message_segment(text);
+            if needs_translation(&segment) {
+                missing.push(MissingKey {
+                    key: key.to_string(),
+                    pointer: pointer.to_string(),
+                    segment,
+                });
+            }
+            Value::String(text.clone())
+        }
+        (source, target) if is_missing(target) => source.clone(),
+        (_, Some(target)) => {
+            *kept += 1;
+            target.clone()
+        }
+        (source, None) => source.clone(),
+    }
+}
+
+fn locale_root(value: &Value, language: &str) -> Option<String> {
+    let Value::Object(map) = value else {
+        return None;
+    };
+    let (key, _) = map.iter().next().filter(|_| map.len() == 1)?;
+    (LOCALE_CODE.is_match(key) && normalize_code(key) == normalize_code(language))
+        .then(|| key.clone())
+}
+
+fn parse_tree(format: LocaleFormat, bytes: &[u8]) -> Result<Value> {
+    let text = std::str::from_utf8(bytes)?;
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let value = match format {
+        LocaleFormat::Yaml => serde_yaml_ng::from_str(text)?,
+        _ => serde_json::from_str(text)?,
+    };
+    Ok(value)
+}
+
+fn json_indent(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .lines()
+        .skip(1)
+        .find(|line| !line.trim().is_empty())
+        .map(|line| {
+            line.chars()
+                .take_while(|c| *c == ' ' || *c == '\t')
+                .collect::<String>()
+        })
+        .filter(|indent| !indent.is_empty())
+        .unwrap_or_else(|| "  ".to_string())
+}
+
+fn parse_fluent(bytes: &[u8]) -> Result<Vec<FluentItem>> {
+    let text = std::str::from_utf8(bytes)?;
+    let text = text
+        .strip_prefix('\u{feff}')
+        .unwrap_or(text)
+        .replace("\r\n", "\n");
+    let mut items = Vec::new();
+    for line in text.lines() {
+        let continues = line.starts_with([' ', '\t']) && !line.trim().is_empty();
+        match items.last_mut() {
+            Some(FluentItem::Entry { lines, .. }) if continues => lines.push(line.to_string()),
+            _ => match FLUENT_ENTRY.captures(line) {
+                Some(captures) => items.push(FluentItem::Entry {
+                    id: captures[1].to_string(),
+                    lines: vec![line.to_string()],
+                }),
+                None => items.push(FluentItem::Other(line.to_string())),
+            },
+        }
+    }
+    Ok(items)
+}
+
+fn fluent_value(lines: &[String]) -> (String, String) {
+    let text = lines.join("\n");
+    let equals = text.find('=').map_or(text.len(), |index| index + 1);
+    let value = &text[equals..];
+    let trimmed = value.trim_start_matches([' ', '\t']);
+    let prefix = &text[..text.len() - trimmed.len()];
+    (prefix.to_string(), trimmed.to_string())
+}
+
+fn fluent_ids(items: &[FluentItem]) -> Vec<&str> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            FluentItem::Entry { id, .. } => Some(id.as_str()),
+            FluentItem::Other(_) => None,
+        })
+        .collect()
+}
+
+fn merge_fluent(
+    source: &[FluentItem],
+    target: Option<Vec<FluentItem>>,
+    missing: &mut Vec<MissingKey>,
+    kept: &mut usize,
+) -> Vec<FluentItem> {
+    let Some(mut merged) = target else {
+        for item in source {
+            if let FluentItem::Entry { id, lines } = item {
+                let segment = fluent_segment(&fluent_value(lines).1);
+                if needs_translation(&segment) {
+                    missing.push(MissingKey {
+                        key: id.clone(),
+                        pointer: id.clone(),
+                        segment,
+                    });
+                }
+            }
+        }
+        return source.to_vec();
+    };
+
+    let mut previous: Option<&str> = None;
+    for item in source {
+        let FluentItem::Entry { id, lines } = item else {
+            continue;
+        };
+        if fluent_ids(&merged).contains(&id.as_str()) {
+            *kept += 1;
+            previous = Some(id);
+            continue;
+        }
+        let position = match previous {
+            Some(previous) => merged
+                .iter()
+                .position(|item| matches!(item, FluentItem::Entry { id, .. } if id == previous)),
+            None => None,
+        };
+        let insert_at = match position {
+            Some(position) => position + 1,
+            None => merged
+                .iter()
+                .position(|item| matches!(item, FluentItem::Entry { .. }))
+                .unwrap_or(merged.len()),
+        };
+        merged.insert(insert_at, item.clone());
+        let segment = fluent_segment(&fluent_value(lines).1);
+        if needs_translation(&segment) {
+            missing.push(MissingKey {
+                key: id.clone(),
+                pointer: id.clone(),
+                segment,
+            });
+        }
+        previous = Some(id);
+    }
+    merged
+}
+
+fn yaml_unquote(key: &str) -> String {
+    match key.chars().next() {
+        Some('"') => serde_json::from_str(key).unwrap_or_else(|_| key.to_string()),
+        Some('\'') => key[1..key.len() - 1].replace("''", "'"),
+        _ => key.trim().to_string(),
+    }
+}
+
+fn is_yaml_content(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    !trimmed.is_empty() && !trimmed.starts_with('#')
+}
+
+fn line_indent(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+impl YamlText {
+    fn parse(text: &str) -> Self {
+        let lines: Vec<String> = text.lines().map(str::to_string).collect();
+        let mut keys: Vec<YamlKey> = Vec::new();
+        let mut stack: Vec<(usize, Vec<String>)> = Vec::new();
+        let mut block_scalar: Option<usize> = None;
+        for (number, line) in lines.iter().enumerate() {
+            if !is_yaml_content(line) {
+                continue;
+            }
+            let indent = line_indent(line);
+            if let Some(owner) = block_scalar {
+                if indent > owner {
+                    continue;
+                }
+                block_scalar = None;
+            }
+            let Some(captures) = YAML_KEY.captures(line) else {
+                continue;
+            };
+            while stack.last().is_some_and(|(parent, _)| *parent >= indent) {
+                stack.pop();
+            }
+            let mut path = stack
+                .last()
+                .map(|(_, path)| path.clone())
+                .unwrap_or_default();
+            path.push(yaml_unquote(&captures[2]));
+            let colon = captures.get(3).map_or(line.len(), |colon| colon.end());
+            if line[colon..].trim_start().starts_with(['|', '>']) {
+                block_scalar = Some(indent);
+            }
+            keys.push(YamlKey {
+                path: path.clone(),
+                line: number,
+                indent,
+                colon,
+                end: number,
+            });
+            stack.push((indent, path));
+        }
+
+        for key in keys.iter_mut() {
+            for (number, line) in lines.iter().enumerate().skip(key.line + 1) {
+                if !is_yaml_content(line) {
+                    continue;
+                }
+                let indent = line_indent(line);
+                let item = line.trim_start().starts_with('-');
+                if indent > key.indent || (indent == key.indent && item) {
+                    key.end = number;
+                } else {
+                    break;
+                }
+            }
+        }
+
+        let unit = keys
+            .iter()
+            .map(|key| key.indent)
+            .filter(|indent| *indent > 0)
+            .min()
+            .unwrap_or(2);
+        YamlText { lines, keys, unit }
+    }
+
+    fn find(&self, path: &[String]) -> Option<&YamlKey> {
+        self.keys.iter().find(|key| key.path == path)
+    }
+
+    fn first_child(&self, path: &[String]) -> Option<&YamlKey> {
+        self.keys
+            .iter()
+            .find(|key| key.path.len() == path.len() + 1 && key.path.starts_with(path))
+    }
+
+    fn edits(
+        &self,
+        map: &Map<String, Value>,
+        path: &mut Vec<String>,
+        missing: &HashSet<Vec<String>>,
+        edits: &mut YamlEdits,
+    ) -> Result<()> {
+        let parent = self.find(path);
+        let first_child = self.first_child(path);
+        let indent = match (first_child, parent) {
+            (Some(child), _) => child.indent,
+            (None, Some(parent)) => parent.indent + self.unit,
+            (None, None) => 0,
+        };
+        let mut insert_at = match (parent, first_child) {
+            (Some(parent), _) => parent.line + 1,
+            (None, Some(child)) => child.line,
+            (None, None) => self.lines.len(),
+        };
+
+        for (name, value) in map {
+            path.push(name.clone());
+            match self.find(path) {
+                Some(existing) => {
+                    if missing.contains(path.as_slice()) {
+                        edits
+                            .replacements
+                            .push((existing.line, self.replace_value(existing, value)?));
+                    } else if let Value::Object(children) = value {
+                        self.edits(children, path, missing, edits)?;
+                    }
+                    insert_at = existing.end + 1;
+                }
+                None => {
+                    let mut block = Map::new();
+                    block.insert(name.clone(), value.clone());
+                    let padding = " ".repeat(indent);
+                    let lines = serde_yaml_ng::to_string(&block)?
+                        .lines()
+                        .map(|line| format!("{}{}", padding, line))
+                        .collect();
+                    edits.inserts.push((insert_at, lines));
+                }
+            }
+            path.pop();
+        }
+        Ok(())
+    }
+
+    fn replace_value(&self, key: &YamlKey, value: &Value) -> Result<Vec<String>> {
+        let line = &self.lines[key.line];
+        let rest = &line[key.colon..];
+        let comment = rest
+            .find(" #")
+            .or_else(|| rest.find("\t#"))
+            .map(|position| rest[position..].trim_start());
+        let serialized = serde_yaml_ng::to_string(value)?;
+        let mut lines: Vec<String> = serialized.lines().map(str::to_string).collect();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines[0] = match comment {
+            Some(comment) => format!("{} {} {}", &line[..key.colon], lines[0], comment),
+            None => format!("{} {}", &line[..key.colon], lines[0]),
+        };
+        let padding = " ".repeat(key.indent);
+        for line in lines.iter_mut().skip(1) {
+            line.insert_str(0, &padding);
+        }
+        Ok(lines)
+    }
+
+    fn apply(&self, edits: YamlEdits) -> String {
+        let mut output: Vec<String> = Vec::new();
+        for number in 0..=self.lines.len() {
+            for (_, lines) in edits.inserts.iter().filter(|(at, _)| *at == number) {
+                output.extend(lines.iter().cloned());
+            }
+            if number == self.lines.len() {
+                break;
+            }
+            match edits.replacements.iter().find(|(line, _)| *line == number) {
+                Some((_, lines)) => output.extend(lines.iter().cloned()),
+                None => output.push(self.lines[number].clone()),
+            }
+        }
+        let mut text = output.join("\n");
+        text.push('\n');
+        text
+    }
+}
+
+fn json_path(pointer: &str) -> Vec<String> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+impl MergedResource {
+    fn build(
+        format: LocaleFormat,
+        source: &[u8],
+        existing: Option<&[u8]>,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> Result<Self> {
+        let mut missing = Vec::new();
+        let mut kept = 0;
+        if format == LocaleFormat::Fluent {
+            let source = parse_fluent(source)?;
+            let target = existing.map(parse_fluent).transpose()?;
+            let resource = merge_fluent(&source, target, &mut missing, &mut kept);
+            return Ok(MergedResource {
+                resource: Resource::Fluent(resource),
+                root: None,
+                missing,
+                kept,
+                indent: String::new(),
+                existing_yaml: None,
+            });
+        }
+
+        let indent = json_indent(existing.unwrap_or(source));
+        let mut source = parse_tree(format, source)?;
+        let mut target = existing
+            .map(|existing| parse_tree(format, existing))
+            .transpose()?;
+        let root = locale_root(&source, source_lang).map(|source_root| {
+            if let Value::Object(map) = &mut source {
+                source = map.remove(&source_root).unwrap_or(Value::Null);
+            }
+            let target_root = target
+                .as_ref()
+                .and_then(|target| locale_root(target, target_lang))
+                .unwrap_or_else(|| target_lang.to_string());
+            if let Some(Value::Object(map)) = &mut target {
+                if let Some(inner) = map.remove(&target_root) {
+                    target = Some(inner);
+                }
+            }
+            target_root
+        });
+        let resource = merge_tree(&source, target.as_ref(), "", "", &mut missing, &mut kept);
+        Ok(MergedResource {
+            resource: Resource::Tree(resource),
+            root,
+            missing,
+            kept,
+            indent,
+            existing_yaml: existing
+                .filter(|_| format == LocaleFormat::Yaml)
+                .map(|existing| String::from_utf8_lossy(existing).into_owned()),
+        })
+    }
+
+    fn edit_yaml(&self, existing: &str, value: &Value) -> Result<Option<String>> {
+        let bom = existing.starts_with('\u{feff}');
+        let crlf = existing.contains("\r\n");
+        let text = YamlText::parse(existing.strip_prefix('\u{feff}').unwrap_or(existing));
+        let original = parse_tree(LocaleFormat::Yaml, existing.as_bytes())?;
+        if let Some(root) = &self.root {
+            if text.find(std::slice::from_ref(root)).is_none() {
+                return Ok(None);
+            }
+        }
+        let Value::Object(map) = value else {
+            return Ok(None);
+        };
+
+        let prefix: Vec<String> = self.root.iter().cloned().collect();
+        let missing: HashSet<Vec<String>> = self
+            .missing
+            .iter()
+            .map(|missing| {
+                let mut path = prefix.clone();
+                path.extend(json_path(&missing.pointer));
+                path
+            })
+            .collect();
+        let mut edits = YamlEdits {
+            inserts: Vec::new(),
+            replacements: Vec::new(),
+        };
+        text.edits(map, &mut Vec::new(), &missing, &mut edits)?;
+
+        let mut inserted = Vec::new();
+        for key in map.keys() {
+            collect_absent(&text, map, &mut vec![key.clone()], &mut inserted);
+        }
+        let clashes = inserted.iter().any(|path: &Vec<String>| {
+            let pointer: String = path
+                .iter()
+                .map(|key| format!("/{}", pointer_token(key)))
+                .collect();
+            original.pointer(&pointer).is_some()
+        });
+        if clashes {
+            return Ok(None);
+        }
+
+        let mut output = text.apply(edits);
+        if crlf {
+            output = output.replace('\n', "\r\n");
+        }
+        if bom {
+            output.insert(0, '\u{feff}');
+        }
+        Ok(Some(output))
+    }
+
+    fn render(&self, format: LocaleFormat, translations: &[String]) -> Result<Vec<u8>> {
+        match &self.resource {
+            Resource::Fluent(items) => {
+                let mut items = items.clone();
+                for (missing, translated) in self.missing.iter().zip(translations) {
+                    for item in items.iter_mut() {
+                        if let FluentItem::Entry { id, lines } = item {
+                            if *id == missing.pointer {
+                                let (prefix, _) = fluent_value(lines);
+                                *lines = format!("{}{}", prefix, translated)
+                                    .lines()
+                                    .map(str::to_string)
+                                    .collect();
+                            }
+                        }
+                    }
+                }
+                let mut output = items
+                    .iter()
+                    .map(|item| match item {
+                        FluentItem::Entry { lines, .. } => lines.join("\n"),
+                        FluentItem::Other(line) => line.clone(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                output.push('\n');
+                Ok(output.into_bytes())
+            }
+            Resource::Tree(value) => {
+                let mut value = value.clone();
+                for (missing, translated) in self.missing.iter().zip(translations) {
+                    if let Some(slot) = value.pointer_mut(&missing.pointer) {
+                        *slot = Value::String(translated.clone());
+                    }
+                }
+                if let Some(root) = &self.root {
+                    let mut wrapped = Map::new();
+                    wrapped.insert(root.clone(), value);
+                    value = Value::Object(wrapped);
+                }
+                if let Some(existing) = &self.existing_yaml {
+                    match self.edit_yaml(existing, &value)? {
+                        Some(edited) => return Ok(edited.into_bytes()),
+                        None => log_line!(
+                            "[BATCH] Could not edit the existing YAML layout in place, rewriting the file without its comments"
+                        ),
+                    }
+                }
+                let mut output = match format {
+                    LocaleFormat::Yaml => serde_yaml_ng::to_string(&value)?.into_bytes(),
+                    _ => {
+                        let mut output = Vec::new();
+                        let formatter =
+                            serde_json::ser::PrettyFormatter::with_indent(self.indent.as_bytes());
+                        let mut serializer =
+                            serde_json::Serializer::with_formatter(&mut output, formatter);
+                        value.serialize(&mut serializer)?;
+                        output
+                    }
+                };
+                if !output.ends_with(b"\n") {
+                    output.push(b'\n');
+                }
+                Ok(output)
+            }
+        }
+    }
+}
+
+fn collect_absent(
+    text: &YamlText,
+    map: &Map<String, Value>,
+    path: &mut Vec<String>,
+    absent: &mut Vec<Vec<String>>,
+) {
+    let Some(value) = path.last().and_then(|key| map.get(key)) else {
+        return;
+    };
+    if text.find(path).is_none() {
+        absent.push(path.clone());
+        return;
+    }
+    if let Value::Object(children) = value {
+        for key in children.keys() {
+            path.push(key.clone());
+            collect_absent(text, children, path, absent);
+            path.pop();
+        }
+    }
+}
+
+fn value_sample(format: LocaleFormat, bytes: &[u8]) -> Result<String> {
+    fn collect(value: &Value, sample: &mut String) {
+        match value {
+            Value::String(text) if sample.len() < DETECTION_SAMPLE_CHARS => {
+                sample.push_str(text);
+                sample.push('\n');
+            }
+            Value::Array(values) => values.iter().for_each(|value| collect(value, sample)),
+            Value::Object(map) => map.values().for_each(|value| collect(value, sample)),
+            _ => {}
+        }
+    }
+
+    let mut sample = String::new();
+    match format {
+        LocaleFormat::Fluent => {
+            for item in parse_fluent(bytes)? {
+                if let FluentItem::Entry { lines, .. } = item {
+                    sample.push_str(&fluent_value(&lines).1);
+                    sample.push('\n');
+                }
+            }
+        }
+        _ => collect(&parse_tree(format, bytes)?, &mut sample),
+    }
+    Ok(sample.chars().take(DETECTION_SAMPLE_CHARS).collect())
+}
+
+pub(super) async fn translate_locale(
+    path: &Path,
+    target_lang: Option<&str>,
+    target_override: Option<&Path>,
+    progress: &FileProgress<'_>,
+) -> Result<LocaleFileResult> {
+    let format = locale_format(path).ok_or_else(|| {
+        TranslatorError::InvalidInput(format!("Unsupported locale file: {}", path.display()))
+    })?;
+    let source = fs::read(path)?;
+
+    let settings = load_settings();
+    let source_lang = match path_language(path) {
+        Some(language) => language,
+        None => {
+            let sample = value_sample(format, &source)?;
+            if sample.trim().is_empty() {
+                String::new()
+            } else {
+                detect_language(&sample).await?
+            }
+        }
+    };
+    let pair = resolve_with_override(&source_lang, target_lang, &settings);
+    let style = resolve_style(&pair.target, &settings);
+    let output = target_override
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| target_path(path, &source_lang, &pair.target));
+    if output == path {
+        return Err(TranslatorError::InvalidInput(
+            "Target locale file is the source file".to_string(),
+        )
+        .into());
+    }
+    let existing = match output.exists() {
+        true => Some(fs::read(&output)?),
+        false => None,
+    };
+
+    let merged = MergedResource::build(
+        format,
+        &source,
+        existing.as_deref(),
+        &source_lang,
+        &pair.target,
+    )?;
+    let segments: Vec<Segment> = merged
+        .missing
+        .iter()
+        .map(|missing| missing.segment.clone())
+        .collect();
+    progress.emit(BatchStage::Started, 0, segments.len());
+    log_line!(
+        "[BATCH] Filling {} missing keys of {} into {} ({} -> {}, {} kept)",
+        segments.len(),
+        path.display(),
+        output.display(),
+        pair.source,
+        pair.target,
+        merged.kept
+    );
+
+    let translations = translate_segments(&segments, &pair, &style, false, progress).await?;
+    if !segments.is_empty() || existing.is_none() {
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&output, merged.render(format, &translations)?)?;
+    }
+
+    let added = merged
+        .missing
+        .iter()
+        .zip(translations)
+        .map(|(missing, translation)| LocaleKeyChange {
+            key: missing.key.clone(),
+            source: missing.segment.text.clone(),
+            translation,
+        })
+        .collect();
+    Ok(LocaleFileResult {
+        output_path: output.to_string_lossy().into_owned(),
+        added,
+        kept: merged.kept,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn icu_syntax_is_protected_but_plural_text_is_not() {
+        let text = "You have {count, plural, one {# message} other {# messages}} from {name}";
+        let segment = message_segment(text);
+        let protected: Vec<&str> = segment
+            .protected
+            .iter()
+            .map(|range| &text[range.clone()])
+            .collect();
+
+        assert_eq!(
+            protected,
+            vec![
+                "{count, plural, one {",
+                "#",
+                "} other {",
+                "#",
+                "}}",
+                "{name}"
+            ]
+        );
+        assert!(needs_translation(&segment));
+        assert!(!needs_translation(&message_segment("{count, number}")));
+    }
+
+    #[test]
+    fn json_merge_keeps_existing_translations_and_source_order() {
+        let source = br#"{
+    "title": "Inbox",
+    "menu": {
+        "open": "Open",
+        "close": "Close"
+    },
+    "count": "{n} items"
+}"#;
+        let existing = br#"{"menu": {"close": "Schliessen"}, "legacy": "Alt"}"#;
+        let merged =
+            MergedResource::build(LocaleFormat::Json, source, Some(existing), "en", "de").unwrap();
+        let keys: Vec<&str> = merged.missing.iter().map(|key| key.key.as_str()).collect();
+        assert_eq!(keys, vec!["title", "menu.open", "count"]);
+        assert_eq!(merged.kept, 1);
+
+        let translations = vec![
+            "Posteingang".to_string(),
+            "Öffnen".to_string(),
+            "{n} Elemente".to_string(),
+        ];
+        let rendered =
+            String::from_utf8(merged.render(LocaleFormat::Json, &translations).unwrap()).unwrap();
+        assert_eq!(
+            rendered,
+            "{\n  \"title\": \"Posteingang\",\n  \"menu\": {\n    \"open\": \"Öffnen\",\n    \"close\": \"Schliessen\"\n  },\n  \"count\": \"{n} Elemente\",\n  \"legacy\": \"Alt\"\n}\n"
+        );
+    }
+
+    #[test]
+    fn yaml_locale_root_is_renamed() {
+        let source = b"en:\n  greeting: Hello\n  farewell: Goodbye\n";
+        let existing = b"fr:\n  greeting: Bonjour\n";
+        let merged =
+            MergedResource::build(LocaleFormat::Yaml, source, Some(existing), "en", "fr").unwrap();
+        let rendered = String::from_utf8(
+            merged
+                .render(LocaleFormat::Yaml, &["Au revoir".to_string()])
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            rendered,
+            "fr:\n  greeting: Bonjour\n  farewell: Au revoir\n"
+        );
+    }
+
+    #[test]
+    fn yaml_merge_keeps_comments_and_layout_of_existing_target() {
+        let source = b"en:\n  greeting: Hello\n  menu:\n    open: Open\n    close: Close\n  farewell: Goodbye\n";
+        let existing = b"# French strings, maintained by hand\nfr:\n  greeting: Bonjour # salutation\n  menu:\n    # verbs\n    close: 'Fermer'\n  farewell: \"\"  # todo\n  legacy: &old Ancien\n";
+        let merged =
+            MergedResource::build(LocaleFormat::Yaml, source, Some(existing), "en", "fr").unwrap();
+        let keys: Vec<&str> = merged.missing.iter().map(|key| key.key.as_str()).collect();
+        assert_eq!(keys, vec!["menu.open", "farewell"]);
+
+        let translations = vec!["Ouvrir".to_string(), "Au revoir".to_string()];
+        let rendered =
+            String::from_utf8(merged.render(LocaleFormat::Yaml, &translations).unwrap()).unwrap();
+        assert_eq!(
+            rendered,
+            "# French strings, maintained by hand\nfr:\n  greeting: Bonjour # salutation\n  menu:\n    open: Ouvrir\n    # verbs\n    close: 'Fermer'\n  farewell: Au revoir # todo\n  legacy: &old Ancien\n"
+        );
+    }
+
+    #[test]
+    fn yaml_missing_sections_are_inserted_with_file_indent() {
+        let source = b"title: Inbox\nmenu:\n  open: Open\n  close: Close\n";
+        let existing = b"# Deutsch\ntitle: Posteingang\n";
+        let merged =
+            MergedResource::build(LocaleFormat::Yaml, source, Some(existing), "en", "de").unwrap();
+        let translations = vec!["Öffnen".to_string(), "Schliessen".to_string()];
+        let rendered =
+            String::from_utf8(merged.render(LocaleFormat::Yaml, &translations).unwrap()).unwrap();
+        assert_eq!(
+            rendered,
+            "# Deutsch\ntitle: Posteingang\nmenu:\n  open: Öffnen\n  close: Schliessen\n"
+        );
+    }
+
+    #[test]
+    fn fluent_messages_are_inserted_after_their_predecessor() {
+        let source = b"# Main\nhello = Hello, { $name }!\nemails =\n    { $count ->\n        [one] One email\n       *[other] { $count } emails\n    }\nbye = Goodbye\n    .title = Leave\n";
+        let existing = b"hello = Hallo, { $name }!\n\nbye = Tschuess\n    .title = Gehen\n";
+        let merged =
+            MergedResource::build(LocaleFormat::Fluent, source, Some(existing), "en", "de")
+                .unwrap();
+        assert_eq!(merged.missing.len(), 1);
+        let segment = &merged.missing[0].segment;
+        let protected: Vec<&str> = segment
+            .protected
+            .iter()
+            .map(|range| &segment.text[range.clone()])
+            .collect();
+        assert_eq!(
+            protected,
+            vec![
+                "\n    ",
+                "{ $count ->",
+                "\n        [one] ",
+                "\n       *[other] ",
+                "{ $count }",
+                "\n    }"
+            ]
+        );
+
+        let translated = segment
+            .text
+            .replace("One email", "Eine E-Mail")
+            .replace(" emails", " E-Mails");
+        let rendered =
+            String::from_utf8(merged.render(LocaleFormat::Fluent, &[translated]).unwrap()).unwrap();
+        assert_eq!(
+            rendered,
+            "hello = Hallo, { $name }!\nemails =\n    { $count ->\n        [one] Eine E-Mail\n       *[other] { $count } E-Mails\n    }\n\nbye = Tschuess\n    .title = Gehen\n"
+        );
+    }
+
+    #[test]
+    fn target_path_follows_locale_layout() {
+        assert_eq!(
+            target_path(Path::new("/app/locales/en.json"), "en", "de"),
+            PathBuf::from("/app/locales/de.json")
+        );
+        assert_eq!(
+            target_path(Path::new("/app/locales/en/common.yml"), "en", "de"),
+            PathBuf::from("/app/locales/de/common.yml")
+        );
+        assert_eq!(
+            target_path(Path::new("/app/ui/strings.ftl"), "en", "de"),
+            PathBuf::from("/app/ui/strings.de.ftl")
+        );
+    }
+}
@@ -0,0 +1,523 @@
+mod docx;
+mod locale;
+mod po;
+mod subtitles;
+mod text;
+mod xliff;
+
+use anyhow::Result;
+use futures_util::future::{AbortHandle, Abortable};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::future::Future;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::AppHandle;
+
+use self::docx::DocxDocument;
+use self::locale::{LocaleFileResult, LocaleKeyChange};
+use self::po::PoCatalog;
+use self::subtitles::SubtitleDocument;
+use self::text::TextDocument;
+use self::xliff::XliffDocument;
+use super::cache;
+use super::error::TranslatorErrorPayload;
+use super::events::EventSink;
+use super::format::TextFormat;
+use super::protect;
+use super::translate::{translate_pipelined, translate_segment};
+use super::{
+    detect_language, resolve_style, resolve_with_override, AppliedStyle, LanguagePair,
+    TranslatorError,
+};
+use crate::settings::load_settings;
+
+const MARKER_OPEN: char = '\u{E000}';
+const MARKER_CLOSE: char = '\u{E001}';
+const DETECTION_SAMPLE_CHARS: usize = 2000;
+
+static PLACEHOLDER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"%(?:\d+\$)?(?:\([A-Za-z0-9_]+\))?[-+0#]*\d*(?:\.\d+)?[sdifuxXeEgGcp@]|%%|\{\{[^{}\n]*\}\}|\$\{[^{}\n]*\}|\{[A-Za-z0-9_.:-]*\}",
+    )
+    .expect("valid placeholder pattern")
+});
+
+static NEXT_BATCH_ID: AtomicU64 = AtomicU64::new(1);
+static ACTIVE_BATCH: Mutex<Option<(u64, AbortHandle)>> = Mutex::new(None);
+
+#[derive(Debug, Clone)]
+pub(super) struct Segment {
+    pub text: String,
+    pub protected: Vec<Range<usize>>,
+    pub notes: Option<String>,
+}
+
+impl Segment {
+    pub fn joined(pieces: &[&str]) -> Self {
+        let mut text = String::new();
+        let mut protected = Vec::new();
+        for (index, piece) in pieces.iter().enumerate() {
+            if index > 0 {
+                let start = text.len();
+                text.push_str(&marker(index));
+                protected.push(start..text.len());
+            }
+            text.push_str(piece);
+        }
+        Segment {
+            text,
+            protected,
+            notes: None,
+        }
+    }
+}
+
+pub(super) trait BatchDocument: Send + Sync {
+    fn segments(&self) -> Vec<Segment>;
+    fn render(&self, translations: &[String]) -> Result<Vec<u8>>;
+
+    fn declared_languages(&self) -> (Option<String>, Option<String>) {
+        (None, None)
+    }
+
+    fn set_target_language(&mut self, _language: &str) {}
+
+    fn is_continuous(&self) -> bool {
+        true
+    }
+
+    fn output_extension(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BatchOptions {
+    #[serde(default)]
+    pub include_fuzzy: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchStage {
+    Started,
+    Segment,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchProgress {
+    pub batch_id: u64,
+    pub file_index: usize,
+    pub file_count: usize,
+    pub path: String,
+    pub stage: BatchStage,
+    pub completed_segments: usize,
+    pub total_segments: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<TranslatorErrorPayload>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub added_keys: Option<Vec<LocaleKeyChange>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchFileResult {
+    pub path: String,
+    pub output_path: Option<String>,
+    pub error: Option<TranslatorErrorPayload>,
+    pub added_keys: Option<Vec<LocaleKeyChange>>,
+}
+
+struct FileProgress<'a> {
+    events: &'a dyn EventSink,
+    batch_id: u64,
+    file_index: usize,
+    file_count: usize,
+    path: &'a str,
+}
+
+impl FileProgress<'_> {
+    fn emit(&self, stage: BatchStage, completed: usize, total: usize) {
+        self.emit_with(stage, completed, total, None, None, None);
+    }
+
+    fn emit_with(
+        &self,
+        stage: BatchStage,
+        completed: usize,
+        total: usize,
+        output_path: Option<String>,
+        error: Option<TranslatorErrorPayload>,
+        added_keys: Option<Vec<LocaleKeyChange>>,
+    ) {
+        self.events.emit(
+            "batch-progress",
+            BatchProgress {
+                batch_id: self.batch_id,
+                file_index: self.file_index,
+                file_count: self.file_count,
+                path: self.path.to_string(),
+                stage,
+                completed_segments: completed,
+                total_segments: total,
+                output_path,
+                error,
+                added_keys,
+            },
+        );
+    }
+}
+
+fn marker(index: usize) -> String {
+    format!("{}{}{}", MARKER_OPEN, index, MARKER_CLOSE)
+}
+
+fn placeholder_ranges(text: &str) -> Vec<Range<usize>> {
+    PLACEHOLDER
+        .find_iter(text)
+        .map(|placeholder| placeholder.range())
+        .collect()
+}
+
+fn split_joined(translated: &str, count: usize) -> Vec<String> {
+    let mut positions: Vec<(usize, usize, usize)> = (1..count)
+        .filter_map(|index| {
+            let marker = marker(index);
+            translated
+                .find(&marker)
+                .map(|start| (start, start + marker.len(), index))
+        })
+        .collect();
+    positions.sort();
+
+    let mut pieces = vec![String::new(); count.max(1)];
+    let mut current = 0;
+    let mut cursor = 0;
+    for (start, end, index) in positions {
+        pieces[current].push_str(&translated[cursor..start]);
+        current = index;
+        cursor = end;
+    }
+    pieces[current].push_str(&translated[cursor..]);
+    pieces
+}
+
+fn open_document(path: &Path, options: &BatchOptions) -> Result<Box<dyn BatchDocument>> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    let bytes = fs::read(path)?;
+    let document: Box<dyn BatchDocument> = match extension.as_str() {
+        "txt" | "text" => Box::new(TextDocument::parse(&bytes, TextFormat::Plain)?),
+        "md" | "markdown" => Box::new(TextDocument::parse(&bytes, TextFormat::Markdown)?),
+        "docx" => Box::new(DocxDocument::parse(&bytes)?),
+        "srt" | "vtt" => Box::new(SubtitleDocument::parse(
+            &bytes,
+            load_settings().subtitle_max_line_chars,
+        )?),
+        "po" | "pot" => Box::new(PoCatalog::parse(
+            &bytes,
+            extension == "pot",
+            options.include_fuzzy,
+        )?),
+        "xlf" | "xliff" => Box::new(XliffDocument::parse(&bytes, options.include_fuzzy)?),
+        _ => {
+            return Err(TranslatorError::InvalidInput(format!(
+                "Unsupported file type: {}",
+                path.display()
+            ))
+            .into())
+        }
+    };
+    Ok(document)
+}
+
+fn output_path(path: &Path, language: &str, extension: Option<&str>) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = extension
+        .map(str::to_string)
+        .or_else(|| path.extension().map(|e| e.to_string_lossy().into_owned()));
+    let name = match extension {
+        Some(extension) => format!("{}.{}.{}", stem, language, extension),
+        None => format!("{}.{}", stem, language),
+    };
+    path.with_file_name(name)
+}
+
+async fn translate_segments(
+    segments: &[Segment],
+    pair: &LanguagePair,
+    style: &AppliedStyle,
+    continuous: bool,
+    progress: &FileProgress<'_>,
+) -> Result<Vec<String>> {
+    let parallel = load_settings().max_parallel_requests.max(1);
+    let completed = Mutex::new(0);
+
+    let sources: Vec<&str> = segments
+        .iter()
+        .map(|segment| segment.text.as_str())
+        .collect();
+    let completed = &completed;
+    let translations = translate_pipelined(&sources, parallel, continuous, |index, context| {
+        let segment = &segments[index];
+        async move {
+            let masked = protect::mask_with_settings(&segment.text, &segment.protected);
+            let notes = segment.notes.clone();
+            let translation =
+                translate_segment(&segment.text, &masked, pair, style, context, notes, |_| {})
+                    .await?;
+
+            let mut completed = completed.lock();
+            *completed += 1;
+            progress.emit(BatchStage::Segment, *completed, segments.len());
+            Ok(translation)
+        }
+    })
+    .await?;
+    Ok(translations
+        .into_iter()
+        .map(|translation| translation.text)
+        .collect())
+}
+
+async fn translate_file(
+    path: &Path,
+    target_lang: Option<&str>,
+    options: &BatchOptions,
+    progress: &FileProgress<'_>,
+) -> Result<PathBuf> {
+    let mut document = open_document(path, options)?;
+    let (declared_source, declared_target) = document.declared_languages();
+
+    let settings = load_settings();
+    let source_lang = match declared_source {
+        Some(source) => source,
+        None => {
+            let sample: String = document
+                .segments()
+                .iter()
+                .map(|segment| segment.text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n")
+                .chars()
+                .take(DETECTION_SAMPLE_CHARS)
+                .collect();
+            if sample.trim().is_empty() {
+                String::new()
+            } else {
+                detect_language(&sample).await?
+            }
+        }
+    };
+    let target_lang = target_lang.or(declared_target.as_deref());
+    let pair = resolve_with_override(&source_lang, target_lang, &settings);
+    let style = resolve_style(&pair.target, &settings);
+    document.set_target_language(&pair.target);
+    let segments = document.segments();
+    progress.emit(BatchStage::Started, 0, segments.len());
+    log_line!(
+        "[BATCH] Translating {} segments of {} ({} -> {})",
+        segments.len(),
+        path.display(),
+        pair.source,
+        pair.target
+    );
+
+    let translations =
+        translate_segments(&segments, &pair, &style, document.is_continuous(), progress).await?;
+    let rendered = document.render(&translations)?;
+    let output = output_path(path, &pair.target, document.output_extension());
+    fs::write(&output, rendered)?;
+    Ok(output)
+}
+
+async fn translate_batch(
+    events: &dyn EventSink,
+    batch_id: u64,
+    paths: &[String],
+    target_lang: Option<&str>,
+    options: &BatchOptions,
+) -> Vec<BatchFileResult> {
+    let mut results = Vec::new();
+    for (file_index, path) in paths.iter().enumerate() {
+        let progress = FileProgress {
+            events,
+            batch_id,
+            file_index,
+            file_count: paths.len(),
+            path,
+        };
+        let outcome = match locale::is_locale_file(Path::new(path)) {
+            true => locale::translate_locale(Path::new(path), target_lang, None, &progress)
+                .await
+                .map(|result| (PathBuf::from(result.output_path), Some(result.added))),
+            false => translate_file(Path::new(path), target_lang, options, &progress)
+                .await
+                .map(|output| (output, None)),
+        };
+        match outcome {
+            Ok((output, added_keys)) => {
+                let output = output.to_string_lossy().into_owned();
+                log_line!("[BATCH] ✅ {} -> {}", path, output);
+                progress.emit_with(
+                    BatchStage::Completed,
+                    0,
+                    0,
+                    Some(output.clone()),
+                    None,
+                    added_keys.clone(),
+                );
+                results.push(BatchFileResult {
+                    path: path.clone(),
+                    output_path: Some(output),
+                    error: None,
+                    added_keys,
+                });
+            }
+            Err(e) => {
+                let error = TranslatorError::from(e).payload();
+                log_line!("[BATCH] ❌ {} failed: {}", path, error.message);
+                progress.emit_with(BatchStage::Failed, 0, 0, None, Some(error.clone()), None);
+                results.push(BatchFileResult {
+                    path: path.clone(),
+                    output_path: None,
+                    error: Some(error),
+                    added_keys: None,
+                });
+            }
+        }
+    }
+    results
+}
+
+async fn run_batch<T>(batch_id: u64, work: impl Future<Output = T>) -> Result<T, String> {
+    let (abort_handle, registration) = AbortHandle::new_pair();
+    if let Some((previous_id, previous)) = ACTIVE_BATCH.lock().replace((batch_id, abort_handle)) {
+        log_line!(
+            "[BATCH] Batch {} superseded by batch {}",
+            previous_id,
+            batch_id
+        );
+        previous.abort();
+    }
+
+    let result = Abortable::new(work, registration).await;
+    {
+        let mut active = ACTIVE_BATCH.lock();
+        if active.as_ref().is_some_and(|(id, _)| *id == batch_id) {
+            *active = None;
+        }
+    }
+    let _ = tokio::task::spawn_blocking(cache::flush).await;
+    result.map_err(|_| {
+        log_line!("[BATCH] Batch {} cancelled", batch_id);
+        "Batch translation cancelled".to_string()
+    })
+}
+
+pub async fn translate_paths(
+    events: &dyn EventSink,
+    paths: &[String],
+    target_lang: Option<&str>,
+    options: &BatchOptions,
+) -> Vec<BatchFileResult> {
+    let batch_id = NEXT_BATCH_ID.fetch_add(1, Ordering::SeqCst);
+    translate_batch(events, batch_id, paths, target_lang, options).await
+}
+
+#[tauri::command]
+pub async fn translate_files(
+    app: AppHandle,
+    paths: Vec<String>,
+    target_lang: Option<String>,
+    options: Option<BatchOptions>,
+) -> Result<Vec<BatchFileResult>, String> {
+    if paths.is_empty() {
+        return Err("No files selected".to_string());
+    }
+    let batch_id = NEXT_BATCH_ID.fetch_add(1, Ordering::SeqCst);
+    log_line!(
+        "[BATCH] Starting batch {} with {} files",
+        batch_id,
+        paths.len()
+    );
+
+    let options = options.unwrap_or_default();
+    let batch = translate_batch(&app, batch_id, &paths, target_lang.as_deref(), &options);
+    run_batch(batch_id, batch).await
+}
+
+#[tauri::command]
+pub async fn translate_locale_file(
+    app: AppHandle,
+    path: String,
+    target_lang: String,
+    target_path: Option<String>,
+) -> Result<LocaleFileResult, String> {
+    let batch_id = NEXT_BATCH_ID.fetch_add(1, Ordering::SeqCst);
+    let progress = FileProgress {
+        events: &app,
+        batch_id,
+        file_index: 0,
+        file_count: 1,
+        path: &path,
+    };
+    let work = locale::translate_locale(
+        Path::new(&path),
+        Some(&target_lang),
+        target_path.as_deref().map(Path::new),
+        &progress,
+    );
+    run_batch(batch_id, work).await?.map_err(|e| {
+        let error = TranslatorError::from(e).payload();
+        log_line!("[BATCH] ❌ {} failed: {}", path, error.message);
+        error.message
+    })
+}
+
+#[tauri::command]
+pub fn cancel_batch_translation() -> bool {
+    match ACTIVE_BATCH.lock().take() {
+        Some((batch_id, handle)) => {
+            log_line!("[BATCH] Cancelling batch {}", batch_id);
+            handle.abort();
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_path_inserts_language() {
+        assert_eq!(
+            output_path(Path::new("/docs/report.docx"), "de", None),
+            PathBuf::from("/docs/report.de.docx")
+        );
+        assert_eq!(
+            output_path(Path::new("notes"), "ja", None),
+            PathBuf::from("notes.ja")
+        );
+        assert_eq!(
+            output_path(Path::new("locale/messages.pot"), "fr", Some("po")),
+            PathBuf::from("locale/messages.fr.po")
+        );
+    }
+}
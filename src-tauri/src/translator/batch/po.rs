@@ -0,0 +1,561 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::{placeholder_ranges, split_joined, BatchDocument, Segment};
+
+static NPLURALS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"nplurals\s*=\s*(\d+)").expect("valid nplurals pattern"));
+
+static MSGSTR_INDEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^msgstr\[(\d+)\]\s").expect("valid msgstr index pattern"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Context,
+    Id,
+    Plural,
+    Str(usize),
+}
+
+#[derive(Debug, Clone, Default)]
+struct PoEntry {
+    lines: Vec<String>,
+    comments: Vec<String>,
+    flags: Vec<String>,
+    msgctxt: Option<String>,
+    msgid: String,
+    msgid_plural: Option<String>,
+    msgstr: Vec<String>,
+    obsolete: bool,
+}
+
+pub(super) struct PoCatalog {
+    entries: Vec<PoEntry>,
+    template: bool,
+    include_fuzzy: bool,
+    target: String,
+}
+
+fn unescape(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => output.push('\n'),
+            Some('t') => output.push('\t'),
+            Some('r') => output.push('\r'),
+            Some(other) => output.push(other),
+            None => output.push('\\'),
+        }
+    }
+    output
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+        .replace('\r', "\\r")
+}
+
+fn quoted(line: &str) -> String {
+    let line = line.trim();
+    let inner = line
+        .strip_prefix('"')
+        .and_then(|line| line.strip_suffix('"'))
+        .unwrap_or(line);
+    unescape(inner)
+}
+
+fn po_string(keyword: &str, value: &str) -> Vec<String> {
+    if !value.trim_end_matches('\n').contains('\n') {
+        return vec![format!("{} \"{}\"", keyword, escape(value))];
+    }
+    std::iter::once(format!("{} \"\"", keyword))
+        .chain(
+            value
+                .split_inclusive('\n')
+                .map(|line| format!("\"{}\"", escape(line))),
+        )
+        .collect()
+}
+
+fn parse_entry(lines: Vec<String>) -> PoEntry {
+    let mut entry = PoEntry::default();
+    let mut field = None;
+    for line in &lines {
+        let trimmed = line.trim();
+        if trimmed.starts_with("#~") {
+            entry.obsolete = true;
+        } else if let Some(flags) = trimmed.strip_prefix("#,") {
+            entry.flags = flags
+                .split(',')
+                .map(|flag| flag.trim().to_string())
+                .filter(|flag| !flag.is_empty())
+                .collect();
+        } else if let Some(comment) = trimmed.strip_prefix("#.") {
+            entry.comments.push(comment.trim().to_string());
+        } else if trimmed.starts_with("#:") || trimmed.starts_with("#|") {
+        } else if let Some(comment) = trimmed.strip_prefix('#') {
+            if !comment.trim().is_empty() {
+                entry.comments.push(comment.trim().to_string());
+            }
+        } else if let Some(value) = trimmed.strip_prefix("msgctxt ") {
+            entry.msgctxt = Some(quoted(value));
+            field = Some(Field::Context);
+        } else if let Some(value) = trimmed.strip_prefix("msgid_plural ") {
+            entry.msgid_plural = Some(quoted(value));
+            field = Some(Field::Plural);
+        } else if let Some(value) = trimmed.strip_prefix("msgid ") {
+            entry.msgid = quoted(value);
+            field = Some(Field::Id);
+        } else if let Some(index) = MSGSTR_INDEX.captures(trimmed) {
+            let index: usize = index[1].parse().unwrap_or(0);
+            let value = trimmed
+                .split_once(' ')
+                .map(|(_, value)| value)
+                .unwrap_or("");
+            if entry.msgstr.len() <= index {
+                entry.msgstr.resize(index + 1, String::new());
+            }
+            entry.msgstr[index] = quoted(value);
+            field = Some(Field::Str(index));
+        } else if let Some(value) = trimmed.strip_prefix("msgstr ") {
+            entry.msgstr = vec![quoted(value)];
+            field = Some(Field::Str(0));
+        } else if trimmed.starts_with('"') {
+            let value = quoted(trimmed);
+            match field {
+                Some(Field::Context) => entry
+                    .msgctxt
+                    .get_or_insert_with(String::new)
+                    .push_str(&value),
+                Some(Field::Id) => entry.msgid.push_str(&value),
+                Some(Field::Plural) => entry
+                    .msgid_plural
+                    .get_or_insert_with(String::new)
+                    .push_str(&value),
+                Some(Field::Str(index)) => entry.msgstr[index].push_str(&value),
+                None => {}
+            }
+        }
+    }
+    entry.lines = lines;
+    entry
+}
+
+fn header_field<'a>(header: &'a str, name: &str) -> Option<&'a str> {
+    header.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim().eq_ignore_ascii_case(name)).then(|| value.trim())
+    })
+}
+
+fn set_header_field(header: &str, name: &str, value: &str) -> String {
+    let mut found = false;
+    let mut lines: Vec<String> = header
+        .lines()
+        .map(|line| match line.split_once(':') {
+            Some((key, _)) if key.trim().eq_ignore_ascii_case(name) => {
+                found = true;
+                format!("{}: {}", key.trim(), value)
+            }
+            _ => line.to_string(),
+        })
+        .collect();
+    if !found {
+        lines.push(format!("{}: {}", name, value));
+    }
+    lines
+        .into_iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line + "\n")
+        .collect()
+}
+
+pub(super) fn plural_forms(language: &str) -> (usize, &'static str) {
+    let language = language.to_lowercase().replace('_', "-");
+    let primary = language.split('-').next().unwrap_or_default();
+    match primary {
+        "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" | "lo" | "km" | "my" => (1, "0"),
+        "pt" if language == "pt-br" => (2, "(n > 1)"),
+        "fr" | "oc" | "fil" | "ln" | "ti" => (2, "(n > 1)"),
+        "ru" | "uk" | "be" | "sr" | "hr" | "bs" => (
+            3,
+            "(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2)",
+        ),
+        "pl" => (
+            3,
+            "(n==1 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2)",
+        ),
+        "cs" | "sk" => (3, "(n==1 ? 0 : (n>=2 && n<=4) ? 1 : 2)"),
+        "lt" => (
+            3,
+            "(n%10==1 && n%100!=11 ? 0 : n%10>=2 && (n%100<10 || n%100>=20) ? 1 : 2)",
+        ),
+        "lv" => (3, "(n%10==1 && n%100!=11 ? 0 : n != 0 ? 1 : 2)"),
+        "ro" => (
+            3,
+            "(n==1 ? 0 : (n==0 || (n%100 > 0 && n%100 < 20)) ? 1 : 2)",
+        ),
+        "sl" => (
+            4,
+            "(n%100==1 ? 0 : n%100==2 ? 1 : n%100==3 || n%100==4 ? 2 : 3)",
+        ),
+        "ga" => (5, "(n==1 ? 0 : n==2 ? 1 : n<7 ? 2 : n<11 ? 3 : 4)"),
+        "ar" => (
+            6,
+            "(n==0 ? 0 : n==1 ? 1 : n==2 ? 2 : n%100>=3 && n%100<=10 ? 3 : n%100>=11 ? 4 : 5)",
+        ),
+        _ => (2, "(n != 1)"),
+    }
+}
+
+impl PoEntry {
+    fn is_header(&self) -> bool {
+        !self.obsolete && self.msgid.is_empty() && self.msgctxt.is_none()
+    }
+
+    fn is_fuzzy(&self) -> bool {
+        self.flags.iter().any(|flag| flag == "fuzzy")
+    }
+
+    fn is_untranslated(&self) -> bool {
+        self.msgstr.iter().all(|value| value.is_empty())
+    }
+
+    fn notes(&self, plural_forms: Option<usize>) -> Option<String> {
+        let mut notes = Vec::new();
+        if let Some(context) = &self.msgctxt {
+            notes.push(format!("Message context: {}.", context));
+        }
+        if !self.comments.is_empty() {
+            notes.push(format!("Comments: {}.", self.comments.join(" ")));
+        }
+        match plural_forms {
+            Some(1) => notes.push(
+                "This is the plural message; the target language has a single plural form."
+                    .to_string(),
+            ),
+            Some(count) => notes.push(format!(
+                "The text holds the {} plural forms of one message, in gettext order and split \
+                 by separator tokens. Translate each part with the matching plural form of the \
+                 target language and keep the separators.",
+                count
+            )),
+            None => {}
+        }
+        (!notes.is_empty()).then(|| notes.join(" "))
+    }
+
+    fn render(&self, plural: bool) -> Vec<String> {
+        let mut lines = Vec::new();
+        let flags = match self.flags.is_empty() {
+            true => None,
+            false => Some(format!("#, {}", self.flags.join(", "))),
+        };
+        let mut flags_written = false;
+        let keyword_start = self
+            .lines
+            .iter()
+            .position(|line| !line.trim_start().starts_with('#'))
+            .unwrap_or(self.lines.len());
+        for line in &self.lines[..keyword_start] {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("#,") {
+                lines.extend(flags.clone());
+                flags_written = true;
+                continue;
+            }
+            if trimmed.starts_with("#|") && !flags_written {
+                lines.extend(flags.clone());
+                flags_written = true;
+            }
+            lines.push(line.clone());
+        }
+        if !flags_written {
+            lines.extend(flags);
+        }
+
+        lines.extend(
+            self.lines[keyword_start..]
+                .iter()
+                .take_while(|line| !line.trim_start().starts_with("msgstr"))
+                .cloned(),
+        );
+        if plural {
+            for (index, value) in self.msgstr.iter().enumerate() {
+                lines.extend(po_string(&format!("msgstr[{}]", index), value));
+            }
+        } else {
+            lines.extend(po_string(
+                "msgstr",
+                self.msgstr.first().map(String::as_str).unwrap_or(""),
+            ));
+        }
+        lines
+    }
+}
+
+impl PoCatalog {
+    pub fn parse(bytes: &[u8], template: bool, include_fuzzy: bool) -> Result<Self> {
+        let text = String::from_utf8(bytes.to_vec())?;
+        let text = text
+            .strip_prefix('\u{feff}')
+            .unwrap_or(&text)
+            .replace("\r\n", "\n");
+
+        let mut entries = Vec::new();
+        let mut current: Vec<String> = Vec::new();
+        let mut seen_msgstr = false;
+        for line in text.lines() {
+            let trimmed = line.trim_start();
+            let starts_entry = trimmed.starts_with('#')
+                || trimmed.starts_with("msgctxt")
+                || trimmed.starts_with("msgid ");
+            if trimmed.is_empty() || (seen_msgstr && starts_entry) {
+                if !current.is_empty() {
+                    entries.push(parse_entry(std::mem::take(&mut current)));
+                }
+                seen_msgstr = false;
+                if trimmed.is_empty() {
+                    continue;
+                }
+            }
+            seen_msgstr |= trimmed.starts_with("msgstr");
+            current.push(line.to_string());
+        }
+        if !current.is_empty() {
+            entries.push(parse_entry(current));
+        }
+        if !entries.iter().any(|entry| !entry.msgid.is_empty()) {
+            anyhow::bail!("No messages found in the catalog");
+        }
+
+        Ok(PoCatalog {
+            entries,
+            template,
+            include_fuzzy,
+            target: String::new(),
+        })
+    }
+
+    fn header(&self) -> Option<&PoEntry> {
+        self.entries.iter().find(|entry| entry.is_header())
+    }
+
+    fn plural_count(&self) -> usize {
+        let declared = self
+            .header()
+            .and_then(|header| header_field(header.msgstr.first()?, "Plural-Forms"))
+            .and_then(|forms| NPLURALS.captures(forms)?[1].parse::<usize>().ok())
+            .filter(|count| *count > 0);
+        match (declared, self.template) {
+            (Some(count), false) => count,
+            _ => plural_forms(&self.target).0,
+        }
+    }
+
+    fn is_translatable(&self, entry: &PoEntry) -> bool {
+        if entry.obsolete || entry.msgid.is_empty() {
+            return false;
+        }
+        entry.is_untranslated() || (self.include_fuzzy && entry.is_fuzzy())
+    }
+
+    fn entry_segment(&self, entry: &PoEntry) -> Segment {
+        let mut segment = match &entry.msgid_plural {
+            Some(plural) => {
+                let count = self.plural_count();
+                let pieces: Vec<&str> = (0..count)
+                    .map(|form| match (count, form) {
+                        (1, _) => plural.as_str(),
+                        (_, 0) => entry.msgid.as_str(),
+                        _ => plural.as_str(),
+                    })
+                    .collect();
+                let mut segment = Segment::joined(&pieces);
+                segment.notes = entry.notes(Some(count));
+                segment
+            }
+            None => Segment {
+                text: entry.msgid.clone(),
+                protected: Vec::new(),
+                notes: entry.notes(None),
+            },
+        };
+        segment.protected.extend(placeholder_ranges(&segment.text));
+        segment.protected.sort_by_key(|range| range.start);
+        segment
+    }
+
+    fn updated_header(&self, header: &PoEntry) -> PoEntry {
+        let mut header = header.clone();
+        let mut value = header.msgstr.first().cloned().unwrap_or_default();
+        value = set_header_field(&value, "Language", &self.target);
+        let declared = header_field(&value, "Plural-Forms")
+            .and_then(|forms| NPLURALS.captures(forms)?[1].parse::<usize>().ok());
+        if self.template || declared.is_none() {
+            let (count, expression) = plural_forms(&self.target);
+            value = set_header_field(
+                &value,
+                "Plural-Forms",
+                &format!("nplurals={}; plural={};", count, expression),
+            );
+        }
+        header.msgstr = vec![value];
+        header
+    }
+}
+
+impl BatchDocument for PoCatalog {
+    fn segments(&self) -> Vec<Segment> {
+        self.entries
+            .iter()
+            .filter(|entry| self.is_translatable(entry))
+            .map(|entry| self.entry_segment(entry))
+            .collect()
+    }
+
+    fn render(&self, translations: &[String]) -> Result<Vec<u8>> {
+        let mut translations = translations.iter();
+        let plural_count = self.plural_count();
+        let mut blocks: Vec<String> = Vec::new();
+        for entry in &self.entries {
+            let lines = if entry.is_header() {
+                self.updated_header(entry).render(false)
+            } else if self.is_translatable(entry) {
+                match translations.next() {
+                    Some(translated) => {
+                        let mut entry = entry.clone();
+                        let plural = entry.msgid_plural.is_some();
+                        entry.msgstr = match plural {
+                            true => split_joined(translated, plural_count)
+                                .into_iter()
+                                .map(|form| form.trim().to_string())
+                                .collect(),
+                            false => vec![translated.trim().to_string()],
+                        };
+                        if !entry.is_fuzzy() {
+                            entry.flags.insert(0, "fuzzy".to_string());
+                        }
+                        entry.render(plural)
+                    }
+                    None => entry.lines.clone(),
+                }
+            } else {
+                entry.lines.clone()
+            };
+            blocks.push(lines.join("\n"));
+        }
+        let mut output = blocks.join("\n\n");
+        output.push('\n');
+        Ok(output.into_bytes())
+    }
+
+    fn declared_languages(&self) -> (Option<String>, Option<String>) {
+        let language = self
+            .header()
+            .and_then(|header| header_field(header.msgstr.first()?, "Language"))
+            .filter(|language| !language.is_empty())
+            .map(|language| language.replace('_', "-"));
+        (None, language)
+    }
+
+    fn set_target_language(&mut self, language: &str) {
+        self.target = language.to_string();
+    }
+
+    fn is_continuous(&self) -> bool {
+        false
+    }
+
+    fn output_extension(&self) -> Option<&'static str> {
+        self.template.then_some("po")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::marker;
+    use super::*;
+
+    const POT: &str = r#"# Sample catalog
+msgid ""
+msgstr ""
+"Project-Id-Version: demo\n"
+"Language: \n"
+"Plural-Forms: nplurals=INTEGER; plural=EXPRESSION;\n"
+
+#. Shown on the toolbar
+#: src/app.c:10
+#, c-format
+msgctxt "toolbar"
+msgid "Open %s"
+msgstr ""
+
+msgid "One file"
+msgid_plural "%d files"
+msgstr[0] ""
+msgstr[1] ""
+
+msgid "Done"
+msgstr "Fertig"
+
+#, fuzzy
+msgid "Cancel"
+msgstr "Abbrechen?"
+"#;
+
+    #[test]
+    fn only_untranslated_entries_become_segments() {
+        let mut catalog = PoCatalog::parse(POT.as_bytes(), true, false).unwrap();
+        catalog.set_target_language("ru");
+        let segments = catalog.segments();
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].text, "Open %s");
+        assert_eq!(segments[0].protected, vec![5..7]);
+        let notes = segments[0].notes.as_deref().unwrap();
+        assert!(notes.contains("Message context: toolbar."));
+        assert!(notes.contains("Shown on the toolbar"));
+        assert_eq!(
+            segments[1].text,
+            format!("One file{}%d files{}%d files", marker(1), marker(2))
+        );
+
+        let mut fuzzy = PoCatalog::parse(POT.as_bytes(), true, true).unwrap();
+        fuzzy.set_target_language("ru");
+        assert_eq!(fuzzy.segments().len(), 3);
+    }
+
+    #[test]
+    fn render_marks_results_fuzzy_and_fills_plurals() {
+        let mut catalog = PoCatalog::parse(POT.as_bytes(), true, false).unwrap();
+        catalog.set_target_language("de");
+        let translations = vec![
+            "%s öffnen".to_string(),
+            format!("Eine Datei{}%d Dateien", marker(1)),
+        ];
+        let rendered = String::from_utf8(catalog.render(&translations).unwrap()).unwrap();
+
+        assert!(rendered.contains("\"Language: de\\n\"\n"));
+        assert!(rendered.contains("\"Plural-Forms: nplurals=2; plural=(n != 1);\\n\"\n"));
+        assert!(rendered.contains(
+            "#. Shown on the toolbar\n#: src/app.c:10\n#, fuzzy, c-format\nmsgctxt \"toolbar\"\nmsgid \"Open %s\"\nmsgstr \"%s öffnen\"\n"
+        ));
+        assert!(rendered.contains(
+            "#, fuzzy\nmsgid \"One file\"\nmsgid_plural \"%d files\"\nmsgstr[0] \"Eine Datei\"\nmsgstr[1] \"%d Dateien\"\n"
+        ));
+        assert!(rendered.contains("msgid \"Done\"\nmsgstr \"Fertig\"\n"));
+        assert!(rendered.contains("#, fuzzy\nmsgid \"Cancel\"\nmsgstr \"Abbrechen?\"\n"));
+        assert_eq!(catalog.output_extension(), Some("po"));
+    }
+}
@@ -0,0 +1,434 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::{split_joined, BatchDocument, Segment};
+
+const WINDOW_MIN_CUES: usize = 4;
+const WINDOW_MAX_CUES: usize = 10;
+
+static BLOCK_BREAK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\n(?:[ \t]*\n)+").expect("valid block break pattern"));
+
+static INLINE_TAG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"<[^<>\n\x{E000}\x{E001}]+>|\{\\[^{}\n]*\}").expect("valid subtitle tag pattern")
+});
+
+static DIALOGUE_DASH: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:<[^<>\n]+>|\{\\[^{}\n]*\})*[-‐–—]").expect("valid dialogue dash pattern")
+});
+
+static SENTENCE_END: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"[.!?…。！？]["'”’»)\]]*$"#).expect("valid sentence end pattern"));
+
+#[derive(Debug, Clone)]
+enum Block {
+    Verbatim(String),
+    Cue {
+        header: Vec<String>,
+        lines: Vec<String>,
+    },
+}
+
+pub(super) struct SubtitleDocument {
+    blocks: Vec<Block>,
+    windows: Vec<Vec<usize>>,
+    crlf: bool,
+    max_line_chars: usize,
+}
+
+fn parse_block(block: &str) -> Block {
+    let lines: Vec<&str> = block.lines().collect();
+    if lines.first().is_some_and(|line| line.starts_with("NOTE")) {
+        return Block::Verbatim(block.to_string());
+    }
+    match lines.iter().take(2).position(|line| line.contains("-->")) {
+        Some(timing) => Block::Cue {
+            header: lines[..=timing]
+                .iter()
+                .map(|line| line.to_string())
+                .collect(),
+            lines: lines[timing + 1..]
+                .iter()
+                .map(|line| line.to_string())
+                .collect(),
+        },
+        None => Block::Verbatim(block.to_string()),
+    }
+}
+
+fn cue_text(lines: &[String]) -> String {
+    lines
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn dialogue_turns(lines: &[String]) -> Option<Vec<String>> {
+    let lines: Vec<&str> = lines
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect();
+    let dashes = lines
+        .iter()
+        .filter(|line| DIALOGUE_DASH.is_match(line))
+        .count();
+    if dashes < 2 || !DIALOGUE_DASH.is_match(lines[0]) {
+        return None;
+    }
+
+    let mut turns: Vec<String> = Vec::new();
+    for line in lines {
+        match turns.last_mut() {
+            Some(turn) if !DIALOGUE_DASH.is_match(line) => {
+                turn.push(' ');
+                turn.push_str(line);
+            }
+            _ => turns.push(line.to_string()),
+        }
+    }
+    Some(turns)
+}
+
+fn cue_pieces(lines: &[String]) -> Vec<String> {
+    dialogue_turns(lines).unwrap_or_else(|| vec![cue_text(lines)])
+}
+
+fn visible_text(text: &str) -> String {
+    INLINE_TAG.replace_all(text, "").into_owned()
+}
+
+fn visible_len(text: &str) -> usize {
+    visible_text(text).chars().count()
+}
+
+fn cue_windows(blocks: &[Block]) -> Vec<Vec<usize>> {
+    let mut windows = Vec::new();
+    let mut window = Vec::new();
+    for (index, block) in blocks.iter().enumerate() {
+        let Block::Cue { lines, .. } = block else {
+            continue;
+        };
+        let visible = visible_text(&cue_text(lines));
+        if !visible.chars().any(char::is_alphabetic) {
+            continue;
+        }
+        window.push(index);
+        let ends_sentence = SENTENCE_END.is_match(visible.trim_end());
+        if window.len() >= WINDOW_MAX_CUES || (window.len() >= WINDOW_MIN_CUES && ends_sentence) {
+            windows.push(std::mem::take(&mut window));
+        }
+    }
+    if !window.is_empty() {
+        windows.push(window);
+    }
+    windows
+}
+
+fn words(text: &str) -> Vec<String> {
+    let tags: Vec<_> = INLINE_TAG.find_iter(text).map(|tag| tag.range()).collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for (offset, c) in text.char_indices() {
+        let in_tag = tags.iter().any(|tag| tag.contains(&offset));
+        if c.is_whitespace() && !in_tag {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn split_long_word(word: &str, max_chars: usize) -> Vec<String> {
+    if visible_len(word) <= max_chars {
+        return vec![word.to_string()];
+    }
+
+    let mut atoms: Vec<&str> = Vec::new();
+    let mut cursor = 0;
+    for tag in INLINE_TAG.find_iter(word) {
+        atoms.extend(word[cursor..tag.start()].split_inclusive(|_| true));
+        atoms.push(tag.as_str());
+        cursor = tag.end();
+    }
+    atoms.extend(word[cursor..].split_inclusive(|_| true));
+
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    let mut pending = String::new();
+    let mut visible = 0;
+    for atom in atoms {
+        if INLINE_TAG.is_match(atom) {
+            match atom.starts_with("</") {
+                true => current.push_str(atom),
+                false => pending.push_str(atom),
+            }
+            continue;
+        }
+        if visible == max_chars {
+            pieces.push(std::mem::take(&mut current));
+            visible = 0;
+        }
+        current.push_str(&std::mem::take(&mut pending));
+        current.push_str(atom);
+        visible += 1;
+    }
+    current.push_str(&pending);
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
+
+fn wrap_cue(text: &str, max_chars: usize) -> Vec<String> {
+    let words: Vec<String> = words(text)
+        .iter()
+        .flat_map(|word| split_long_word(word, max_chars))
+        .collect();
+    if words.is_empty() {
+        return Vec::new();
+    }
+    let joined = words.join(" ");
+    if visible_len(&joined) <= max_chars {
+        return vec![joined];
+    }
+
+    let balanced = (1..words.len())
+        .map(|split| (words[..split].join(" "), words[split..].join(" ")))
+        .filter(|(first, second)| {
+            visible_len(first) <= max_chars && visible_len(second) <= max_chars
+        })
+        .min_by_key(|(first, second)| visible_len(first).abs_diff(visible_len(second)));
+    if let Some((first, second)) = balanced {
+        return vec![first, second];
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for word in words {
+        if !current.is_empty() && visible_len(&current) + 1 + visible_len(&word) > max_chars {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+impl SubtitleDocument {
+    pub fn parse(bytes: &[u8], max_line_chars: usize) -> Result<Self> {
+        let text = String::from_utf8(bytes.to_vec())?;
+        let text = text.strip_prefix('\u{feff}').unwrap_or(&text);
+        let crlf = text.contains("\r\n");
+        let text = text.replace("\r\n", "\n");
+        let blocks: Vec<Block> = BLOCK_BREAK
+            .split(text.trim_matches('\n'))
+            .map(parse_block)
+            .collect();
+        if !blocks
+            .iter()
+            .any(|block| matches!(block, Block::Cue { .. }))
+        {
+            anyhow::bail!("No subtitle cues found");
+        }
+        Ok(SubtitleDocument {
+            windows: cue_windows(&blocks),
+            blocks,
+            crlf,
+            max_line_chars: max_line_chars.max(1),
+        })
+    }
+
+    fn cue_lines(&self, index: usize) -> &[String] {
+        match &self.blocks[index] {
+            Block::Cue { lines, .. } => lines,
+            Block::Verbatim(_) => &[],
+        }
+    }
+}
+
+impl BatchDocument for SubtitleDocument {
+    fn segments(&self) -> Vec<Segment> {
+        self.windows
+            .iter()
+            .map(|window| {
+                let texts: Vec<String> = window
+                    .iter()
+                    .flat_map(|index| cue_pieces(self.cue_lines(*index)))
+                    .collect();
+                let pieces: Vec<&str> = texts.iter().map(String::as_str).collect();
+                let mut segment = Segment::joined(&pieces);
+                segment.protected.extend(
+                    INLINE_TAG
+                        .find_iter(&segment.text)
+                        .map(|tag| tag.start()..tag.end()),
+                );
+                segment.protected.sort_by_key(|range| range.start);
+                segment
+            })
+            .collect()
+    }
+
+    fn render(&self, translations: &[String]) -> Result<Vec<u8>> {
+        let mut blocks = self.blocks.clone();
+        for (window, translated) in self.windows.iter().zip(translations) {
+            let counts: Vec<Option<usize>> = window
+                .iter()
+                .map(|index| dialogue_turns(self.cue_lines(*index)).map(|turns| turns.len()))
+                .collect();
+            let total = counts.iter().map(|count| count.unwrap_or(1)).sum();
+            let mut pieces = split_joined(translated, total).into_iter();
+            for (index, count) in window.iter().zip(counts) {
+                let text = match count {
+                    Some(turns) => pieces
+                        .by_ref()
+                        .take(turns)
+                        .map(|turn| turn.trim().to_string())
+                        .filter(|turn| !turn.is_empty())
+                        .collect(),
+                    None => wrap_cue(&pieces.next().unwrap_or_default(), self.max_line_chars),
+                };
+                if let Block::Cue { lines, .. } = &mut blocks[*index] {
+                    *lines = text;
+                }
+            }
+        }
+
+        let mut output = blocks
+            .iter()
+            .map(|block| match block {
+                Block::Verbatim(text) => text.clone(),
+                Block::Cue { header, lines } => header
+                    .iter()
+                    .chain(lines)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        output.push('\n');
+        if self.crlf {
+            output = output.replace('\n', "\r\n");
+        }
+        Ok(output.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::marker;
+    use super::*;
+
+    const SRT: &str = "1\r\n00:00:01,000 --> 00:00:03,000\r\nI think we should\r\n\r\n2\r\n00:00:03,100 --> 00:00:05,000\r\n<i>leave right now.</i>\r\n\r\n3\r\n00:00:06,000 --> 00:00:07,000\r\n♪\r\n";
+
+    #[test]
+    fn srt_cues_share_a_context_window() {
+        let document = SubtitleDocument::parse(SRT.as_bytes(), 42).unwrap();
+        let segments = document.segments();
+
+        assert_eq!(segments.len(), 1);
+        assert_eq!(
+            segments[0].text,
+            format!("I think we should{}<i>leave right now.</i>", marker(1))
+        );
+        assert_eq!(segments[0].protected.len(), 3);
+    }
+
+    #[test]
+    fn render_keeps_numbering_and_timings() {
+        let document = SubtitleDocument::parse(SRT.as_bytes(), 42).unwrap();
+        let translations = vec![format!(
+            "Ich finde, wir sollten{}<i>jetzt sofort gehen.</i>",
+            marker(1)
+        )];
+        let rendered = String::from_utf8(document.render(&translations).unwrap()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "1\r\n00:00:01,000 --> 00:00:03,000\r\nIch finde, wir sollten\r\n\r\n2\r\n00:00:03,100 --> 00:00:05,000\r\n<i>jetzt sofort gehen.</i>\r\n\r\n3\r\n00:00:06,000 --> 00:00:07,000\r\n♪\r\n"
+        );
+    }
+
+    #[test]
+    fn vtt_header_and_notes_are_kept() {
+        let vtt = "WEBVTT\n\nNOTE translated by hand\n\nintro\n00:01.000 --> 00:02.000 align:start\nHello there.\n";
+        let document = SubtitleDocument::parse(vtt.as_bytes(), 42).unwrap();
+        let rendered =
+            String::from_utf8(document.render(&["Hallo zusammen.".to_string()]).unwrap()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "WEBVTT\n\nNOTE translated by hand\n\nintro\n00:01.000 --> 00:02.000 align:start\nHallo zusammen.\n"
+        );
+    }
+
+    #[test]
+    fn long_cues_are_wrapped_into_balanced_lines() {
+        let lines = wrap_cue(
+            "This sentence is definitely too long for a single subtitle line",
+            42,
+        );
+
+        assert_eq!(
+            lines,
+            vec![
+                "This sentence is definitely too",
+                "long for a single subtitle line"
+            ]
+        );
+        assert_eq!(wrap_cue("<i>Short line</i>", 10), vec!["<i>Short line</i>"]);
+    }
+
+    #[test]
+    fn dialogue_cues_are_translated_line_by_line() {
+        let srt =
+            "1\n00:00:01,000 --> 00:00:03,000\n- Hi, how are you doing today my friend?\n- Fine.\n";
+        let document = SubtitleDocument::parse(srt.as_bytes(), 20).unwrap();
+        let segments = document.segments();
+
+        assert_eq!(
+            segments[0].text,
+            format!(
+                "- Hi, how are you doing today my friend?{}- Fine.",
+                marker(1)
+            )
+        );
+
+        let translations = vec![format!(
+            "- Hallo, wie geht es dir heute, mein Freund?{}- Gut.",
+            marker(1)
+        )];
+        let rendered = String::from_utf8(document.render(&translations).unwrap()).unwrap();
+        assert_eq!(
+            rendered,
+            "1\n00:00:01,000 --> 00:00:03,000\n- Hallo, wie geht es dir heute, mein Freund?\n- Gut.\n"
+        );
+    }
+
+    #[test]
+    fn wrapping_never_cuts_inline_tags() {
+        let lines = wrap_cue("{\\an8}<i>Donaudampfschifffahrt</i>", 8);
+        assert_eq!(lines, vec!["{\\an8}<i>Donaudam", "pfschiff", "fahrt</i>"]);
+
+        let lines = wrap_cue("<font color=\"#ff0000\">Achtung</font> bitte", 8);
+        assert_eq!(
+            lines,
+            vec!["<font color=\"#ff0000\">Achtung</font>", "bitte"]
+        );
+    }
+}
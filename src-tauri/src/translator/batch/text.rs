@@ -0,0 +1,69 @@
+use anyhow::Result;
+
+use super::{BatchDocument, Segment};
+use crate::settings::load_settings;
+use crate::translator::format::{self, TextFormat};
+use crate::translator::segment::{self, Chunk};
+
+pub(super) struct TextDocument {
+    text: String,
+    chunks: Vec<Chunk>,
+}
+
+impl TextDocument {
+    pub fn parse(bytes: &[u8], text_format: TextFormat) -> Result<Self> {
+        let text = String::from_utf8(bytes.to_vec())?;
+        let text = text.strip_prefix('\u{feff}').unwrap_or(&text).to_string();
+        let chunks = match text_format {
+            TextFormat::Plain => segment::split(&text, load_settings().chunk_max_chars)
+                .into_iter()
+                .filter(|chunk| !text[chunk.range.clone()].trim().is_empty())
+                .collect(),
+            _ => format::document_chunks(&text, text_format),
+        };
+        Ok(TextDocument { text, chunks })
+    }
+}
+
+impl BatchDocument for TextDocument {
+    fn segments(&self) -> Vec<Segment> {
+        self.chunks
+            .iter()
+            .map(|chunk| Segment {
+                text: self.text[chunk.range.clone()].to_string(),
+                protected: chunk.protected.clone(),
+                notes: None,
+            })
+            .collect()
+    }
+
+    fn render(&self, translations: &[String]) -> Result<Vec<u8>> {
+        let mut output = String::with_capacity(self.text.len());
+        let mut cursor = 0;
+        for (chunk, translated) in self.chunks.iter().zip(translations) {
+            output.push_str(&self.text[cursor..chunk.range.start]);
+            output.push_str(translated);
+            cursor = chunk.range.end;
+        }
+        output.push_str(&self.text[cursor..]);
+        Ok(output.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_render_keeps_structure() {
+        let document =
+            TextDocument::parse(b"# Title\n\nSome **bold** text.\n", TextFormat::Markdown).unwrap();
+        let segments = document.segments();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[1].text, "Some **bold** text.");
+
+        let translations = vec!["Titel".to_string(), "Etwas **fetter** Text.".to_string()];
+        let rendered = String::from_utf8(document.render(&translations).unwrap()).unwrap();
+        assert_eq!(rendered, "# Titel\n\nEtwas **fetter** Text.\n");
+    }
+}
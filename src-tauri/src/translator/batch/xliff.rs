@@ -0,0 +1,557 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use quick_xml::escape::{partial_escape, unescape};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use regex::Regex;
+use std::ops::Range;
+
+use super::{placeholder_ranges, BatchDocument, Segment};
+
+static INLINE_TAG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<[^<>]+>").expect("valid xliff inline tag pattern"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum XliffVersion {
+    V1,
+    V2,
+}
+
+#[derive(Debug, Clone)]
+struct XliffTarget {
+    span: Range<usize>,
+    inner: String,
+    state: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct XliffEntry {
+    source: String,
+    source_end: usize,
+    indent: String,
+    target: Option<XliffTarget>,
+    segment_tag: Option<(Range<usize>, Option<String>)>,
+    notes: Option<String>,
+    translatable: bool,
+}
+
+#[derive(Debug, Default)]
+struct OpenUnit {
+    name: Option<String>,
+    translatable: bool,
+    notes: Vec<String>,
+    entries: Vec<XliffEntry>,
+}
+
+pub(super) struct XliffDocument {
+    xml: String,
+    version: XliffVersion,
+    entries: Vec<XliffEntry>,
+    language_tags: Vec<Range<usize>>,
+    source_language: Option<String>,
+    target_language: Option<String>,
+    include_fuzzy: bool,
+    target: String,
+}
+
+fn attribute(element: &BytesStart, name: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|attribute| attribute.key.as_ref() == name)
+        .and_then(|attribute| attribute.unescape_value().ok())
+        .map(|value| value.into_owned())
+}
+
+fn set_attribute(tag: &str, name: &str, value: &str) -> String {
+    let existing = Regex::new(&format!(
+        r#"(\s{}\s*=\s*)(?:"[^"]*"|'[^']*')"#,
+        regex::escape(name)
+    ))
+    .expect("valid attribute pattern");
+    if existing.is_match(tag) {
+        return existing
+            .replace(tag, |captures: &regex::Captures| {
+                format!("{}\"{}\"", &captures[1], partial_escape(value))
+            })
+            .into_owned();
+    }
+    let insert_at = match tag.ends_with("/>") {
+        true => tag.len() - 2,
+        false => tag.len() - 1,
+    };
+    format!(
+        "{} {}=\"{}\"{}",
+        tag[..insert_at].trim_end(),
+        name,
+        partial_escape(value),
+        &tag[insert_at..]
+    )
+}
+
+fn line_indent(xml: &str, position: usize) -> String {
+    let line_start = xml[..position].rfind('\n').map_or(0, |index| index + 1);
+    let prefix = &xml[line_start..position];
+    match prefix.chars().all(char::is_whitespace) {
+        true => prefix.to_string(),
+        false => String::new(),
+    }
+}
+
+fn inline_segment(raw: &str) -> (Segment, Vec<String>) {
+    let mut text = String::new();
+    let mut protected = Vec::new();
+    let mut tags = Vec::new();
+    let mut cursor = 0;
+    let push_text = |text: &mut String, raw_text: &str| match unescape(raw_text) {
+        Ok(unescaped) => text.push_str(&unescaped),
+        Err(_) => text.push_str(raw_text),
+    };
+    for tag in INLINE_TAG.find_iter(raw) {
+        push_text(&mut text, &raw[cursor..tag.start()]);
+        let start = text.len();
+        text.push_str(tag.as_str());
+        protected.push(start..text.len());
+        tags.push(tag.as_str().to_string());
+        cursor = tag.end();
+    }
+    push_text(&mut text, &raw[cursor..]);
+    let placeholders: Vec<Range<usize>> = placeholder_ranges(&text)
+        .into_iter()
+        .filter(|range| {
+            !protected
+                .iter()
+                .any(|tag: &Range<usize>| tag.start < range.end && range.start < tag.end)
+        })
+        .collect();
+    protected.extend(placeholders);
+    protected.sort_by_key(|range| range.start);
+    let segment = Segment {
+        text,
+        protected,
+        notes: None,
+    };
+    (segment, tags)
+}
+
+fn render_inline(translated: &str, tags: &[String]) -> String {
+    let mut spans: Vec<Range<usize>> = tags
+        .iter()
+        .flat_map(|tag| {
+            translated
+                .match_indices(tag.as_str())
+                .map(|(start, tag)| start..start + tag.len())
+        })
+        .collect();
+    spans.sort_by_key(|span| span.start);
+
+    let mut output = String::with_capacity(translated.len());
+    let mut cursor = 0;
+    for span in spans {
+        if span.start < cursor {
+            continue;
+        }
+        output.push_str(&partial_escape(&translated[cursor..span.start]));
+        output.push_str(&translated[span.clone()]);
+        cursor = span.end;
+    }
+    output.push_str(&partial_escape(&translated[cursor..]));
+    output
+}
+
+impl XliffEntry {
+    fn visible_text(&self) -> String {
+        INLINE_TAG.replace_all(&self.source, "").into_owned()
+    }
+}
+
+impl XliffDocument {
+    pub fn parse(bytes: &[u8], include_fuzzy: bool) -> Result<Self> {
+        let xml = String::from_utf8(bytes.to_vec())?;
+        let xml = xml.strip_prefix('\u{feff}').unwrap_or(&xml).to_string();
+
+        let mut reader = Reader::from_str(&xml);
+        let mut version = None;
+        let mut entries = Vec::new();
+        let mut language_tags = Vec::new();
+        let mut source_language = None;
+        let mut target_language = None;
+        let mut unit: Option<OpenUnit> = None;
+        let mut segment_tag: Option<(Range<usize>, Option<String>)> = None;
+        let mut source_start: Option<(usize, usize)> = None;
+        let mut target_start: Option<(usize, usize, Option<String>)> = None;
+        let mut note: Option<String> = None;
+        let mut alternative_depth = 0;
+
+        loop {
+            let start = reader.buffer_position() as usize;
+            let event = reader.read_event()?;
+            let end = reader.buffer_position() as usize;
+            if alternative_depth > 0 {
+                match &event {
+                    Event::Start(_) => alternative_depth += 1,
+                    Event::End(_) => alternative_depth -= 1,
+                    Event::Eof => break,
+                    _ => {}
+                }
+                continue;
+            }
+            match &event {
+                Event::Start(element) | Event::Empty(element) => {
+                    let empty = matches!(event, Event::Empty(_));
+                    match element.local_name().as_ref() {
+                        b"xliff" => {
+                            let declared = attribute(element, b"version").unwrap_or_default();
+                            if declared.starts_with('2') {
+                                version = Some(XliffVersion::V2);
+                                source_language = attribute(element, b"srcLang");
+                                target_language = attribute(element, b"trgLang");
+                                language_tags.push(start..end);
+                            } else {
+                                version = Some(XliffVersion::V1);
+                            }
+                        }
+                        b"file" if version == Some(XliffVersion::V1) => {
+                            if source_language.is_none() {
+                                source_language = attribute(element, b"source-language");
+                                target_language = attribute(element, b"target-language");
+                            }
+                            language_tags.push(start..end);
+                        }
+                        b"trans-unit" | b"unit" if !empty => {
+                            unit = Some(OpenUnit {
+                                name: attribute(element, b"resname")
+                                    .or_else(|| attribute(element, b"name")),
+                                translatable: attribute(element, b"translate").as_deref()
+                                    != Some("no"),
+                                ..OpenUnit::default()
+                            });
+                        }
+                        b"alt-trans" | b"ignorable" if !empty => alternative_depth = 1,
+                        b"segment" if !empty => {
+                            segment_tag = Some((start..end, attribute(element, b"state")));
+                        }
+                        b"source" if !empty && unit.is_some() && source_start.is_none() => {
+                            source_start = Some((start, end));
+                        }
+                        b"target" if unit.is_some() && source_start.is_none() => {
+                            let state = attribute(element, b"state");
+                            if empty {
+                                if let Some(entry) =
+                                    unit.as_mut().and_then(|u| u.entries.last_mut())
+                                {
+                                    entry.target = Some(XliffTarget {
+                                        span: start..end,
+                                        inner: String::new(),
+                                        state,
+                                    });
+                                }
+                            } else if target_start.is_none() {
+                                target_start = Some((start, end, state));
+                            }
+                        }
+                        b"note" if !empty && unit.is_some() => note = Some(String::new()),
+                        _ => {}
+                    }
+                }
+                Event::Text(text) => {
+                    if let Some(note) = note.as_mut() {
+                        note.push_str(&text.unescape()?);
+                    }
+                }
+                Event::End(element) => match element.local_name().as_ref() {
+                    b"source" => {
+                        if let (Some((tag_start, inner_start)), Some(open)) =
+                            (source_start.take(), unit.as_mut())
+                        {
+                            open.entries.push(XliffEntry {
+                                source: xml[inner_start..start].to_string(),
+                                source_end: end,
+                                indent: line_indent(&xml, tag_start),
+                                target: None,
+                                segment_tag: segment_tag.clone(),
+                                notes: None,
+                                translatable: true,
+                            });
+                        }
+                    }
+                    b"target" => {
+                        if let Some((tag_start, inner_start, state)) = target_start.take() {
+                            if let Some(entry) = unit.as_mut().and_then(|u| u.entries.last_mut()) {
+                                entry.target = Some(XliffTarget {
+                                    span: tag_start..end,
+                                    inner: xml[inner_start..start].to_string(),
+                                    state,
+                                });
+                            }
+                        }
+                    }
+                    b"segment" => segment_tag = None,
+                    b"note" => {
+                        if let (Some(text), Some(open)) = (note.take(), unit.as_mut()) {
+                            if !text.trim().is_empty() {
+                                open.notes.push(text.trim().to_string());
+                            }
+                        }
+                    }
+                    b"trans-unit" | b"unit" => {
+                        if let Some(open) = unit.take() {
+                            let mut notes = Vec::new();
+                            if let Some(name) = &open.name {
+                                notes.push(format!("Key: {}.", name));
+                            }
+                            if !open.notes.is_empty() {
+                                notes.push(format!("Notes: {}.", open.notes.join(" ")));
+                            }
+                            let notes = (!notes.is_empty()).then(|| notes.join(" "));
+                            entries.extend(open.entries.into_iter().map(|mut entry| {
+                                entry.notes = notes.clone();
+                                entry.translatable = open.translatable;
+                                entry
+                            }));
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        let Some(version) = version else {
+            anyhow::bail!("File is not an XLIFF document");
+        };
+        if entries.is_empty() {
+            anyhow::bail!("No translation units found");
+        }
+        Ok(XliffDocument {
+            xml,
+            version,
+            entries,
+            language_tags,
+            source_language,
+            target_language,
+            include_fuzzy,
+            target: String::new(),
+        })
+    }
+
+    fn is_fuzzy(&self, entry: &XliffEntry) -> bool {
+        match self.version {
+            XliffVersion::V1 => entry
+                .target
+                .as_ref()
+                .and_then(|target| target.state.as_deref())
+                .is_some_and(|state| state.starts_with("needs-") || state == "new"),
+            XliffVersion::V2 => {
+                entry
+                    .segment_tag
+                    .as_ref()
+                    .and_then(|(_, state)| state.as_deref())
+                    .unwrap_or("initial")
+                    == "initial"
+            }
+        }
+    }
+
+    fn is_translatable(&self, entry: &XliffEntry) -> bool {
+        if !entry.translatable || !entry.visible_text().chars().any(char::is_alphanumeric) {
+            return false;
+        }
+        let untranslated = entry
+            .target
+            .as_ref()
+            .is_none_or(|target| target.inner.trim().is_empty());
+        untranslated || (self.include_fuzzy && self.is_fuzzy(entry))
+    }
+
+    fn target_element(&self, inner: &str) -> String {
+        match self.version {
+            XliffVersion::V1 => format!(
+                "<target state=\"needs-review-translation\">{}</target>",
+                inner
+            ),
+            XliffVersion::V2 => format!("<target>{}</target>", inner),
+        }
+    }
+}
+
+impl BatchDocument for XliffDocument {
+    fn segments(&self) -> Vec<Segment> {
+        self.entries
+            .iter()
+            .filter(|entry| self.is_translatable(entry))
+            .map(|entry| {
+                let (mut segment, _) = inline_segment(&entry.source);
+                segment.notes = entry.notes.clone();
+                segment
+            })
+            .collect()
+    }
+
+    fn render(&self, translations: &[String]) -> Result<Vec<u8>> {
+        let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+        let language_attribute = match self.version {
+            XliffVersion::V1 => "target-language",
+            XliffVersion::V2 => "trgLang",
+        };
+        for tag in &self.language_tags {
+            let updated = set_attribute(&self.xml[tag.clone()], language_attribute, &self.target);
+            edits.push((tag.clone(), updated));
+        }
+
+        let entries = self
+            .entries
+            .iter()
+            .filter(|entry| self.is_translatable(entry));
+        for (entry, translated) in entries.zip(translations) {
+            let (_, tags) = inline_segment(&entry.source);
+            let element = self.target_element(&render_inline(translated, &tags));
+            match &entry.target {
+                Some(target) => edits.push((target.span.clone(), element)),
+                None => edits.push((
+                    entry.source_end..entry.source_end,
+                    format!("\n{}{}", entry.indent, element),
+                )),
+            }
+            if let (XliffVersion::V2, Some((tag, _))) = (self.version, &entry.segment_tag) {
+                let updated = set_attribute(&self.xml[tag.clone()], "state", "translated");
+                edits.push((tag.clone(), updated));
+            }
+        }
+
+        edits.sort_by_key(|(range, _)| range.start);
+        let mut output = String::with_capacity(self.xml.len());
+        let mut cursor = 0;
+        for (range, replacement) in edits {
+            if range.start < cursor {
+                continue;
+            }
+            output.push_str(&self.xml[cursor..range.start]);
+            output.push_str(&replacement);
+            cursor = range.end;
+        }
+        output.push_str(&self.xml[cursor..]);
+        Ok(output.into_bytes())
+    }
+
+    fn declared_languages(&self) -> (Option<String>, Option<String>) {
+        (
+            self.source_language
+                .clone()
+                .filter(|language| !language.is_empty()),
+            self.target_language
+                .clone()
+                .filter(|language| !language.is_empty()),
+        )
+    }
+
+    fn set_target_language(&mut self, language: &str) {
+        self.target = language.to_string();
+    }
+
+    fn is_continuous(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XLIFF_12: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xliff version="1.2" xmlns="urn:oasis:names:tc:xliff:document:1.2">
+  <file source-language="en" datatype="plaintext" original="app">
+    <body>
+      <trans-unit id="1" resname="greeting">
+        <source>Hello <g id="b">%s</g> &amp; welcome</source>
+        <note>Shown after login</note>
+      </trans-unit>
+      <trans-unit id="2">
+        <source>Save</source>
+        <target state="translated">Speichern</target>
+      </trans-unit>
+      <trans-unit id="3">
+        <source>Close</source>
+        <target state="needs-review-translation">Schliesen</target>
+      </trans-unit>
+    </body>
+  </file>
+</xliff>
+"#;
+
+    const XLIFF_20: &str = r#"<xliff xmlns="urn:oasis:names:tc:xliff:document:2.0" version="2.0" srcLang="en">
+  <file id="f1">
+    <unit id="u1" name="title">
+      <segment>
+        <source>Settings</source>
+      </segment>
+    </unit>
+    <unit id="u2">
+      <segment state="final">
+        <source>Quit</source>
+        <target>Beenden</target>
+      </segment>
+    </unit>
+  </file>
+</xliff>
+"#;
+
+    #[test]
+    fn xliff_12_units_become_segments() {
+        let document = XliffDocument::parse(XLIFF_12.as_bytes(), false).unwrap();
+        let segments = document.segments();
+
+        assert_eq!(
+            document.declared_languages(),
+            (Some("en".to_string()), None)
+        );
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].text, "Hello <g id=\"b\">%s</g> & welcome");
+        assert_eq!(segments[0].protected, vec![6..16, 16..18, 18..22]);
+        assert_eq!(
+            segments[0].notes.as_deref(),
+            Some("Key: greeting. Notes: Shown after login.")
+        );
+        assert_eq!(
+            XliffDocument::parse(XLIFF_12.as_bytes(), true)
+                .unwrap()
+                .segments()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn xliff_12_render_inserts_reviewable_targets() {
+        let mut document = XliffDocument::parse(XLIFF_12.as_bytes(), false).unwrap();
+        document.set_target_language("de");
+        let translations = vec!["Hallo <g id=\"b\">%s</g> & willkommen".to_string()];
+        let rendered = String::from_utf8(document.render(&translations).unwrap()).unwrap();
+
+        assert!(rendered.contains(r#"<file source-language="en" datatype="plaintext" original="app" target-language="de">"#));
+        assert!(rendered.contains(
+            "<source>Hello <g id=\"b\">%s</g> &amp; welcome</source>\n        <target state=\"needs-review-translation\">Hallo <g id=\"b\">%s</g> &amp; willkommen</target>\n        <note>"
+        ));
+        assert!(rendered.contains("<target state=\"translated\">Speichern</target>"));
+    }
+
+    #[test]
+    fn xliff_20_render_marks_segments_translated() {
+        let mut document = XliffDocument::parse(XLIFF_20.as_bytes(), false).unwrap();
+        document.set_target_language("fr");
+        let segments = document.segments();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].notes.as_deref(), Some("Key: title."));
+
+        let rendered =
+            String::from_utf8(document.render(&["Paramètres".to_string()]).unwrap()).unwrap();
+        assert!(rendered.starts_with(
+            r#"<xliff xmlns="urn:oasis:names:tc:xliff:document:2.0" version="2.0" srcLang="en" trgLang="fr">"#
+        ));
+        assert!(rendered.contains(
+            "<segment state=\"translated\">\n        <source>Settings</source>\n        <target>Paramètres</target>\n      </segment>"
+        ));
+        assert!(rendered.contains("<segment state=\"final\">\n        <source>Quit</source>\n        <target>Beenden</target>"));
+    }
+}
@@ -0,0 +1,330 @@
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::providers;
+use crate::settings::{load_api_keys, load_settings, save_settings_to_disk};
+
+const PERSIST_DELAY: Duration = Duration::from_secs(2);
+
+static CACHE: Mutex<Option<TranslationCache>> = Mutex::new(None);
+static PERSIST_LOCK: Mutex<()> = Mutex::new(());
+static PERSIST_SCHEDULED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CacheKey {
+    #[serde(default)]
+    pub provider: String,
+    pub text: String,
+    pub source: String,
+    pub target: String,
+    pub mode: String,
+    pub style: String,
+}
+
+impl CacheKey {
+    pub fn new(text: &str, source: &str, target: &str, mode: &str, style: &str) -> Self {
+        let provider = providers::cache_scope(&load_api_keys());
+        Self::for_provider(&provider, text, source, target, mode, style)
+    }
+
+    fn for_provider(
+        provider: &str,
+        text: &str,
+        source: &str,
+        target: &str,
+        mode: &str,
+        style: &str,
+    ) -> Self {
+        CacheKey {
+            provider: provider.to_string(),
+            text: text.trim().to_string(),
+            source: source.to_lowercase(),
+            target: target.to_lowercase(),
+            mode: mode.to_string(),
+            style: style.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub key: CacheKey,
+    pub result: String,
+    pub created_at: u64,
+    pub last_used: u64,
+    pub hits: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub max_entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub disk_bytes: u64,
+}
+
+#[derive(Default)]
+struct TranslationCache {
+    entries: HashMap<CacheKey, CacheEntry>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+    dirty: bool,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+fn get_cache_path() -> Option<PathBuf> {
+    let app_dir = dirs::data_dir()?.join("langra");
+    fs::create_dir_all(&app_dir).ok()?;
+    Some(app_dir.join("translation_cache.json"))
+}
+
+impl TranslationCache {
+    fn load() -> Self {
+        let mut cache = TranslationCache::default();
+        let Some(path) = get_cache_path() else {
+            return cache;
+        };
+
+        let stored: Vec<CacheEntry> = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log_line!("[CACHE] Failed to parse cache file: {:?}", e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+
+        for entry in stored {
+            cache.tick = cache.tick.max(entry.last_used);
+            cache.entries.insert(entry.key.clone(), entry);
+        }
+        log_line!("[CACHE] Loaded {} cached results", cache.entries.len());
+        cache
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn evict_to(&mut self, max_entries: usize) -> bool {
+        let mut evicted = false;
+        while self.entries.len() > max_entries {
+            let Some(oldest) = self
+                .entries
+                .values()
+                .min_by_key(|entry| entry.last_used)
+                .map(|entry| entry.key.clone())
+            else {
+                break;
+            };
+            self.entries.remove(&oldest);
+            evicted = true;
+        }
+        evicted
+    }
+}
+
+fn write_entries(mut entries: Vec<CacheEntry>) {
+    let Some(path) = get_cache_path() else {
+        return;
+    };
+
+    entries.sort_by_key(|entry| entry.last_used);
+    match serde_json::to_string(&entries) {
+        Ok(json) => {
+            if let Err(e) = fs::write(&path, json) {
+                log_line!("[CACHE] Failed to write cache file: {:?}", e);
+            }
+        }
+        Err(e) => log_line!("[CACHE] Failed to serialize cache: {:?}", e),
+    }
+}
+
+fn schedule_flush() {
+    if PERSIST_SCHEDULED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    std::thread::spawn(|| {
+        std::thread::sleep(PERSIST_DELAY);
+        flush();
+    });
+}
+
+pub fn flush() {
+    let _writing = PERSIST_LOCK.lock();
+    PERSIST_SCHEDULED.store(false, Ordering::SeqCst);
+    let entries = {
+        let mut cache_lock = CACHE.lock();
+        let Some(cache) = cache_lock.as_mut().filter(|cache| cache.dirty) else {
+            return;
+        };
+        cache.dirty = false;
+        cache.entries.values().cloned().collect()
+    };
+    write_entries(entries);
+}
+
+fn with_cache<T>(f: impl FnOnce(&mut TranslationCache) -> T) -> T {
+    let mut cache_lock = CACHE.lock();
+    let cache = cache_lock.get_or_insert_with(TranslationCache::load);
+    f(cache)
+}
+
+pub fn lookup(key: &CacheKey) -> Option<String> {
+    with_cache(|cache| {
+        let tick = cache.next_tick();
+        match cache.entries.get_mut(key) {
+            Some(entry) => {
+                entry.last_used = tick;
+                entry.hits += 1;
+                let result = entry.result.clone();
+                cache.hits += 1;
+                log_line!(
+                    "[CACHE] Hit for {} {} -> {}",
+                    key.mode,
+                    key.source,
+                    key.target
+                );
+                Some(result)
+            }
+            None => {
+                cache.misses += 1;
+                None
+            }
+        }
+    })
+}
+
+pub fn store(key: CacheKey, result: &str) {
+    if key.text.is_empty() || result.trim().is_empty() {
+        return;
+    }
+
+    let max_entries = load_settings().cache_max_entries;
+    if max_entries == 0 {
+        return;
+    }
+
+    with_cache(|cache| {
+        let tick = cache.next_tick();
+        let entry = CacheEntry {
+            key: key.clone(),
+            result: result.to_string(),
+            created_at: now_secs(),
+            last_used: tick,
+            hits: 0,
+        };
+        cache.entries.insert(key, entry);
+        cache.evict_to(max_entries);
+        cache.dirty = true;
+    });
+    schedule_flush();
+}
+
+#[tauri::command]
+pub fn get_cache_stats() -> CacheStats {
+    let disk_bytes = get_cache_path()
+        .and_then(|path| fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+
+    let max_entries = load_settings().cache_max_entries;
+
+    with_cache(|cache| CacheStats {
+        entries: cache.entries.len(),
+        max_entries,
+        hits: cache.hits,
+        misses: cache.misses,
+        disk_bytes,
+    })
+}
+
+#[tauri::command]
+pub fn get_cache_entries(limit: Option<usize>) -> Vec<CacheEntry> {
+    with_cache(|cache| {
+        let mut entries: Vec<CacheEntry> = cache.entries.values().cloned().collect();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.last_used));
+        entries.truncate(limit.unwrap_or(50));
+        entries
+    })
+}
+
+#[tauri::command]
+pub fn set_cache_limit(max_entries: usize) -> Result<CacheStats, String> {
+    log_line!("[CACHE] Setting cache limit to {} entries", max_entries);
+
+    let mut settings = load_settings();
+    settings.cache_max_entries = max_entries;
+    save_settings_to_disk(&settings)?;
+
+    with_cache(|cache| {
+        if cache.evict_to(max_entries) {
+            cache.dirty = true;
+        }
+    });
+    flush();
+
+    Ok(get_cache_stats())
+}
+
+#[tauri::command]
+pub fn clear_cache() -> Result<(), String> {
+    log_line!("[CACHE] Clearing translation cache");
+
+    let _writing = PERSIST_LOCK.lock();
+    with_cache(|cache| {
+        cache.entries.clear();
+        cache.hits = 0;
+        cache.misses = 0;
+        cache.dirty = false;
+    });
+
+    if let Some(path) = get_cache_path().filter(|path| path.exists()) {
+        fs::remove_file(&path).map_err(|e| format!("Failed to delete cache file: {}", e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::ApiKeySettings;
+
+    fn key(provider: &str, text: &str) -> CacheKey {
+        CacheKey::for_provider(provider, text, "de", "en", "translate", "friendly")
+    }
+
+    #[test]
+    fn key_keeps_inner_whitespace() {
+        assert_eq!(key("deepl", "  a\n\nb \n"), key("deepl", "a\n\nb"));
+        assert_ne!(key("deepl", "a\n\nb"), key("deepl", "a b"));
+        assert_ne!(key("deepl", "# Title\ntext"), key("deepl", "# Title text"));
+    }
+
+    #[test]
+    fn key_depends_on_provider_and_model() {
+        assert_ne!(key("deepl", "Hallo"), key("backend", "Hallo"));
+
+        let mut api_settings = ApiKeySettings {
+            provider: "openai".to_string(),
+            openai_model: "gpt-4o".to_string(),
+            ..ApiKeySettings::default()
+        };
+        let first = providers::cache_scope(&api_settings);
+        api_settings.openai_model = "gpt-4.1".to_string();
+        assert_ne!(first, providers::cache_scope(&api_settings));
+    }
+}
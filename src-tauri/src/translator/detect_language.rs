@@ -1,34 +1,100 @@
 use anyhow::Result;
-use crate::get_access_token;
+
+use super::cache::{self, CacheKey};
+use super::languages::{is_known_language, normalize_code};
+use super::local_detect::{detect_local, LOCAL_CONFIDENCE_THRESHOLD};
+use super::providers::{active_provider, collect_last};
+
+async fn detect_remote(text: &str) -> Result<String> {
+    let provider = active_provider()?;
+    let stream = provider.detect(text.to_string()).await?;
+    let reply = collect_last(stream).await?;
+    match parse_language_reply(&reply) {
+        Some(lang) => Ok(lang),
+        None => anyhow::bail!(
+            "Remote detector returned an invalid language code: '{}'",
+            reply.trim()
+        ),
+    }
+}
+
+fn parse_language_reply(reply: &str) -> Option<String> {
+    let code = reply
+        .trim()
+        .trim_matches(|c: char| matches!(c, '"' | '\'' | '`' | '.'));
+    let is_tag = !code.is_empty()
+        && code
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    let lang = normalize_code(code);
+    (is_tag && lang.len() == 2 && is_known_language(&lang)).then_some(lang)
+}
 
 pub async fn detect_language(text: &str) -> Result<String> {
     let sample: String = text.chars().take(100).collect();
-    println!("[DETECT_LANG] Detecting language for text sample: '{}'", sample);
-
-    let token = get_access_token().map_err(|e| anyhow::anyhow!(e))?;
-
-    let client = reqwest::Client::new();
-    let response = client
-        .post("https://white-bush-0ea25dc03.3.azurestaticapps.net/api/detect-language")
-        .header("Authorization", format!("Bearer {}", token))
-        .json(&serde_json::json!({
-            "text": text,
-        }))
-        .send()
-        .await?;
-
-    if !response.status().is_success() {
-        let error_text = response.text().await?;
-        println!("[DETECT_LANG] Detection failed: {}, falling back to 'en'", error_text);
-        return Ok("en".to_string());
-    }
+    log_line!(
+        "[DETECT_LANG] Detecting language for text sample: '{}'",
+        sample
+    );
 
-    let result: serde_json::Value = response.json().await?;
-    let detected_lang = result["language"]
-        .as_str()
-        .unwrap_or("en")
-        .to_string();
+    let cache_key = CacheKey::new(text, "", "", "detect", "");
+    if let Some(cached) = cache::lookup(&cache_key) {
+        log_line!("[DETECT_LANG] Detected language from cache: {}", cached);
+        return Ok(cached);
+    }
 
-    println!("[DETECT_LANG] Detected language: {}", detected_lang);
+    let detected_lang = detect_uncached(text).await?;
+    cache::store(cache_key, &detected_lang);
     Ok(detected_lang)
 }
+
+async fn detect_uncached(text: &str) -> Result<String> {
+    let candidates = detect_local(text);
+    let best_local = candidates.first().cloned();
+
+    if let Some(best) = best_local
+        .as_ref()
+        .filter(|best| best.confidence >= LOCAL_CONFIDENCE_THRESHOLD)
+    {
+        log_line!(
+            "[DETECT_LANG] Detected language locally: {} ({:.2})",
+            best.language,
+            best.confidence
+        );
+        return Ok(best.language.clone());
+    }
+
+    log_line!("[DETECT_LANG] Low local confidence, consulting remote detector");
+    match detect_remote(text).await {
+        Ok(lang) => {
+            log_line!("[DETECT_LANG] Detected language remotely: {}", lang);
+            Ok(lang)
+        }
+        Err(e) => match best_local {
+            Some(best) => {
+                log_line!(
+                    "[DETECT_LANG] Remote detection failed: {}, using local guess '{}'",
+                    e,
+                    best.language
+                );
+                Ok(best.language)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_reply_must_be_a_known_language_code() {
+        assert_eq!(parse_language_reply(" FR\n").as_deref(), Some("fr"));
+        assert_eq!(parse_language_reply("\"pt-BR\".").as_deref(), Some("pt"));
+        assert_eq!(parse_language_reply("English"), None);
+        assert_eq!(parse_language_reply("The language is fr."), None);
+        assert_eq!(parse_language_reply("xx"), None);
+        assert_eq!(parse_language_reply(""), None);
+    }
+}
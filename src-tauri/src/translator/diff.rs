@@ -0,0 +1,287 @@
+use parking_lot::Mutex;
+use serde::Serialize;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::events::EventSink;
+
+const MAX_TABLE_CELLS: usize = 4_000_000;
+
+static LAST_DIFF: Mutex<Option<TextDiff>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpanKind {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffSpan {
+    pub kind: SpanKind,
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hunk: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffHunk {
+    pub id: usize,
+    pub original: String,
+    pub enhanced: String,
+    pub chars: Vec<DiffSpan>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TextDiff {
+    pub job_id: u64,
+    pub spans: Vec<DiffSpan>,
+    pub hunks: Vec<DiffHunk>,
+}
+
+fn diff_sequences<T: PartialEq + Copy>(old: &[T], new: &[T]) -> Vec<(SpanKind, T)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<(SpanKind, T)> = old[..prefix]
+        .iter()
+        .map(|item| (SpanKind::Equal, *item))
+        .collect();
+
+    let rows = old_middle.len() + 1;
+    let columns = new_middle.len() + 1;
+    if rows.saturating_mul(columns) > MAX_TABLE_CELLS {
+        ops.extend(old_middle.iter().map(|item| (SpanKind::Delete, *item)));
+        ops.extend(new_middle.iter().map(|item| (SpanKind::Insert, *item)));
+    } else {
+        let mut table = vec![0u32; rows * columns];
+        for i in (0..old_middle.len()).rev() {
+            for j in (0..new_middle.len()).rev() {
+                table[i * columns + j] = if old_middle[i] == new_middle[j] {
+                    table[(i + 1) * columns + j + 1] + 1
+                } else {
+                    table[(i + 1) * columns + j].max(table[i * columns + j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < old_middle.len() && j < new_middle.len() {
+            if old_middle[i] == new_middle[j] {
+                ops.push((SpanKind::Equal, old_middle[i]));
+                i += 1;
+                j += 1;
+            } else if table[(i + 1) * columns + j] >= table[i * columns + j + 1] {
+                ops.push((SpanKind::Delete, old_middle[i]));
+                i += 1;
+            } else {
+                ops.push((SpanKind::Insert, new_middle[j]));
+                j += 1;
+            }
+        }
+        ops.extend(old_middle[i..].iter().map(|item| (SpanKind::Delete, *item)));
+        ops.extend(new_middle[j..].iter().map(|item| (SpanKind::Insert, *item)));
+    }
+
+    ops.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|item| (SpanKind::Equal, *item)),
+    );
+    ops
+}
+
+fn push_span(spans: &mut Vec<DiffSpan>, kind: SpanKind, text: &str, hunk: Option<usize>) {
+    if text.is_empty() {
+        return;
+    }
+    match spans.last_mut() {
+        Some(last) if last.kind == kind && last.hunk == hunk => last.text.push_str(text),
+        _ => spans.push(DiffSpan {
+            kind,
+            text: text.to_string(),
+            hunk,
+        }),
+    }
+}
+
+fn char_spans(original: &str, enhanced: &str, hunk: usize) -> Vec<DiffSpan> {
+    let old: Vec<&str> = original.graphemes(true).collect();
+    let new: Vec<&str> = enhanced.graphemes(true).collect();
+    let mut spans = Vec::new();
+    let mut deleted = String::new();
+    let mut inserted = String::new();
+    for (kind, grapheme) in diff_sequences(&old, &new) {
+        match kind {
+            SpanKind::Delete => deleted.push_str(grapheme),
+            SpanKind::Insert => inserted.push_str(grapheme),
+            SpanKind::Equal => {
+                push_span(&mut spans, SpanKind::Delete, &deleted, Some(hunk));
+                push_span(&mut spans, SpanKind::Insert, &inserted, Some(hunk));
+                deleted.clear();
+                inserted.clear();
+                push_span(&mut spans, SpanKind::Equal, grapheme, Some(hunk));
+            }
+        }
+    }
+    push_span(&mut spans, SpanKind::Delete, &deleted, Some(hunk));
+    push_span(&mut spans, SpanKind::Insert, &inserted, Some(hunk));
+    spans
+}
+
+fn close_hunk(
+    deleted: &mut String,
+    inserted: &mut String,
+    spans: &mut Vec<DiffSpan>,
+    hunks: &mut Vec<DiffHunk>,
+) {
+    if deleted.is_empty() && inserted.is_empty() {
+        return;
+    }
+    let id = hunks.len();
+    push_span(spans, SpanKind::Delete, deleted, Some(id));
+    push_span(spans, SpanKind::Insert, inserted, Some(id));
+    hunks.push(DiffHunk {
+        id,
+        chars: char_spans(deleted, inserted, id),
+        original: std::mem::take(deleted),
+        enhanced: std::mem::take(inserted),
+    });
+}
+
+pub fn compute_diff(job_id: u64, original: &str, enhanced: &str) -> TextDiff {
+    let old: Vec<&str> = original.split_word_bounds().collect();
+    let new: Vec<&str> = enhanced.split_word_bounds().collect();
+
+    let mut spans = Vec::new();
+    let mut hunks = Vec::new();
+    let mut deleted = String::new();
+    let mut inserted = String::new();
+    for (kind, token) in diff_sequences(&old, &new) {
+        match kind {
+            SpanKind::Delete => deleted.push_str(token),
+            SpanKind::Insert => inserted.push_str(token),
+            SpanKind::Equal => {
+                close_hunk(&mut deleted, &mut inserted, &mut spans, &mut hunks);
+                push_span(&mut spans, SpanKind::Equal, token, None);
+            }
+        }
+    }
+    close_hunk(&mut deleted, &mut inserted, &mut spans, &mut hunks);
+
+    TextDiff {
+        job_id,
+        spans,
+        hunks,
+    }
+}
+
+pub fn apply_hunks(diff: &TextDiff, accepted: &[usize]) -> String {
+    let mut output = String::new();
+    for span in &diff.spans {
+        let keep = match (span.kind, span.hunk) {
+            (SpanKind::Equal, _) => true,
+            (SpanKind::Insert, Some(hunk)) => accepted.contains(&hunk),
+            (SpanKind::Delete, Some(hunk)) => !accepted.contains(&hunk),
+            (_, None) => true,
+        };
+        if keep {
+            output.push_str(&span.text);
+        }
+    }
+    output
+}
+
+pub(super) fn emit_enhancement_diff(
+    events: &dyn EventSink,
+    job_id: u64,
+    original: &str,
+    enhanced: &str,
+) {
+    let diff = compute_diff(job_id, original, enhanced);
+    log_line!("[DIFF] Enhancement changed {} hunks", diff.hunks.len());
+    *LAST_DIFF.lock() = Some(diff.clone());
+    events.emit("enhancement-diff", diff);
+}
+
+#[tauri::command]
+pub fn apply_diff_hunks(job_id: u64, accepted: Vec<usize>) -> Result<String, String> {
+    let last = LAST_DIFF.lock();
+    let diff = last
+        .as_ref()
+        .filter(|diff| diff.job_id == job_id)
+        .ok_or_else(|| "No diff available for this enhancement".to_string())?;
+    if let Some(unknown) = accepted.iter().find(|id| **id >= diff.hunks.len()) {
+        return Err(format!("Unknown diff hunk: {}", unknown));
+    }
+    log_line!(
+        "[DIFF] Applying {} of {} hunks",
+        accepted.len(),
+        diff.hunks.len()
+    );
+    Ok(apply_hunks(diff, &accepted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_changes_become_hunks() {
+        let diff = compute_diff(1, "I has a apple.", "I have an apple.");
+
+        assert_eq!(diff.hunks.len(), 2);
+        assert_eq!(
+            (
+                diff.hunks[0].original.as_str(),
+                diff.hunks[0].enhanced.as_str()
+            ),
+            ("has", "have")
+        );
+        assert_eq!(
+            (
+                diff.hunks[1].original.as_str(),
+                diff.hunks[1].enhanced.as_str()
+            ),
+            ("a", "an")
+        );
+    }
+
+    #[test]
+    fn hunks_carry_character_spans() {
+        let diff = compute_diff(1, "Teh cat", "The cat");
+        let chars: Vec<(SpanKind, &str)> = diff.hunks[0]
+            .chars
+            .iter()
+            .map(|span| (span.kind, span.text.as_str()))
+            .collect();
+
+        assert_eq!(
+            chars,
+            vec![
+                (SpanKind::Equal, "T"),
+                (SpanKind::Delete, "e"),
+                (SpanKind::Equal, "h"),
+                (SpanKind::Insert, "e"),
+            ]
+        );
+    }
+
+    #[test]
+    fn accepted_hunks_build_final_text() {
+        let original = "I has a apple.";
+        let enhanced = "I have an apple.";
+        let diff = compute_diff(1, original, enhanced);
+
+        assert_eq!(apply_hunks(&diff, &[]), original);
+        assert_eq!(apply_hunks(&diff, &[0, 1]), enhanced);
+        assert_eq!(apply_hunks(&diff, &[1]), "I has an apple.");
+    }
+}
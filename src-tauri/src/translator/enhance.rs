@@ -1,56 +1,61 @@
 use anyhow::Result;
-use tauri::{AppHandle, Emitter};
-use crate::get_access_token;
+use futures_util::StreamExt;
+use serde::Serialize;
+use std::ops::Range;
 
-async fn enhance_with_backend_api(text: &str, language: &str, instruction: Option<&str>, token: &str, app: &AppHandle) -> Result<String> {
-    println!("[ENHANCE] Using backend API");
+use super::cache::{self, CacheKey};
+use super::diff;
+use super::events::EventSink;
+use super::protect;
+use super::providers::{
+    active_provider, CorrectionCategory, CorrectionRequest, EnhanceRequest, ProposedCorrection,
+};
+use super::stream_events::{DeltaEmitter, StreamKind};
+use super::AppliedStyle;
 
-    let client = reqwest::Client::new();
-    let mut json_body = serde_json::json!({
-        "text": text,
-        "language": language,
-    });
-
-    if let Some(inst) = instruction {
-        json_body["instruction"] = serde_json::json!(inst);
+async fn enhance_with_provider(
+    text: &str,
+    language: &str,
+    instruction: Option<&str>,
+    style: &AppliedStyle,
+    job_id: u64,
+    events: &dyn EventSink,
+) -> Result<String> {
+    let mut emitter = DeltaEmitter::new(events, job_id, StreamKind::Enhancement);
+    let mode = match instruction {
+        Some(instruction) => format!("enhance:{}", instruction.trim()),
+        None => "enhance".to_string(),
+    };
+    let cache_key = CacheKey::new(text, language, language, &mode, &style.cache_tag());
+    if let Some(cached) = cache::lookup(&cache_key) {
+        emitter.finish(&cached);
+        diff::emit_enhancement_diff(events, job_id, text, &cached);
+        return Ok(cached);
     }
 
-    let response = client
-        .post("https://white-bush-0ea25dc03.3.azurestaticapps.net/api/enhance")
-        .header("Authorization", format!("Bearer {}", token))
-        .json(&json_body)
-        .send()
-        .await?;
+    let masked = protect::mask_with_settings(text, &[]);
+    let provider = active_provider()?;
+    log_line!("[ENHANCE] Using provider: {}", provider.name());
 
-    if !response.status().is_success() {
-        let error_text = response.text().await?;
-        anyhow::bail!("Backend API error: {}", error_text);
-    }
+    let mut stream = provider
+        .enhance(EnhanceRequest {
+            text: masked.text.clone(),
+            language: language.to_string(),
+            instruction: instruction.map(str::to_string),
+            style: style.clone(),
+        })
+        .await?;
 
-    let mut result = String::new();
-    let mut stream = response.bytes_stream();
-
-    use futures_util::StreamExt;
-    while let Some(chunk) = stream.next().await {
-        let chunk = chunk?;
-        let chunk_str = String::from_utf8_lossy(&chunk);
-
-        for line in chunk_str.lines() {
-            if line.starts_with("data: ") {
-                let data = &line[6..];
-                if data == "[DONE]" {
-                    break;
-                }
-                if let Ok(json) = serde_json::from_str::<serde_json::Value>(data) {
-                    if let Some(content) = json["content"].as_str() {
-                        result = content.to_string();
-                        let _ = app.emit("enhancement-partial", result.clone());
-                    }
-                }
-            }
-        }
+    let mut raw = String::new();
+    while let Some(update) = stream.next().await {
+        update?.apply_to(&mut raw);
+        emitter.update(&masked.unmask_partial(&raw));
     }
+    let result = masked.unmask(&raw)?;
+    emitter.finish(&result);
+    diff::emit_enhancement_diff(events, job_id, text, &result);
 
+    cache::store(cache_key, &result);
     Ok(result)
 }
 
@@ -58,19 +63,206 @@ pub async fn enhance_stream_with_instruction(
     text: &str,
     language: &str,
     instruction: &str,
-    app: &AppHandle,
+    style: &AppliedStyle,
+    job_id: u64,
+    events: &dyn EventSink,
+) -> Result<String> {
+    log_line!("[ENHANCE_CUSTOM] Starting text improvement with custom instruction...");
+    log_line!(
+        "[ENHANCE_CUSTOM] Language: {}, Instruction: {}",
+        language,
+        instruction
+    );
+
+    enhance_with_provider(text, language, Some(instruction), style, job_id, events).await
+}
+
+pub async fn enhance_stream(
+    text: &str,
+    language: &str,
+    style: &AppliedStyle,
+    job_id: u64,
+    events: &dyn EventSink,
 ) -> Result<String> {
-    println!("[ENHANCE_CUSTOM] Starting text improvement with custom instruction...");
-    println!("[ENHANCE_CUSTOM] Language: {}, Instruction: {}", language, instruction);
+    log_line!("[ENHANCE] Starting text correction...");
+    log_line!("[ENHANCE] Language: {}", language);
+
+    enhance_with_provider(text, language, None, style, job_id, events).await
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Correction {
+    pub start: usize,
+    pub end: usize,
+    pub original: String,
+    pub replacement: String,
+    pub category: CorrectionCategory,
+    pub explanation: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CorrectionReport {
+    pub job_id: u64,
+    pub corrected: String,
+    pub corrections: Vec<Correction>,
+}
+
+fn utf16_len(text: &str) -> usize {
+    text.encode_utf16().count()
+}
+
+fn locate_corrections(text: &str, proposed: Vec<ProposedCorrection>) -> (String, Vec<Correction>) {
+    let mut located: Vec<(Range<usize>, ProposedCorrection)> = Vec::new();
+    let mut cursor = 0;
+    for correction in proposed {
+        if correction.original.is_empty() || correction.original == correction.replacement {
+            continue;
+        }
+        let found = text[cursor..]
+            .match_indices(&correction.original)
+            .map(|(offset, _)| cursor + offset)
+            .chain(
+                text.match_indices(&correction.original)
+                    .map(|(offset, _)| offset),
+            )
+            .map(|start| start..start + correction.original.len())
+            .find(|range| {
+                located
+                    .iter()
+                    .all(|(taken, _)| range.end <= taken.start || range.start >= taken.end)
+            });
+        match found {
+            Some(range) => {
+                cursor = range.end;
+                located.push((range, correction));
+            }
+            None => log_line!(
+                "[ENHANCE_CORRECTIONS] Could not locate correction for '{}'",
+                correction.original
+            ),
+        }
+    }
+    located.sort_by_key(|(range, _)| range.start);
+
+    let mut corrected = String::with_capacity(text.len());
+    let mut last = 0;
+    let corrections = located
+        .into_iter()
+        .map(|(range, correction)| {
+            corrected.push_str(&text[last..range.start]);
+            corrected.push_str(&correction.replacement);
+            last = range.end;
+            Correction {
+                start: utf16_len(&text[..range.start]),
+                end: utf16_len(&text[..range.end]),
+                original: correction.original,
+                replacement: correction.replacement,
+                category: correction.category,
+                explanation: correction.explanation.trim().to_string(),
+            }
+        })
+        .collect();
+    corrected.push_str(&text[last..]);
+    (corrected, corrections)
+}
+
+pub async fn enhance_with_corrections(
+    text: &str,
+    language: &str,
+    explanation_language: &str,
+    style: &AppliedStyle,
+    job_id: u64,
+    events: &dyn EventSink,
+) -> Result<CorrectionReport> {
+    log_line!("[ENHANCE_CORRECTIONS] Starting structured correction...");
+    log_line!(
+        "[ENHANCE_CORRECTIONS] Language: {}, explanations in: {}",
+        language,
+        explanation_language
+    );
+
+    let masked = protect::mask_with_settings(text, &[]);
+    let provider = active_provider()?;
+    let proposed: Vec<ProposedCorrection> = provider
+        .corrections(CorrectionRequest {
+            text: masked.text.clone(),
+            language: language.to_string(),
+            explanation_language: explanation_language.to_string(),
+            style: style.clone(),
+        })
+        .await?
+        .into_iter()
+        .map(|correction| ProposedCorrection {
+            original: masked.unmask_partial(&correction.original),
+            replacement: masked.unmask_partial(&correction.replacement),
+            ..correction
+        })
+        .collect();
+
+    let (corrected, corrections) = locate_corrections(text, proposed);
+    log_line!(
+        "[ENHANCE_CORRECTIONS] Located {} corrections",
+        corrections.len()
+    );
 
-    let token = get_access_token().map_err(|e| anyhow::anyhow!(e))?;
-    enhance_with_backend_api(text, language, Some(instruction), &token, app).await
+    DeltaEmitter::new(events, job_id, StreamKind::Enhancement).finish(&corrected);
+    diff::emit_enhancement_diff(events, job_id, text, &corrected);
+    let report = CorrectionReport {
+        job_id,
+        corrected,
+        corrections,
+    };
+    events.emit("enhancement-corrections", &report);
+    Ok(report)
 }
 
-pub async fn enhance_stream(text: &str, language: &str, app: &AppHandle) -> Result<String> {
-    println!("[ENHANCE] Starting text correction...");
-    println!("[ENHANCE] Language: {}", language);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proposed(original: &str, replacement: &str) -> ProposedCorrection {
+        ProposedCorrection {
+            original: original.to_string(),
+            replacement: replacement.to_string(),
+            category: CorrectionCategory::Grammar,
+            explanation: " Explanation. ".to_string(),
+        }
+    }
 
-    let token = get_access_token().map_err(|e| anyhow::anyhow!(e))?;
-    enhance_with_backend_api(text, language, None, &token, app).await
+    #[test]
+    fn corrections_are_located_in_order() {
+        let (corrected, corrections) = locate_corrections(
+            "Ich habe ein Äpfel, und ich habe ein Birne.",
+            vec![
+                proposed("ein Äpfel", "einen Apfel"),
+                proposed("ein Birne", "eine Birne"),
+            ],
+        );
+
+        assert_eq!(corrected, "Ich habe einen Apfel, und ich habe eine Birne.");
+        assert_eq!(
+            corrections
+                .iter()
+                .map(|correction| (correction.start, correction.end))
+                .collect::<Vec<_>>(),
+            vec![(9, 18), (33, 42)]
+        );
+        assert_eq!(corrections[0].explanation, "Explanation.");
+    }
+
+    #[test]
+    fn repeated_and_unknown_passages_do_not_overlap() {
+        let (corrected, corrections) = locate_corrections(
+            "teh cat and teh dog",
+            vec![
+                proposed("teh", "the"),
+                proposed("missing", "gone"),
+                proposed("teh", "the"),
+            ],
+        );
+
+        assert_eq!(corrected, "the cat and the dog");
+        assert_eq!(corrections.len(), 2);
+        assert_eq!(corrections[1].start, 12);
+    }
 }
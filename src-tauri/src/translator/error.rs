@@ -0,0 +1,308 @@
+use serde::Serialize;
+use std::fmt;
+
+use super::sse::SseServerError;
+use crate::http::FirstByteTimeout;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranslatorError {
+    Unauthorized(String),
+    Forbidden(String),
+    QuotaExceeded(String),
+    RateLimited {
+        retry_after: Option<u64>,
+        message: String,
+    },
+    Network(String),
+    Timeout(String),
+    BackendError {
+        status: Option<u16>,
+        message: String,
+    },
+    InvalidInput(String),
+    ValidationFailed(String),
+    BudgetExceeded(String),
+    Configuration(String),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranslatorErrorPayload {
+    pub code: &'static str,
+    pub message: String,
+    pub retry_after: Option<u64>,
+    pub status: Option<u16>,
+}
+
+fn is_quota_code(code: &str) -> bool {
+    matches!(
+        code.to_lowercase().as_str(),
+        "quota"
+            | "quota_exceeded"
+            | "insufficient_quota"
+            | "billing_hard_limit_reached"
+            | "payment_required"
+    )
+}
+
+fn from_code(code: &str, message: String) -> Option<TranslatorError> {
+    let error = match code.to_lowercase().as_str() {
+        code if is_quota_code(code) => TranslatorError::QuotaExceeded(message),
+        "rate_limit" | "rate_limited" | "rate_limit_exceeded" | "too_many_requests" => {
+            TranslatorError::RateLimited {
+                retry_after: None,
+                message,
+            }
+        }
+        "forbidden" | "permission_denied" | "permission_error" => {
+            TranslatorError::Forbidden(message)
+        }
+        "unauthorized"
+        | "invalid_api_key"
+        | "authentication_error"
+        | "invalid_token"
+        | "token_expired" => TranslatorError::Unauthorized(message),
+        _ => return None,
+    };
+    Some(error)
+}
+
+impl TranslatorError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            TranslatorError::Unauthorized(_) => "unauthorized",
+            TranslatorError::Forbidden(_) => "forbidden",
+            TranslatorError::QuotaExceeded(_) => "quota_exceeded",
+            TranslatorError::RateLimited { .. } => "rate_limited",
+            TranslatorError::Network(_) => "network",
+            TranslatorError::Timeout(_) => "timeout",
+            TranslatorError::BackendError { .. } => "backend_error",
+            TranslatorError::InvalidInput(_) => "invalid_input",
+            TranslatorError::ValidationFailed(_) => "validation_failed",
+            TranslatorError::BudgetExceeded(_) => "budget_exceeded",
+            TranslatorError::Configuration(_) => "configuration",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            TranslatorError::Unauthorized(message)
+            | TranslatorError::Forbidden(message)
+            | TranslatorError::QuotaExceeded(message)
+            | TranslatorError::Network(message)
+            | TranslatorError::Timeout(message)
+            | TranslatorError::InvalidInput(message)
+            | TranslatorError::ValidationFailed(message)
+            | TranslatorError::BudgetExceeded(message)
+            | TranslatorError::Configuration(message)
+            | TranslatorError::RateLimited { message, .. }
+            | TranslatorError::BackendError { message, .. } => message,
+        }
+    }
+
+    pub fn payload(&self) -> TranslatorErrorPayload {
+        TranslatorErrorPayload {
+            code: self.code(),
+            message: self.message().to_string(),
+            retry_after: match self {
+                TranslatorError::RateLimited { retry_after, .. } => *retry_after,
+                _ => None,
+            },
+            status: match self {
+                TranslatorError::BackendError { status, .. } => *status,
+                _ => None,
+            },
+        }
+    }
+
+    pub fn from_status(
+        label: &str,
+        status: reqwest::StatusCode,
+        retry_after: Option<u64>,
+        body: &str,
+    ) -> Self {
+        let message = format!("{} error ({}): {}", label, status, body.trim());
+        match status.as_u16() {
+            401 => TranslatorError::Unauthorized(message),
+            403 => TranslatorError::Forbidden(message),
+            402 | 456 => TranslatorError::QuotaExceeded(message),
+            429 if SseServerError::from_payload(body)
+                .code
+                .is_some_and(|code| is_quota_code(&code)) =>
+            {
+                TranslatorError::QuotaExceeded(message)
+            }
+            429 => TranslatorError::RateLimited {
+                retry_after,
+                message,
+            },
+            400 | 413 | 422 => TranslatorError::InvalidInput(message),
+            404 => TranslatorError::Configuration(message),
+            408 | 504 => TranslatorError::Timeout(message),
+            code => TranslatorError::BackendError {
+                status: Some(code),
+                message,
+            },
+        }
+    }
+
+    fn from_server_error(error: &SseServerError) -> Self {
+        let code = error.code.as_deref().unwrap_or_default();
+        if let Some(classified) = from_code(code, error.message.clone()) {
+            return classified;
+        }
+
+        let status = error
+            .status
+            .or_else(|| code.parse().ok())
+            .and_then(|status| reqwest::StatusCode::from_u16(status).ok());
+        match status {
+            Some(status) => {
+                TranslatorError::from_status("Server stream", status, None, &error.message)
+            }
+            None => TranslatorError::BackendError {
+                status: None,
+                message: error.to_string(),
+            },
+        }
+    }
+
+    fn from_reqwest(error: &reqwest::Error) -> Self {
+        let message = error.to_string();
+        if error.is_timeout() {
+            TranslatorError::Timeout(message)
+        } else if error.is_builder() {
+            TranslatorError::Configuration(message)
+        } else if let Some(status) = error.status() {
+            TranslatorError::from_status("HTTP", status, None, &message)
+        } else if error.is_decode() {
+            TranslatorError::BackendError {
+                status: None,
+                message,
+            }
+        } else {
+            TranslatorError::Network(message)
+        }
+    }
+
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(self, TranslatorError::Unauthorized(_))
+    }
+}
+
+impl fmt::Display for TranslatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TranslatorError::RateLimited {
+                retry_after: Some(seconds),
+                message,
+            } => write!(f, "{} (retry after {}s)", message, seconds),
+            other => write!(f, "{}", other.message()),
+        }
+    }
+}
+
+impl std::error::Error for TranslatorError {}
+
+impl From<&anyhow::Error> for TranslatorError {
+    fn from(error: &anyhow::Error) -> Self {
+        for cause in error.chain() {
+            if let Some(error) = cause.downcast_ref::<TranslatorError>() {
+                return error.clone();
+            }
+            if let Some(error) = cause.downcast_ref::<SseServerError>() {
+                return TranslatorError::from_server_error(error);
+            }
+            if let Some(error) = cause.downcast_ref::<FirstByteTimeout>() {
+                return TranslatorError::Timeout(error.to_string());
+            }
+            if let Some(error) = cause.downcast_ref::<reqwest::Error>() {
+                return TranslatorError::from_reqwest(error);
+            }
+        }
+
+        TranslatorError::BackendError {
+            status: None,
+            message: error.to_string(),
+        }
+    }
+}
+
+impl From<anyhow::Error> for TranslatorError {
+    fn from(error: anyhow::Error) -> Self {
+        TranslatorError::from(&error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn forbidden_is_not_treated_as_expired_session() {
+        let error = TranslatorError::from_status("Backend", StatusCode::FORBIDDEN, None, "plan");
+        assert_eq!(error.code(), "forbidden");
+        assert!(!error.is_unauthorized());
+
+        let error =
+            TranslatorError::from_status("Backend", StatusCode::UNAUTHORIZED, None, "expired");
+        assert!(error.is_unauthorized());
+    }
+
+    #[test]
+    fn server_error_codes_separate_forbidden_from_expired_tokens() {
+        let error = |code: &str| {
+            TranslatorError::from_server_error(&SseServerError {
+                code: Some(code.to_string()),
+                status: None,
+                message: "denied".to_string(),
+            })
+        };
+        assert!(error("token_expired").is_unauthorized());
+        assert_eq!(error("forbidden").code(), "forbidden");
+        assert_eq!(error("403").code(), "forbidden");
+    }
+
+    #[test]
+    fn server_errors_are_classified_by_code_and_status_not_message() {
+        let error = |code: Option<&str>, status: Option<u16>, message: &str| {
+            TranslatorError::from_server_error(&SseServerError {
+                code: code.map(str::to_string),
+                status,
+                message: message.to_string(),
+            })
+        };
+        assert_eq!(
+            error(None, None, "Payment of quota failed").code(),
+            "backend_error"
+        );
+        assert_eq!(
+            error(Some("insufficient_quota"), None, "x").code(),
+            "quota_exceeded"
+        );
+        assert_eq!(error(None, Some(429), "slow down").code(), "rate_limited");
+        assert_eq!(error(None, Some(401), "expired").code(), "unauthorized");
+        assert_eq!(
+            error(Some("server_overloaded"), Some(503), "busy").code(),
+            "backend_error"
+        );
+    }
+
+    #[test]
+    fn missing_endpoint_is_a_configuration_error() {
+        let error = TranslatorError::from_status("Backend", StatusCode::NOT_FOUND, None, "");
+        assert_eq!(error.code(), "configuration");
+
+        let body = r#"{"error":{"code":"insufficient_quota"}}"#;
+        let error =
+            TranslatorError::from_status("OpenAI", StatusCode::TOO_MANY_REQUESTS, None, body);
+        assert_eq!(error.code(), "quota_exceeded");
+        let error = TranslatorError::from_status(
+            "OpenAI",
+            StatusCode::TOO_MANY_REQUESTS,
+            Some(3),
+            "quota of requests per minute",
+        );
+        assert_eq!(error.code(), "rate_limited");
+    }
+}
@@ -0,0 +1,22 @@
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+pub trait EventSink: Send + Sync {
+    fn emit_value(&self, event: &str, payload: Value);
+}
+
+impl EventSink for AppHandle {
+    fn emit_value(&self, event: &str, payload: Value) {
+        let _ = Emitter::emit(self, event, payload);
+    }
+}
+
+impl<'a> dyn EventSink + 'a {
+    pub fn emit<T: Serialize>(&self, event: &str, payload: T) {
+        match serde_json::to_value(payload) {
+            Ok(payload) => self.emit_value(event, payload),
+            Err(e) => log_line!("[EVENTS] Failed to serialize {} payload: {}", event, e),
+        }
+    }
+}
@@ -0,0 +1,474 @@
+use once_cell::sync::Lazy;
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use regex::{Captures, Regex};
+use serde::Serialize;
+use std::ops::Range;
+
+use super::segment::Chunk;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextFormat {
+    Plain,
+    Markdown,
+    Html,
+}
+
+static HTML_TAG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?s)<!--.*?-->|<!\[CDATA\[.*?\]\]>|<[!?][^>]*>|</?([A-Za-z][A-Za-z0-9-]*)(?:[^>"']|"[^"]*"|'[^']*')*>"#,
+    )
+    .expect("valid html tag pattern")
+});
+
+static HTML_KNOWN_TAG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)<(/?)(p|div|h[1-6]|ul|ol|li|a|strong|em|b|i|u|span|table|thead|tbody|tr|td|th|br|pre|code|blockquote|section|article|html|body|img)\b[^>]*>",
+    )
+    .expect("valid html signal pattern")
+});
+
+static HTML_RICH_TAG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)<(h[1-6]|ul|ol|li|a|strong|em|b|i|table|pre|code|blockquote)\b[^>]*>")
+        .expect("valid html rich pattern")
+});
+
+static HTML_PARAGRAPH: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)<p\b[^>]*>").expect("valid paragraph pattern"));
+
+static HTML_ENTITY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"&(#[0-9]+|#[xX][0-9A-Fa-f]+|[A-Za-z]+);").expect("valid entity pattern")
+});
+
+static HTML_WHITESPACE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[ \t\r\n]+").expect("valid whitespace pattern"));
+
+static HTML_TRAILING_SPACE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[ \t]+\n").expect("valid trailing space pattern"));
+
+static HTML_EXTRA_LINES: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\n{3,}").expect("valid blank line pattern"));
+
+static MARKDOWN_SIGNALS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    [
+        r"(?m)^#{1,6}\s+\S",
+        r"(?m)^\s{0,3}[-*+]\s+\S",
+        r"(?m)^\s{0,3}\d{1,9}[.)]\s+\S",
+        r"(?m)^\s{0,3}>\s?\S",
+        r"\[[^\]\n]+\]\([^)\s]+\)",
+        r"(?:\*\*|__)[^*_\n]+(?:\*\*|__)",
+        r"(?m)^\s*\|.*\|\s*$",
+    ]
+    .iter()
+    .map(|pattern| Regex::new(pattern).expect("valid markdown signal pattern"))
+    .collect()
+});
+
+static MARKDOWN_FENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^\s{0,3}(?:```|~~~)").expect("valid fence pattern"));
+
+const HTML_INLINE_TAGS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "br", "cite", "code", "data", "del", "dfn", "em", "font", "i",
+    "img", "ins", "kbd", "label", "mark", "q", "s", "samp", "small", "span", "strong", "sub",
+    "sup", "time", "u", "var", "wbr",
+];
+
+const HTML_RAW_TAGS: &[&str] = &[
+    "code", "kbd", "math", "pre", "samp", "script", "style", "svg", "textarea",
+];
+
+pub fn detect_format(text: &str) -> TextFormat {
+    let html_tags: Vec<_> = HTML_KNOWN_TAG.captures_iter(text).collect();
+    let has_closing_tag = html_tags.iter().any(|tag| &tag[1] == "/");
+    if html_tags.len() >= 2 && has_closing_tag {
+        return TextFormat::Html;
+    }
+
+    if MARKDOWN_FENCE.find_iter(text).count() >= 2 {
+        return TextFormat::Markdown;
+    }
+    let signals: usize = MARKDOWN_SIGNALS
+        .iter()
+        .map(|pattern| pattern.find_iter(text).count())
+        .sum();
+    if signals >= 2 {
+        return TextFormat::Markdown;
+    }
+
+    TextFormat::Plain
+}
+
+fn rich_html_fragment(html: &str) -> Option<&str> {
+    let fragment = match (
+        html.find("<!--StartFragment-->"),
+        html.find("<!--EndFragment-->"),
+    ) {
+        (Some(start), Some(end)) if start < end => &html[start + "<!--StartFragment-->".len()..end],
+        _ => html,
+    };
+    let fragment = fragment.trim();
+
+    let is_rich =
+        HTML_RICH_TAG.is_match(fragment) || HTML_PARAGRAPH.find_iter(fragment).count() >= 2;
+    (is_rich && detect_format(fragment) == TextFormat::Html).then_some(fragment)
+}
+
+fn decode_entities(text: &str) -> String {
+    HTML_ENTITY
+        .replace_all(text, |entity: &Captures| {
+            let name = &entity[1];
+            let decoded = match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => name
+                        .strip_prefix('#')
+                        .and_then(|digits| digits.parse().ok()),
+                }
+                .and_then(char::from_u32),
+            };
+            decoded.map_or_else(|| entity[0].to_string(), String::from)
+        })
+        .into_owned()
+}
+
+fn html_to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut hidden = 0usize;
+    let mut preformatted = 0usize;
+    let mut cursor = 0;
+    let push_run = |text: &mut String, run: &str, preformatted: bool| {
+        if preformatted {
+            text.push_str(&decode_entities(run));
+            return;
+        }
+        let collapsed = HTML_WHITESPACE.replace_all(run, " ");
+        let collapsed = if text.is_empty() || text.ends_with('\n') {
+            collapsed.trim_start()
+        } else {
+            &collapsed
+        };
+        text.push_str(&decode_entities(collapsed));
+    };
+
+    for tag in HTML_TAG.captures_iter(html) {
+        let whole = tag.get(0).expect("whole match");
+        if hidden == 0 {
+            push_run(&mut text, &html[cursor..whole.start()], preformatted > 0);
+        }
+        cursor = whole.end();
+
+        let Some(name) = tag.get(1) else {
+            continue;
+        };
+        let name = name.as_str().to_lowercase();
+        let closing = whole.as_str().starts_with("</");
+        let depth = match name.as_str() {
+            "script" | "style" => &mut hidden,
+            "pre" => &mut preformatted,
+            _ => &mut 0,
+        };
+        *depth = match closing {
+            true => depth.saturating_sub(1),
+            false => *depth + 1,
+        };
+
+        match name.as_str() {
+            "br" => text.push('\n'),
+            "li" | "tr" if !closing => text.push('\n'),
+            "li" | "tr" => {}
+            _ if HTML_INLINE_TAGS.contains(&name.as_str()) => {}
+            _ => text.push_str("\n\n"),
+        }
+    }
+    if hidden == 0 {
+        push_run(&mut text, &html[cursor..], preformatted > 0);
+    }
+
+    let text = HTML_TRAILING_SPACE.replace_all(&text, "\n");
+    HTML_EXTRA_LINES
+        .replace_all(&text, "\n\n")
+        .trim()
+        .to_string()
+}
+
+pub fn html_flavor_text(html: &str) -> Option<String> {
+    rich_html_fragment(html).map(html_to_text)
+}
+
+fn push_block(text: &str, ranges: &mut Vec<Range<usize>>, blocks: &mut Vec<Chunk>) {
+    let mut texts: Vec<Range<usize>> = ranges
+        .drain(..)
+        .filter(|range| !text[range.clone()].trim().is_empty())
+        .collect();
+    if !texts
+        .iter()
+        .any(|range| text[range.clone()].chars().any(char::is_alphabetic))
+    {
+        return;
+    }
+
+    let first = texts.first_mut().expect("non-empty block");
+    first.start += text[first.clone()].len() - text[first.clone()].trim_start().len();
+    let last = texts.last_mut().expect("non-empty block");
+    last.end -= text[last.clone()].len() - text[last.clone()].trim_end().len();
+
+    let start = texts[0].start;
+    let protected = texts
+        .windows(2)
+        .map(|pair| pair[0].end..pair[1].start)
+        .filter(|gap| !text[gap.clone()].trim().is_empty())
+        .map(|gap| gap.start - start..gap.end - start)
+        .collect();
+
+    blocks.push(Chunk {
+        range: start..texts[texts.len() - 1].end,
+        protected,
+    });
+}
+
+fn is_inline_start(tag: &Tag) -> bool {
+    matches!(
+        tag,
+        Tag::Emphasis
+            | Tag::Strong
+            | Tag::Strikethrough
+            | Tag::Superscript
+            | Tag::Subscript
+            | Tag::Link { .. }
+            | Tag::Image { .. }
+    )
+}
+
+fn is_inline_end(tag: &TagEnd) -> bool {
+    matches!(
+        tag,
+        TagEnd::Emphasis
+            | TagEnd::Strong
+            | TagEnd::Strikethrough
+            | TagEnd::Superscript
+            | TagEnd::Subscript
+            | TagEnd::Link
+            | TagEnd::Image
+    )
+}
+
+fn markdown_blocks(text: &str) -> Vec<Chunk> {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+
+    let mut blocks = Vec::new();
+    let mut ranges = Vec::new();
+    let mut in_code_block = false;
+    for (event, range) in Parser::new_ext(text, options).into_offset_iter() {
+        match event {
+            Event::Start(tag) if is_inline_start(&tag) => {}
+            Event::End(tag) if is_inline_end(&tag) => {}
+            Event::Start(Tag::CodeBlock(_)) => {
+                push_block(text, &mut ranges, &mut blocks);
+                in_code_block = true;
+            }
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Start(_) | Event::End(_) | Event::Rule => {
+                push_block(text, &mut ranges, &mut blocks);
+            }
+            Event::Text(_) if !in_code_block => ranges.push(range),
+            _ => {}
+        }
+    }
+    push_block(text, &mut ranges, &mut blocks);
+    blocks
+}
+
+fn html_blocks(text: &str) -> Vec<Chunk> {
+    let mut blocks = Vec::new();
+    let mut ranges = Vec::new();
+    let mut raw_stack: Vec<String> = Vec::new();
+    let mut cursor = 0;
+    for tag in HTML_TAG.captures_iter(text) {
+        let whole = tag.get(0).expect("whole match");
+        if raw_stack.is_empty() && cursor < whole.start() {
+            ranges.push(cursor..whole.start());
+        }
+        cursor = whole.end();
+
+        let Some(name) = tag.get(1) else {
+            if !whole.as_str().starts_with("<!--") {
+                push_block(text, &mut ranges, &mut blocks);
+            }
+            continue;
+        };
+        let name = name.as_str().to_lowercase();
+        let closing = whole.as_str().starts_with("</");
+        let self_closing = whole.as_str().ends_with("/>");
+
+        if HTML_RAW_TAGS.contains(&name.as_str()) && !self_closing {
+            if closing {
+                if let Some(position) = raw_stack.iter().rposition(|open| *open == name) {
+                    raw_stack.truncate(position);
+                }
+            } else {
+                raw_stack.push(name.clone());
+            }
+        }
+        if !HTML_INLINE_TAGS.contains(&name.as_str()) {
+            push_block(text, &mut ranges, &mut blocks);
+        }
+    }
+    if raw_stack.is_empty() && cursor < text.len() {
+        ranges.push(cursor..text.len());
+    }
+    push_block(text, &mut ranges, &mut blocks);
+    blocks
+}
+
+pub(super) fn document_chunks(text: &str, text_format: TextFormat) -> Vec<Chunk> {
+    match text_format {
+        TextFormat::Markdown => markdown_blocks(text),
+        TextFormat::Html => html_blocks(text),
+        TextFormat::Plain => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pieces(block: &str, chunk: &Chunk) -> Vec<Range<usize>> {
+        let mut pieces = Vec::new();
+        let mut cursor = 0;
+        for gap in &chunk.protected {
+            pieces.push(cursor..gap.start);
+            cursor = gap.end;
+        }
+        pieces.push(cursor..block.len());
+        pieces
+    }
+
+    fn sent_texts(text: &str, chunks: &[Chunk]) -> Vec<String> {
+        chunks
+            .iter()
+            .map(|chunk| {
+                let block = &text[chunk.range.clone()];
+                pieces(block, chunk)
+                    .into_iter()
+                    .map(|piece| &block[piece])
+                    .collect::<Vec<_>>()
+                    .join("|")
+            })
+            .collect()
+    }
+
+    fn translate_with(text: &str, chunks: &[Chunk], translate: fn(&str) -> String) -> String {
+        let mut result = String::new();
+        let mut cursor = 0;
+        for chunk in chunks {
+            result.push_str(&text[cursor..chunk.range.start]);
+            let block = &text[chunk.range.clone()];
+            let mut end = 0;
+            for piece in pieces(block, chunk) {
+                result.push_str(&block[end..piece.start]);
+                result.push_str(&translate(&block[piece.clone()]));
+                end = piece.end;
+            }
+            cursor = chunk.range.end;
+        }
+        result.push_str(&text[cursor..]);
+        result
+    }
+
+    #[test]
+    fn detects_plain_markdown_and_html() {
+        assert_eq!(
+            detect_format("Just a sentence with a # sign."),
+            TextFormat::Plain
+        );
+        assert_eq!(
+            detect_format("# Title\n\n- first item\n- second item"),
+            TextFormat::Markdown
+        );
+        assert_eq!(
+            detect_format("Run this:\n\n```\ncargo build\n```\n"),
+            TextFormat::Markdown
+        );
+        assert_eq!(
+            detect_format("<p>Hello <strong>world</strong></p>"),
+            TextFormat::Html
+        );
+        assert_eq!(detect_format("Use <br> sparingly"), TextFormat::Plain);
+    }
+
+    #[test]
+    fn markdown_sends_only_text_nodes() {
+        let text =
+            "# Getting started\n\nSee [the docs](https://example.com/a_b) and `cargo run`.\n";
+        let chunks = markdown_blocks(text);
+        assert_eq!(
+            sent_texts(text, &chunks),
+            vec!["Getting started", "See |the docs| and |."]
+        );
+    }
+
+    #[test]
+    fn markdown_markup_stays_byte_identical() {
+        let text = "# Getting started\n\n\
+                    - **Install** the [tool](https://example.com/install?v=2)\n\
+                    - Run `cargo run -- --help`\n\n\
+                    ```sh\ncargo build --release\n```\n\n\
+                    > Quoted *note*.\n";
+        let translated = translate_with(text, &markdown_blocks(text), str::to_uppercase);
+        assert_eq!(
+            translated,
+            "# GETTING STARTED\n\n\
+             - **INSTALL** THE [TOOL](https://example.com/install?v=2)\n\
+             - RUN `cargo run -- --help`\n\n\
+             ```sh\ncargo build --release\n```\n\n\
+             > QUOTED *NOTE*.\n"
+        );
+    }
+
+    #[test]
+    fn html_sends_only_text_nodes() {
+        let text = "<h1 id=\"top\">Welcome</h1><p class=\"intro\">Read <a href=\"/docs\">the guide</a> first.</p>";
+        let chunks = html_blocks(text);
+        assert_eq!(
+            sent_texts(text, &chunks),
+            vec!["Welcome", "Read |the guide| first."]
+        );
+    }
+
+    #[test]
+    fn html_markup_and_attributes_stay_byte_identical() {
+        let text = "<div class=\"note\" title=\"Keep me\">\n  <p>Call <code>init()</code> with <a href=\"https://example.com/?a=1&amp;b=2\">care</a>.</p>\n  <script>var label = \"untouched\";</script>\n  <pre>raw  text</pre>\n</div>";
+        let translated = translate_with(text, &html_blocks(text), str::to_uppercase);
+        assert_eq!(
+            translated,
+            "<div class=\"note\" title=\"Keep me\">\n  <p>CALL <code>init()</code> WITH <a href=\"https://example.com/?a=1&amp;b=2\">CARE</a>.</p>\n  <script>var label = \"untouched\";</script>\n  <pre>raw  text</pre>\n</div>"
+        );
+    }
+
+    #[test]
+    fn html_flavor_becomes_plain_text_with_block_structure() {
+        let html = "<html><body><!--StartFragment--><h2>Release&nbsp;notes</h2>\n<p>Fixed <b>two</b>\n   bugs &amp; added <a href=\"https://example.com\">docs</a>.</p><ul><li>One</li><li>Two</li></ul><style>p { color: red; }</style><!--EndFragment--></body></html>";
+        assert_eq!(
+            html_flavor_text(html).as_deref(),
+            Some("Release\u{a0}notes\n\nFixed two bugs & added docs.\n\nOne\nTwo")
+        );
+    }
+
+    #[test]
+    fn html_flavor_without_structure_is_ignored() {
+        assert_eq!(
+            html_flavor_text("<span style=\"color: red\">Hello</span>"),
+            None
+        );
+        assert_eq!(html_flavor_text("<meta charset=\"utf-8\">Hello"), None);
+    }
+}
@@ -0,0 +1,740 @@
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use super::hashing::stable_hash;
+use super::languages::normalize_code;
+use super::protect;
+use super::LanguagePair;
+use crate::settings::get_config_file_path;
+
+static GLOSSARIES: Mutex<Option<Vec<Glossary>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GlossaryTerm {
+    pub source: String,
+    pub target: String,
+    #[serde(default)]
+    pub case_sensitive: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Glossary {
+    pub source_lang: String,
+    pub target_lang: String,
+    pub terms: Vec<GlossaryTerm>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GlossaryViolation {
+    pub source_term: String,
+    pub expected_target: String,
+    pub fixed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GlossaryImportSummary {
+    pub imported: usize,
+    pub pairs: Vec<LanguagePair>,
+}
+
+fn get_glossary_path() -> std::path::PathBuf {
+    get_config_file_path("glossaries.json")
+}
+
+fn load_glossaries() -> Vec<Glossary> {
+    let mut glossaries_lock = GLOSSARIES.lock();
+
+    if let Some(glossaries) = glossaries_lock.as_ref() {
+        return glossaries.clone();
+    }
+
+    let glossaries = match fs::read_to_string(get_glossary_path()) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log_line!("[GLOSSARY] Failed to parse glossaries: {:?}", e);
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    };
+
+    *glossaries_lock = Some(glossaries.clone());
+    glossaries
+}
+
+fn save_glossaries(glossaries: Vec<Glossary>) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(&glossaries)
+        .map_err(|e| format!("Failed to serialize glossaries: {}", e))?;
+    fs::write(get_glossary_path(), json)
+        .map_err(|e| format!("Failed to write glossaries: {}", e))?;
+
+    *GLOSSARIES.lock() = Some(glossaries);
+    Ok(())
+}
+
+fn merge_terms(
+    glossaries: &mut Vec<Glossary>,
+    source: &str,
+    target: &str,
+    terms: Vec<GlossaryTerm>,
+) {
+    let source = normalize_code(source);
+    let target = normalize_code(target);
+
+    let index = match glossaries
+        .iter()
+        .position(|glossary| glossary.source_lang == source && glossary.target_lang == target)
+    {
+        Some(index) => index,
+        None => {
+            glossaries.push(Glossary {
+                source_lang: source,
+                target_lang: target,
+                terms: Vec::new(),
+            });
+            glossaries.len() - 1
+        }
+    };
+
+    let existing = &mut glossaries[index].terms;
+    for term in terms {
+        match existing
+            .iter_mut()
+            .find(|existing| existing.source == term.source)
+        {
+            Some(existing) => *existing = term,
+            None => existing.push(term),
+        }
+    }
+}
+
+pub fn terms_for(pair: &LanguagePair) -> Vec<GlossaryTerm> {
+    let source = normalize_code(&pair.source);
+    let target = normalize_code(&pair.target);
+    load_glossaries()
+        .into_iter()
+        .find(|glossary| glossary.source_lang == source && glossary.target_lang == target)
+        .map(|glossary| glossary.terms)
+        .unwrap_or_default()
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn find_terms(haystack: &str, needle: &str, case_sensitive: bool) -> Vec<(usize, usize)> {
+    if needle.is_empty() {
+        return Vec::new();
+    }
+
+    let (haystack_cmp, origins) = fold(haystack, case_sensitive);
+    let (needle_cmp, _) = fold(needle, case_sensitive);
+
+    let mut matches = Vec::new();
+    let mut offset = 0;
+    while let Some(found) = haystack_cmp[offset..].find(&needle_cmp) {
+        let folded_start = offset + found;
+        let folded_end = folded_start + needle_cmp.len();
+        if let (Some(start), Some(end)) = (origins[folded_start], origins[folded_end]) {
+            let before = haystack[..start].chars().next_back();
+            let after = haystack[end..].chars().next();
+            if !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char) {
+                matches.push((start, end));
+                offset = folded_end;
+                continue;
+            }
+        }
+        offset = folded_start
+            + haystack_cmp[folded_start..]
+                .chars()
+                .next()
+                .map_or(1, char::len_utf8);
+    }
+    matches
+}
+
+fn fold(text: &str, case_sensitive: bool) -> (String, Vec<Option<usize>>) {
+    if case_sensitive {
+        return (text.to_string(), (0..=text.len()).map(Some).collect());
+    }
+    let mut folded = String::with_capacity(text.len());
+    let mut origins = Vec::with_capacity(text.len() + 1);
+    for (index, c) in text.char_indices() {
+        let start = folded.len();
+        folded.extend(c.to_lowercase());
+        origins.push(Some(index));
+        origins.extend((start + 1..folded.len()).map(|_| None));
+    }
+    origins.push(Some(text.len()));
+    (folded, origins)
+}
+
+fn find_unprotected(haystack: &str, needle: &str, case_sensitive: bool) -> Vec<(usize, usize)> {
+    let tokens = protect::token_ranges(haystack);
+    find_terms(haystack, needle, case_sensitive)
+        .into_iter()
+        .filter(|(start, end)| {
+            !tokens
+                .iter()
+                .any(|token| *start < token.end && token.start < *end)
+        })
+        .collect()
+}
+
+fn contains_term(haystack: &str, needle: &str, case_sensitive: bool) -> bool {
+    !find_terms(haystack, needle, case_sensitive).is_empty()
+}
+
+pub fn matching_terms(text: &str, pair: &LanguagePair) -> Vec<GlossaryTerm> {
+    terms_for(pair)
+        .into_iter()
+        .filter(|term| contains_term(text, &term.source, term.case_sensitive))
+        .collect()
+}
+
+pub fn fingerprint(terms: &[GlossaryTerm]) -> Option<String> {
+    if terms.is_empty() {
+        return None;
+    }
+    Some(stable_hash(terms.iter().flat_map(|term| {
+        [
+            term.source.as_str(),
+            term.target.as_str(),
+            if term.case_sensitive { "1" } else { "0" },
+        ]
+    })))
+}
+
+pub fn enforce(
+    output: &str,
+    terms: &[GlossaryTerm],
+    fix: bool,
+) -> (String, Vec<GlossaryViolation>) {
+    let mut result = output.to_string();
+    let mut violations = Vec::new();
+
+    for term in terms {
+        if contains_term(&result, &term.target, term.case_sensitive) {
+            continue;
+        }
+
+        let mut fixed = false;
+        if fix {
+            let mut found = find_unprotected(&result, &term.source, term.case_sensitive);
+            if found.is_empty() && term.case_sensitive {
+                found = find_unprotected(&result, &term.target, false);
+            }
+            for (start, end) in found.into_iter().rev() {
+                result.replace_range(start..end, &term.target);
+                fixed = true;
+            }
+        }
+
+        violations.push(GlossaryViolation {
+            source_term: term.source.clone(),
+            expected_target: term.target.clone(),
+            fixed,
+        });
+    }
+
+    (result, violations)
+}
+
+fn parse_bool(value: &str) -> bool {
+    matches!(
+        value.trim().to_lowercase().as_str(),
+        "1" | "true" | "yes" | "y"
+    )
+}
+
+fn import_csv(
+    content: &str,
+    default_pair: Option<&LanguagePair>,
+) -> Result<Vec<(LanguagePair, GlossaryTerm)>> {
+    let content = content.trim_start_matches('\u{feff}');
+    let first_line = content.lines().next().unwrap_or_default();
+    let delimiter = if first_line.contains('\t') && !first_line.contains(',') {
+        b'\t'
+    } else {
+        b','
+    };
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .delimiter(delimiter)
+        .from_reader(content.as_bytes());
+    let mut records = reader.records().peekable();
+
+    let headers: Vec<String> = match records.peek() {
+        Some(Ok(record)) => record.iter().map(|header| header.to_lowercase()).collect(),
+        _ => Vec::new(),
+    };
+    let column = |names: &[&str]| {
+        headers
+            .iter()
+            .position(|header| names.contains(&header.as_str()))
+    };
+
+    let source_column = column(&["source", "source_term", "term"]);
+    let target_column = column(&["target", "target_term", "translation"]);
+    let has_header = source_column.is_some() || target_column.is_some();
+    if has_header {
+        records.next();
+    }
+
+    let source_column = source_column.unwrap_or(0);
+    let target_column = target_column.unwrap_or(1);
+    let source_lang_column = column(&["source_lang", "source_language"]);
+    let target_lang_column = column(&["target_lang", "target_language"]);
+    let case_column = column(&["case_sensitive"]);
+
+    let mut entries = Vec::new();
+    for record in records {
+        let record = record?;
+        let field = |index: Option<usize>| {
+            index
+                .and_then(|index| record.get(index))
+                .unwrap_or_default()
+        };
+
+        let source = field(Some(source_column));
+        let target = field(Some(target_column));
+        if source.is_empty() || target.is_empty() {
+            continue;
+        }
+
+        let pair = match (field(source_lang_column), field(target_lang_column)) {
+            ("", _) | (_, "") => default_pair.cloned().context(
+                "CSV rows need source_lang/target_lang columns or an explicit language pair",
+            )?,
+            (source_lang, target_lang) => LanguagePair {
+                source: source_lang.to_string(),
+                target: target_lang.to_string(),
+            },
+        };
+
+        entries.push((
+            pair,
+            GlossaryTerm {
+                source: source.to_string(),
+                target: target.to_string(),
+                case_sensitive: parse_bool(field(case_column)),
+            },
+        ));
+    }
+    Ok(entries)
+}
+
+fn read_tbx_entries(content: &str) -> Result<Vec<Vec<(String, String)>>> {
+    let mut reader = Reader::from_str(content);
+    reader.config_mut().trim_text(true);
+
+    let mut entries = Vec::new();
+    let mut current_entry: Vec<(String, String)> = Vec::new();
+    let mut current_lang: Option<String> = None;
+    let mut in_term = false;
+    let mut term_text = String::new();
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) => match element.local_name().as_ref() {
+                b"termEntry" | b"conceptEntry" => current_entry.clear(),
+                b"langSet" | b"langSec" => {
+                    current_lang = element
+                        .attributes()
+                        .flatten()
+                        .find(|attribute| attribute.key.local_name().as_ref() == b"lang")
+                        .map(|attribute| attribute.unescape_value().map(|value| value.to_string()))
+                        .transpose()?;
+                }
+                b"term" => {
+                    in_term = true;
+                    term_text.clear();
+                }
+                _ => {}
+            },
+            Event::Text(text) if in_term => term_text.push_str(&text.unescape()?),
+            Event::CData(text) if in_term => {
+                term_text.push_str(&String::from_utf8_lossy(&text.into_inner()))
+            }
+            Event::End(element) => match element.local_name().as_ref() {
+                b"term" => {
+                    in_term = false;
+                    if let Some(lang) = &current_lang {
+                        let term = term_text.trim();
+                        if !term.is_empty()
+                            && !current_entry.iter().any(|(existing, _)| existing == lang)
+                        {
+                            current_entry.push((lang.clone(), term.to_string()));
+                        }
+                    }
+                }
+                b"langSet" | b"langSec" => current_lang = None,
+                b"termEntry" | b"conceptEntry" if current_entry.len() > 1 => {
+                    entries.push(std::mem::take(&mut current_entry));
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(entries)
+}
+
+fn import_tbx(
+    content: &str,
+    default_pair: Option<&LanguagePair>,
+) -> Result<Vec<(LanguagePair, GlossaryTerm)>> {
+    let mut entries = Vec::new();
+    for concept in read_tbx_entries(content)? {
+        let find = |lang: &str| {
+            concept
+                .iter()
+                .find(|(code, _)| normalize_code(code) == normalize_code(lang))
+                .map(|(_, term)| term.clone())
+        };
+
+        match default_pair {
+            Some(pair) => {
+                if let (Some(source), Some(target)) = (find(&pair.source), find(&pair.target)) {
+                    entries.push((
+                        pair.clone(),
+                        GlossaryTerm {
+                            source,
+                            target,
+                            case_sensitive: false,
+                        },
+                    ));
+                }
+            }
+            None => {
+                let (source_lang, source) = &concept[0];
+                for (target_lang, target) in &concept[1..] {
+                    entries.push((
+                        LanguagePair {
+                            source: source_lang.clone(),
+                            target: target_lang.clone(),
+                        },
+                        GlossaryTerm {
+                            source: source.clone(),
+                            target: target.clone(),
+                            case_sensitive: false,
+                        },
+                    ));
+                }
+            }
+        }
+    }
+    Ok(entries)
+}
+
+fn is_tbx(path: &Path, content: &str) -> bool {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase);
+    match extension.as_deref() {
+        Some("tbx") | Some("xml") => true,
+        Some("csv") | Some("tsv") => false,
+        _ => content.trim_start().starts_with('<'),
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    quick_xml::escape::escape(text).into_owned()
+}
+
+fn export_tbx(glossary: &Glossary) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<tbx type=\"TBX-Basic\" style=\"dca\" xml:lang=\"");
+    xml.push_str(&escape_xml(&glossary.source_lang));
+    xml.push_str("\" xmlns=\"urn:iso:std:iso:30042:ed-2\">\n  <text>\n    <body>\n");
+
+    for (index, term) in glossary.terms.iter().enumerate() {
+        xml.push_str(&format!("      <conceptEntry id=\"c{}\">\n", index + 1));
+        for (lang, text) in [
+            (&glossary.source_lang, &term.source),
+            (&glossary.target_lang, &term.target),
+        ] {
+            xml.push_str(&format!(
+                "        <langSec xml:lang=\"{}\">\n          <termSec>\n            <term>{}</term>\n          </termSec>\n        </langSec>\n",
+                escape_xml(lang),
+                escape_xml(text)
+            ));
+        }
+        xml.push_str("      </conceptEntry>\n");
+    }
+
+    xml.push_str("    </body>\n  </text>\n</tbx>\n");
+    xml
+}
+
+fn export_csv(glossary: &Glossary) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record([
+        "source",
+        "target",
+        "source_lang",
+        "target_lang",
+        "case_sensitive",
+    ])?;
+    for term in &glossary.terms {
+        writer.write_record([
+            term.source.as_str(),
+            term.target.as_str(),
+            glossary.source_lang.as_str(),
+            glossary.target_lang.as_str(),
+            if term.case_sensitive { "true" } else { "false" },
+        ])?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+#[tauri::command]
+pub fn get_glossaries() -> Vec<Glossary> {
+    load_glossaries()
+}
+
+#[tauri::command]
+pub fn save_glossary(glossary: Glossary) -> Result<(), String> {
+    let source = normalize_code(&glossary.source_lang);
+    let target = normalize_code(&glossary.target_lang);
+    log_line!(
+        "[GLOSSARY] Saving {} terms for {} -> {}",
+        glossary.terms.len(),
+        source,
+        target
+    );
+
+    let mut glossaries = load_glossaries();
+    glossaries
+        .retain(|existing| !(existing.source_lang == source && existing.target_lang == target));
+    merge_terms(&mut glossaries, &source, &target, glossary.terms);
+    save_glossaries(glossaries)
+}
+
+#[tauri::command]
+pub fn delete_glossary(source_lang: String, target_lang: String) -> Result<(), String> {
+    let source = normalize_code(&source_lang);
+    let target = normalize_code(&target_lang);
+    let mut glossaries = load_glossaries();
+    glossaries
+        .retain(|existing| !(existing.source_lang == source && existing.target_lang == target));
+    save_glossaries(glossaries)
+}
+
+#[tauri::command]
+pub fn import_glossary(
+    path: String,
+    source_lang: Option<String>,
+    target_lang: Option<String>,
+) -> Result<GlossaryImportSummary, String> {
+    log_line!("[GLOSSARY] Importing glossary from {}", path);
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let default_pair = match (source_lang, target_lang) {
+        (Some(source), Some(target)) => Some(LanguagePair { source, target }),
+        _ => None,
+    };
+
+    let entries = if is_tbx(Path::new(&path), &content) {
+        import_tbx(&content, default_pair.as_ref())
+    } else {
+        import_csv(&content, default_pair.as_ref())
+    }
+    .map_err(|e| format!("Failed to import glossary: {:#}", e))?;
+
+    let mut glossaries = load_glossaries();
+    let mut pairs: Vec<LanguagePair> = Vec::new();
+    let imported = entries.len();
+    for (pair, term) in entries {
+        merge_terms(&mut glossaries, &pair.source, &pair.target, vec![term]);
+        let pair = LanguagePair {
+            source: normalize_code(&pair.source),
+            target: normalize_code(&pair.target),
+        };
+        if !pairs.contains(&pair) {
+            pairs.push(pair);
+        }
+    }
+    save_glossaries(glossaries)?;
+
+    log_line!("[GLOSSARY] Imported {} terms", imported);
+    Ok(GlossaryImportSummary { imported, pairs })
+}
+
+#[tauri::command]
+pub fn export_glossary(
+    path: String,
+    source_lang: String,
+    target_lang: String,
+) -> Result<usize, String> {
+    let pair = LanguagePair {
+        source: normalize_code(&source_lang),
+        target: normalize_code(&target_lang),
+    };
+    let glossary = Glossary {
+        terms: terms_for(&pair),
+        source_lang: pair.source,
+        target_lang: pair.target,
+    };
+    log_line!(
+        "[GLOSSARY] Exporting {} terms to {}",
+        glossary.terms.len(),
+        path
+    );
+
+    let content = if is_tbx(Path::new(&path), "") {
+        export_tbx(&glossary)
+    } else {
+        export_csv(&glossary).map_err(|e| format!("Failed to export glossary: {}", e))?
+    };
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(glossary.terms.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(source: &str, target: &str) -> LanguagePair {
+        LanguagePair {
+            source: source.to_string(),
+            target: target.to_string(),
+        }
+    }
+
+    fn term(source: &str, target: &str, case_sensitive: bool) -> GlossaryTerm {
+        GlossaryTerm {
+            source: source.to_string(),
+            target: target.to_string(),
+            case_sensitive,
+        }
+    }
+
+    #[test]
+    fn csv_import_reads_headers_quoting_and_bom() {
+        let content = "\u{feff}target,source,source_lang,target_lang,case_sensitive\n\
+                       \"Konto, Benutzer\",\"user account\",en,de,yes\n\
+                       \"sagt \"\"Hallo\"\"\",\"says \"\"hello\"\"\",en,de,\n";
+        let entries = import_csv(content, None).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                (
+                    pair("en", "de"),
+                    term("user account", "Konto, Benutzer", true)
+                ),
+                (
+                    pair("en", "de"),
+                    term("says \"hello\"", "sagt \"Hallo\"", false)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_import_without_header_uses_the_default_pair() {
+        let content = "cache\tZwischenspeicher\nqueue\tWarteschlange\n";
+        let entries = import_csv(content, Some(&pair("en", "de"))).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                (pair("en", "de"), term("cache", "Zwischenspeicher", false)),
+                (pair("en", "de"), term("queue", "Warteschlange", false)),
+            ]
+        );
+        assert!(import_csv(content, None).is_err());
+    }
+
+    #[test]
+    fn tbx_export_round_trips_through_import() {
+        let glossary = Glossary {
+            source_lang: "en".to_string(),
+            target_lang: "de".to_string(),
+            terms: vec![
+                term("pull request", "Pull-Request", false),
+                term("R&D <lab>", "F&E <Labor>", false),
+            ],
+        };
+        let entries = import_tbx(&export_tbx(&glossary), None).unwrap();
+        assert_eq!(
+            entries,
+            glossary
+                .terms
+                .iter()
+                .map(|term| (pair("en", "de"), term.clone()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn find_terms_ignores_case_unless_asked() {
+        assert_eq!(
+            find_terms("Open the Cache now", "cache", false),
+            vec![(9, 14)]
+        );
+        assert!(find_terms("Open the Cache now", "cache", true).is_empty());
+        assert!(find_terms("caches", "cache", false).is_empty());
+    }
+
+    #[test]
+    fn find_terms_maps_folded_matches_back_to_the_original_text() {
+        let text = "İstanbul ẞTRASSE";
+        let matches = find_terms(text, "ßtrasse", false);
+        assert_eq!(matches.len(), 1);
+        let (start, end) = matches[0];
+        assert_eq!(&text[start..end], "ẞTRASSE");
+        assert!(find_terms("İ", "\u{307}", false).is_empty());
+    }
+
+    #[test]
+    fn enforce_flag_mode_reports_without_changing_the_output() {
+        let terms = [term("cache", "Zwischenspeicher", false)];
+        let (output, violations) = enforce("Leere den cache.", &terms, false);
+        assert_eq!(output, "Leere den cache.");
+        assert_eq!(violations.len(), 1);
+        assert!(!violations[0].fixed);
+
+        let (_, violations) = enforce("Leere den Zwischenspeicher.", &terms, false);
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn enforce_fix_mode_replaces_untranslated_source_terms() {
+        let terms = [term("cache", "Zwischenspeicher", false)];
+        let (output, violations) = enforce("Leere den Cache.", &terms, true);
+        assert_eq!(output, "Leere den Zwischenspeicher.");
+        assert!(violations[0].fixed);
+    }
+
+    #[test]
+    fn enforce_fix_mode_corrects_the_case_of_target_terms() {
+        let terms = [term("api", "API", true)];
+        let (output, violations) = enforce("Die Api antwortet.", &terms, true);
+        assert_eq!(output, "Die API antwortet.");
+        assert!(violations[0].fixed);
+    }
+
+    #[test]
+    fn enforce_fix_mode_leaves_other_renderings_alone() {
+        let terms = [term("cache", "Zwischenspeicher", false)];
+        let (output, violations) = enforce("Leere den Puffer.", &terms, true);
+        assert_eq!(output, "Leere den Puffer.");
+        assert!(!violations[0].fixed);
+    }
+
+    #[test]
+    fn enforce_fix_mode_skips_protected_tokens() {
+        let terms = [term("0", "null", false)];
+        let (output, violations) = enforce("Wert ⟦0⟧", &terms, true);
+        assert_eq!(output, "Wert ⟦0⟧");
+        assert!(!violations[0].fixed);
+    }
+}
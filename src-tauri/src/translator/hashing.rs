@@ -0,0 +1,26 @@
+use sha2::{Digest, Sha256};
+
+pub fn stable_hash<'a>(parts: impl IntoIterator<Item = &'a str>) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part.as_bytes());
+    }
+    hasher.finalize()[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_is_fixed_across_builds_and_separates_parts() {
+        assert_eq!(stable_hash(["Hallo"]), stable_hash(["Hallo"]));
+        assert_eq!(stable_hash(["Hallo"]).len(), 16);
+        assert_eq!(stable_hash([]), "e3b0c44298fc1c14");
+        assert_ne!(stable_hash(["ab", "c"]), stable_hash(["a", "bc"]));
+    }
+}
@@ -0,0 +1,116 @@
+use futures_util::future::{AbortHandle, AbortRegistration, Abortable};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::cache;
+use super::error::{TranslatorError, TranslatorErrorPayload};
+use super::events::EventSink;
+use super::providers::requires_login;
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+static ACTIVE_JOB: Mutex<Option<ActiveJob>> = Mutex::new(None);
+
+struct ActiveJob {
+    id: u64,
+    abort_handle: AbortHandle,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobPayload {
+    pub job_id: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobErrorPayload {
+    pub job_id: u64,
+    #[serde(flatten)]
+    pub error: TranslatorErrorPayload,
+}
+
+pub struct TranslationJob {
+    pub id: u64,
+    registration: AbortRegistration,
+}
+
+pub fn begin_job(events: &dyn EventSink) -> TranslationJob {
+    let id = NEXT_JOB_ID.fetch_add(1, Ordering::SeqCst);
+    let (abort_handle, registration) = AbortHandle::new_pair();
+
+    let previous = ACTIVE_JOB.lock().replace(ActiveJob { id, abort_handle });
+    if let Some(previous) = previous {
+        log_line!("[JOBS] Job {} superseded by job {}", previous.id, id);
+        previous.abort_handle.abort();
+    }
+
+    log_line!("[JOBS] Started job {}", id);
+    events.emit("translation-job-started", JobPayload { job_id: id });
+
+    TranslationJob { id, registration }
+}
+
+pub fn latest_job_id() -> u64 {
+    NEXT_JOB_ID.load(Ordering::SeqCst).saturating_sub(1)
+}
+
+pub fn cancel_active_job() -> Option<u64> {
+    let active = ACTIVE_JOB.lock().take()?;
+    log_line!("[JOBS] Cancelling job {}", active.id);
+    active.abort_handle.abort();
+    Some(active.id)
+}
+
+fn finish_job(id: u64) {
+    let mut active = ACTIVE_JOB.lock();
+    if active.as_ref().is_some_and(|job| job.id == id) {
+        *active = None;
+    }
+}
+
+impl TranslationJob {
+    pub async fn run<F: Future>(self, events: &dyn EventSink, future: F) -> Option<F::Output> {
+        let id = self.id;
+        let result = Abortable::new(future, self.registration).await;
+        finish_job(id);
+        let _ = tokio::task::spawn_blocking(cache::flush).await;
+
+        match result {
+            Ok(output) => Some(output),
+            Err(_) => {
+                log_line!("[JOBS] Job {} cancelled", id);
+                events.emit("translation-cancelled", JobPayload { job_id: id });
+                None
+            }
+        }
+    }
+}
+
+pub fn emit_job_complete(events: &dyn EventSink, job_id: u64) {
+    events.emit("translation-complete", JobPayload { job_id });
+}
+
+pub fn emit_job_error(events: &dyn EventSink, job_id: u64, error: TranslatorError) {
+    log_line!(
+        "[JOBS] Job {} failed with {}: {}",
+        job_id,
+        error.code(),
+        error
+    );
+
+    if error.is_unauthorized() && requires_login() {
+        log_line!("[JOBS] Backend rejected the access token, clearing stored credentials");
+        if let Err(e) = crate::clear_access_token() {
+            log_line!("[JOBS] Failed to clear access token: {}", e);
+        }
+        events.emit("credentials-missing", ());
+    }
+
+    events.emit(
+        "translation-error",
+        JobErrorPayload {
+            job_id,
+            error: error.payload(),
+        },
+    );
+}
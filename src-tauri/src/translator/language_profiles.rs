@@ -0,0 +1,449 @@
+pub const LATIN_CORPORA: &[(&str, &str)] = &[
+    (
+        "en",
+        "the people of this country have the right to know what is happening and why. we would like \
+         to thank you for your help with the project, and we will send you the results as soon as \
+         they are ready. it is not easy to find a good place to live in the city, but there are \
+         many options if you know where to look. she said that they were going to meet with him \
+         after work, which was a bit later than planned. please let me know if you have any \
+         questions about the meeting or the new schedule for next week.",
+    ),
+    (
+        "de",
+        "die menschen in diesem land haben das recht zu wissen, was passiert und warum. wir möchten \
+         uns für ihre hilfe bei dem projekt bedanken und werden ihnen die ergebnisse schicken, \
+         sobald sie fertig sind. es ist nicht einfach, in der stadt eine gute wohnung zu finden, \
+         aber es gibt viele möglichkeiten, wenn man weiß, wo man suchen muss. sie sagte, dass sie \
+         sich nach der arbeit mit ihm treffen würden, was etwas später war als geplant. bitte \
+         lassen sie mich wissen, ob sie noch fragen zur besprechung oder zum neuen zeitplan für \
+         die nächste woche haben. das ist auch schön und größer als gedacht.",
+    ),
+    (
+        "fr",
+        "les habitants de ce pays ont le droit de savoir ce qui se passe et pourquoi. nous voulons \
+         vous remercier pour votre aide dans le projet et nous vous enverrons les résultats dès \
+         qu'ils seront prêts. il n'est pas facile de trouver un bon logement dans la ville, mais \
+         il y a beaucoup de possibilités si l'on sait où chercher. elle a dit qu'ils allaient le \
+         retrouver après le travail, ce qui était un peu plus tard que prévu. merci de me faire \
+         savoir si vous avez des questions sur la réunion ou sur le nouveau calendrier de la \
+         semaine prochaine. ça a été très agréable, même à côté de l'hôtel.",
+    ),
+    (
+        "es",
+        "las personas de este país tienen derecho a saber lo que está pasando y por qué. queremos \
+         agradecerle su ayuda con el proyecto y le enviaremos los resultados en cuanto estén \
+         listos. no es fácil encontrar un buen lugar para vivir en la ciudad, pero hay muchas \
+         opciones si sabes dónde buscar. ella dijo que iban a reunirse con él después del trabajo, \
+         lo que fue un poco más tarde de lo previsto. por favor, avíseme si tiene alguna pregunta \
+         sobre la reunión o el nuevo horario de la próxima semana. mañana el niño también irá.",
+    ),
+    (
+        "it",
+        "le persone di questo paese hanno il diritto di sapere che cosa sta succedendo e perché. \
+         vorremmo ringraziarla per il suo aiuto con il progetto e le invieremo i risultati appena \
+         saranno pronti. non è facile trovare un buon posto dove vivere in città, ma ci sono molte \
+         possibilità se sai dove cercare. lei ha detto che si sarebbero incontrati con lui dopo il \
+         lavoro, un po' più tardi del previsto. per favore mi faccia sapere se ha delle domande \
+         sulla riunione o sul nuovo programma della prossima settimana. gli amici della famiglia \
+         sono già qui.",
+    ),
+    (
+        "pt",
+        "as pessoas deste país têm o direito de saber o que está acontecendo e por quê. gostaríamos \
+         de agradecer a sua ajuda com o projeto e enviaremos os resultados assim que estiverem \
+         prontos. não é fácil encontrar um bom lugar para morar na cidade, mas há muitas opções se \
+         você souber onde procurar. ela disse que iam se encontrar com ele depois do trabalho, o \
+         que foi um pouco mais tarde do que o planejado. por favor, me avise se tiver alguma \
+         pergunta sobre a reunião ou sobre o novo horário da próxima semana. então ação, coração \
+         e informações também são comuns.",
+    ),
+    (
+        "nl",
+        "de mensen in dit land hebben het recht om te weten wat er gebeurt en waarom. we willen u \
+         bedanken voor uw hulp bij het project en we sturen u de resultaten zodra ze klaar zijn. \
+         het is niet gemakkelijk om een goede woning in de stad te vinden, maar er zijn veel \
+         mogelijkheden als je weet waar je moet zoeken. ze zei dat ze hem na het werk zouden \
+         ontmoeten, wat iets later was dan gepland. laat me alstublieft weten of u nog vragen \
+         heeft over de vergadering of het nieuwe schema voor volgende week. dat is een mooie tijd \
+         geweest.",
+    ),
+    (
+        "sv",
+        "människorna i det här landet har rätt att veta vad som händer och varför. vi vill tacka \
+         dig för din hjälp med projektet och vi skickar resultaten till dig så snart de är klara. \
+         det är inte lätt att hitta ett bra ställe att bo på i staden, men det finns många \
+         möjligheter om man vet var man ska leta. hon sa att de skulle träffa honom efter jobbet, \
+         vilket var lite senare än planerat. låt mig veta om du har några frågor om mötet eller \
+         det nya schemat för nästa vecka. och också att det är mycket bättre nu.",
+    ),
+    (
+        "da",
+        "menneskerne i dette land har ret til at vide, hvad der sker, og hvorfor. vi vil gerne \
+         takke dig for din hjælp med projektet, og vi sender dig resultaterne, så snart de er \
+         klar. det er ikke let at finde et godt sted at bo i byen, men der er mange muligheder, \
+         hvis man ved, hvor man skal lede. hun sagde, at de ville mødes med ham efter arbejdet, \
+         hvilket var lidt senere end planlagt. lad mig vide, hvis du har spørgsmål om mødet eller \
+         den nye plan for næste uge. det er også meget bedre nu, og vi glæder os.",
+    ),
+    (
+        "nb",
+        "menneskene i dette landet har rett til å vite hva som skjer, og hvorfor. vi vil gjerne \
+         takke deg for hjelpen med prosjektet, og vi sender deg resultatene så snart de er klare. \
+         det er ikke lett å finne et godt sted å bo i byen, men det finnes mange muligheter hvis \
+         man vet hvor man skal lete. hun sa at de skulle møte ham etter jobben, noe som var litt \
+         senere enn planlagt. gi meg beskjed hvis du har spørsmål om møtet eller den nye planen \
+         for neste uke. det er også mye bedre nå, og vi gleder oss.",
+    ),
+    (
+        "fi",
+        "tämän maan ihmisillä on oikeus tietää, mitä tapahtuu ja miksi. haluamme kiittää sinua \
+         avustasi projektissa, ja lähetämme tulokset sinulle heti kun ne ovat valmiita. \
+         kaupungista ei ole helppo löytää hyvää asuntoa, mutta vaihtoehtoja on paljon, jos tietää \
+         mistä etsiä. hän sanoi, että he tapaisivat hänet töiden jälkeen, mikä oli vähän \
+         myöhemmin kuin oli suunniteltu. kerro minulle, jos sinulla on kysyttävää kokouksesta tai \
+         ensi viikon uudesta aikataulusta. se on myös paljon parempi nyt.",
+    ),
+    (
+        "pl",
+        "ludzie w tym kraju mają prawo wiedzieć, co się dzieje i dlaczego. chcielibyśmy \
+         podziękować za pomoc przy projekcie i prześlemy wyniki, gdy tylko będą gotowe. nie jest \
+         łatwo znaleźć dobre miejsce do życia w mieście, ale jest wiele możliwości, jeśli wiesz, \
+         gdzie szukać. powiedziała, że spotkają się z nim po pracy, co było trochę później niż \
+         planowano. proszę daj mi znać, jeśli masz pytania dotyczące spotkania lub nowego \
+         harmonogramu na przyszły tydzień. to jest także bardzo ważne dla nas wszystkich.",
+    ),
+    (
+        "cs",
+        "lidé v této zemi mají právo vědět, co se děje a proč. rádi bychom vám poděkovali za vaši \
+         pomoc s projektem a výsledky vám pošleme, jakmile budou hotové. není snadné najít dobré \
+         místo k bydlení ve městě, ale existuje mnoho možností, pokud víte, kde hledat. řekla, že \
+         se s ním setkají po práci, což bylo o něco později, než bylo plánováno. dejte mi prosím \
+         vědět, pokud máte nějaké otázky ohledně schůzky nebo nového rozvrhu na příští týden. je \
+         to také velmi důležité a tři dny stačí.",
+    ),
+    (
+        "sk",
+        "ľudia v tejto krajine majú právo vedieť, čo sa deje a prečo. radi by sme vám poďakovali \
+         za vašu pomoc s projektom a výsledky vám pošleme hneď, ako budú hotové. nie je ľahké \
+         nájsť dobré miesto na bývanie v meste, ale existuje veľa možností, ak viete, kde hľadať. \
+         povedala, že sa s ním stretnú po práci, čo bolo o niečo neskôr, ako bolo plánované. \
+         dajte mi prosím vedieť, ak máte nejaké otázky týkajúce sa stretnutia alebo nového \
+         rozvrhu na budúci týždeň. je to tiež veľmi dôležité.",
+    ),
+    (
+        "hu",
+        "az ország lakóinak joguk van tudni, hogy mi történik és miért. szeretnénk megköszönni a \
+         segítségét a projektben, és elküldjük az eredményeket, amint elkészülnek. nem könnyű jó \
+         lakást találni a városban, de sok lehetőség van, ha az ember tudja, hol keressen. azt \
+         mondta, hogy munka után találkoznak vele, ami egy kicsit később volt a tervezettnél. \
+         kérem, szóljon, ha bármilyen kérdése van a megbeszéléssel vagy a jövő heti új \
+         menetrenddel kapcsolatban. ez nagyon fontos és szép volt.",
+    ),
+    (
+        "ro",
+        "oamenii din această țară au dreptul să știe ce se întâmplă și de ce. dorim să vă \
+         mulțumim pentru ajutorul dumneavoastră la proiect și vă vom trimite rezultatele imediat \
+         ce vor fi gata. nu este ușor să găsești un loc bun de locuit în oraș, dar există multe \
+         opțiuni dacă știi unde să cauți. ea a spus că se vor întâlni cu el după muncă, ceea ce a \
+         fost puțin mai târziu decât era planificat. vă rog să-mi spuneți dacă aveți întrebări \
+         despre ședință sau despre noul program pentru săptămâna viitoare.",
+    ),
+    (
+        "tr",
+        "bu ülkedeki insanların neler olup bittiğini ve nedenini bilme hakkı var. projedeki \
+         yardımınız için size teşekkür etmek istiyoruz ve sonuçlar hazır olur olmaz size \
+         göndereceğiz. şehirde yaşamak için iyi bir yer bulmak kolay değil, ama nereye \
+         bakacağınızı biliyorsanız birçok seçenek var. işten sonra onunla buluşacaklarını \
+         söyledi, bu da planlanandan biraz daha geçti. toplantı veya gelecek haftanın yeni \
+         programı hakkında sorularınız varsa lütfen bana bildirin. bu çok güzel ve önemli bir şey.",
+    ),
+    (
+        "vi",
+        "người dân ở đất nước này có quyền biết điều gì đang xảy ra và tại sao. chúng tôi muốn \
+         cảm ơn bạn đã giúp đỡ dự án và chúng tôi sẽ gửi cho bạn kết quả ngay khi có. không dễ \
+         để tìm một nơi tốt để sống trong thành phố, nhưng có nhiều lựa chọn nếu bạn biết tìm ở \
+         đâu. cô ấy nói rằng họ sẽ gặp anh ấy sau giờ làm việc, muộn hơn một chút so với kế \
+         hoạch. vui lòng cho tôi biết nếu bạn có câu hỏi về cuộc họp hoặc lịch trình mới cho tuần \
+         sau.",
+    ),
+    (
+        "id",
+        "orang-orang di negara ini berhak untuk mengetahui apa yang sedang terjadi dan mengapa. \
+         kami ingin berterima kasih atas bantuan anda dalam proyek ini dan kami akan mengirimkan \
+         hasilnya segera setelah siap. tidak mudah untuk menemukan tempat tinggal yang baik di \
+         kota, tetapi ada banyak pilihan jika anda tahu di mana harus mencari. dia bilang mereka \
+         akan bertemu dengannya setelah bekerja, yang sedikit lebih lambat dari yang \
+         direncanakan. tolong beri tahu saya jika ada pertanyaan tentang rapat atau jadwal baru \
+         untuk minggu depan. saya tidak bisa datang karena sedang sibuk.",
+    ),
+    (
+        "ms",
+        "rakyat di negara ini berhak untuk mengetahui apa yang sedang berlaku dan mengapa. kami \
+         ingin mengucapkan terima kasih atas bantuan anda dalam projek ini dan kami akan \
+         menghantar keputusannya sebaik sahaja ia siap. bukan mudah untuk mencari tempat tinggal \
+         yang baik di bandar, tetapi terdapat banyak pilihan jika anda tahu di mana hendak \
+         mencari. dia berkata mereka akan berjumpa dengannya selepas kerja, yang agak lewat \
+         daripada yang dirancang. sila maklumkan kepada saya jika ada sebarang soalan mengenai \
+         mesyuarat atau jadual baharu untuk minggu hadapan. saya tidak boleh datang kerana sibuk.",
+    ),
+    (
+        "tl",
+        "ang mga tao sa bansang ito ay may karapatang malaman kung ano ang nangyayari at kung \
+         bakit. nais naming magpasalamat sa inyong tulong sa proyekto at ipapadala namin sa inyo \
+         ang mga resulta sa lalong madaling panahon. hindi madaling makahanap ng magandang \
+         tirahan sa lungsod, pero maraming pagpipilian kung alam mo kung saan maghahanap. sinabi \
+         niya na makikipagkita sila sa kanya pagkatapos ng trabaho, na medyo mas huli kaysa sa \
+         plano. pakisabi sa akin kung mayroon kayong mga tanong tungkol sa pulong o sa bagong \
+         iskedyul para sa susunod na linggo.",
+    ),
+    (
+        "hr",
+        "ljudi u ovoj zemlji imaju pravo znati što se događa i zašto. željeli bismo vam \
+         zahvaliti na pomoći s projektom i poslat ćemo vam rezultate čim budu gotovi. nije lako \
+         pronaći dobro mjesto za život u gradu, ali postoji mnogo mogućnosti ako znate gdje \
+         tražiti. rekla je da će se naći s njim nakon posla, što je bilo malo kasnije nego što \
+         je planirano. molim vas javite mi ako imate pitanja o sastanku ili novom rasporedu za \
+         sljedeći tjedan. to je također vrlo važno za sve nas.",
+    ),
+    (
+        "sl",
+        "ljudje v tej državi imajo pravico vedeti, kaj se dogaja in zakaj. radi bi se vam \
+         zahvalili za vašo pomoč pri projektu in rezultate vam bomo poslali takoj, ko bodo \
+         pripravljeni. ni lahko najti dobrega kraja za življenje v mestu, vendar je veliko \
+         možnosti, če veš, kje iskati. rekla je, da se bodo z njim dobili po službi, kar je bilo \
+         nekoliko kasneje, kot je bilo načrtovano. prosim, sporočite mi, če imate kakšna \
+         vprašanja o sestanku ali novem urniku za naslednji teden. to je tudi zelo pomembno.",
+    ),
+    (
+        "et",
+        "selle riigi inimestel on õigus teada, mis toimub ja miks. soovime teid tänada abi eest \
+         projektis ning saadame teile tulemused kohe, kui need on valmis. linnas ei ole lihtne \
+         leida head elukohta, kuid võimalusi on palju, kui tead, kust otsida. ta ütles, et nad \
+         kohtuvad temaga pärast tööd, mis oli natuke hiljem kui plaanitud. palun andke mulle \
+         teada, kui teil on küsimusi koosoleku või järgmise nädala uue ajakava kohta. see on ka \
+         väga oluline ja hea.",
+    ),
+    (
+        "lv",
+        "šīs valsts cilvēkiem ir tiesības zināt, kas notiek un kāpēc. mēs vēlamies pateikties \
+         jums par palīdzību projektā, un mēs jums nosūtīsim rezultātus, tiklīdz tie būs gatavi. \
+         nav viegli atrast labu dzīvesvietu pilsētā, bet ir daudz iespēju, ja zināt, kur \
+         meklēt. viņa teica, ka viņi satiksies ar viņu pēc darba, kas bija nedaudz vēlāk, nekā \
+         plānots. lūdzu, dariet man zināmu, ja jums ir jautājumi par sanāksmi vai jauno grafiku \
+         nākamajai nedēļai. tas ir arī ļoti svarīgi.",
+    ),
+    (
+        "lt",
+        "šios šalies žmonės turi teisę žinoti, kas vyksta ir kodėl. norėtume padėkoti jums už \
+         pagalbą vykdant projektą, ir atsiųsime jums rezultatus, kai tik jie bus paruošti. \
+         nelengva rasti gerą vietą gyventi mieste, bet yra daug galimybių, jei žinai, kur \
+         ieškoti. ji sakė, kad jie susitiks su juo po darbo, o tai buvo šiek tiek vėliau, nei \
+         planuota. prašau praneškite man, jei turite klausimų apie susitikimą ar naują kitos \
+         savaitės tvarkaraštį. tai taip pat labai svarbu.",
+    ),
+    (
+        "sq",
+        "njerëzit në këtë vend kanë të drejtë të dinë se çfarë po ndodh dhe pse. dëshirojmë t'ju \
+         falënderojmë për ndihmën tuaj në projekt dhe do t'ju dërgojmë rezultatet sapo të jenë \
+         gati. nuk është e lehtë të gjesh një vend të mirë për të jetuar në qytet, por ka shumë \
+         mundësi nëse di ku të kërkosh. ajo tha se do të takoheshin me të pas punës, që ishte \
+         pak më vonë se sa ishte planifikuar. ju lutem më njoftoni nëse keni pyetje për \
+         takimin ose orarin e ri për javën e ardhshme.",
+    ),
+    (
+        "ca",
+        "les persones d'aquest país tenen dret a saber què està passant i per què. volem \
+         agrair-vos la vostra ajuda amb el projecte i us enviarem els resultats tan aviat com \
+         estiguin a punt. no és fàcil trobar un bon lloc per viure a la ciutat, però hi ha moltes \
+         opcions si saps on buscar. ella va dir que es trobarien amb ell després de la feina, \
+         cosa que va ser una mica més tard del que estava previst. si us plau, feu-me saber si \
+         teniu cap pregunta sobre la reunió o el nou horari de la setmana que ve.",
+    ),
+    (
+        "eu",
+        "herrialde honetako jendeak eskubidea du zer gertatzen den eta zergatik jakiteko. \
+         proiektuan emandako laguntzagatik eskerrak eman nahi dizkizugu eta emaitzak bidaliko \
+         dizkizugu prest daudenean. ez da erraza hirian bizitzeko leku on bat aurkitzea, baina \
+         aukera asko daude non bilatu behar den jakinez gero. esan zuen lanaren ondoren harekin \
+         elkartuko zirela, aurreikusitakoa baino pixka bat beranduago. mesedez, jakinarazi \
+         iezadazu bilerari edo datorren asteko ordutegi berriari buruzko galderarik baduzu.",
+    ),
+    (
+        "ga",
+        "tá sé de cheart ag muintir na tíre seo a fháil amach cad atá ag tarlú agus cén fáth. ba \
+         mhaith linn buíochas a ghabháil leat as do chabhair leis an tionscadal agus seolfaimid \
+         na torthaí chugat chomh luath agus a bheidh siad réidh. níl sé éasca áit mhaith chun \
+         cónaí a fháil sa chathair, ach tá go leor roghanna ann má tá a fhios agat cá háit le \
+         cuardach. dúirt sí go mbuailfidís leis tar éis na hoibre. cuir in iúl dom má tá aon \
+         cheist agat faoin gcruinniú nó faoin sceideal nua don tseachtain seo chugainn.",
+    ),
+    (
+        "cy",
+        "mae gan bobl y wlad hon yr hawl i wybod beth sy'n digwydd a pham. hoffem ddiolch i chi \
+         am eich help gyda'r prosiect a byddwn yn anfon y canlyniadau atoch cyn gynted ag y \
+         byddant yn barod. nid yw'n hawdd dod o hyd i le da i fyw yn y ddinas, ond mae llawer o \
+         ddewisiadau os ydych chi'n gwybod ble i chwilio. dywedodd hi y bydden nhw'n cwrdd ag ef \
+         ar ôl gwaith, a oedd ychydig yn hwyrach na'r disgwyl. rhowch wybod i mi os oes gennych \
+         unrhyw gwestiynau am y cyfarfod neu'r amserlen newydd ar gyfer yr wythnos nesaf.",
+    ),
+    (
+        "is",
+        "fólkið í þessu landi á rétt á að vita hvað er að gerast og hvers vegna. við viljum \
+         þakka þér fyrir hjálpina við verkefnið og við munum senda þér niðurstöðurnar um leið og \
+         þær eru tilbúnar. það er ekki auðvelt að finna góðan stað til að búa á í borginni, en \
+         það eru margir möguleikar ef maður veit hvar á að leita. hún sagði að þau myndu hitta \
+         hann eftir vinnu, sem var aðeins seinna en áætlað var. láttu mig vita ef þú hefur \
+         einhverjar spurningar um fundinn eða nýju dagskrána fyrir næstu viku.",
+    ),
+    (
+        "sw",
+        "watu wa nchi hii wana haki ya kujua kinachoendelea na kwa nini. tungependa kukushukuru \
+         kwa msaada wako katika mradi huu na tutakutumia matokeo mara tu yatakapokuwa tayari. si \
+         rahisi kupata mahali pazuri pa kuishi mjini, lakini kuna chaguo nyingi ikiwa unajua wapi \
+         pa kutafuta. alisema kwamba watakutana naye baada ya kazi, jambo ambalo lilikuwa \
+         kuchelewa kidogo kuliko ilivyopangwa. tafadhali nijulishe kama una maswali yoyote kuhusu \
+         mkutano au ratiba mpya ya wiki ijayo.",
+    ),
+    (
+        "af",
+        "die mense in hierdie land het die reg om te weet wat gebeur en hoekom. ons wil u bedank \
+         vir u hulp met die projek en ons sal die resultate aan u stuur sodra dit gereed is. dit \
+         is nie maklik om 'n goeie plek in die stad te vind om te woon nie, maar daar is baie \
+         opsies as jy weet waar om te soek. sy het gesê dat hulle hom na die werk sou ontmoet, \
+         wat 'n bietjie later was as wat beplan is. laat my asseblief weet as u enige vrae het \
+         oor die vergadering of die nuwe skedule vir volgende week.",
+    ),
+    (
+        "az",
+        "bu ölkənin insanlarının nə baş verdiyini və niyə baş verdiyini bilmək hüququ var. \
+         layihədə köməyinizə görə sizə təşəkkür etmək istəyirik və nəticələr hazır olan kimi \
+         sizə göndərəcəyik. şəhərdə yaşamaq üçün yaxşı yer tapmaq asan deyil, amma harada \
+         axtaracağınızı bilsəniz, çoxlu seçim var. o dedi ki, işdən sonra onunla görüşəcəklər, bu \
+         da planlaşdırılandan bir az gec idi. iclas və ya gələn həftənin yeni cədvəli haqqında \
+         sualınız varsa, xahiş edirəm mənə bildirin.",
+    ),
+];
+
+pub const CYRILLIC_CORPORA: &[(&str, &str)] = &[
+    (
+        "ru",
+        "люди в этой стране имеют право знать, что происходит и почему. мы хотели бы \
+         поблагодарить вас за помощь в проекте и отправим вам результаты, как только они будут \
+         готовы. в городе нелегко найти хорошее место для жизни, но есть много вариантов, если \
+         знаешь, где искать. она сказала, что они встретятся с ним после работы, что было \
+         немного позже, чем планировалось. пожалуйста, сообщите мне, если у вас есть вопросы о \
+         встрече или новом расписании на следующую неделю. это очень важно для всех нас.",
+    ),
+    (
+        "uk",
+        "люди в цій країні мають право знати, що відбувається і чому. ми хотіли б подякувати \
+         вам за допомогу в проєкті та надішлемо вам результати, щойно вони будуть готові. у \
+         місті нелегко знайти гарне місце для життя, але є багато варіантів, якщо знаєш, де \
+         шукати. вона сказала, що вони зустрінуться з ним після роботи, що було трохи пізніше, \
+         ніж планувалося. будь ласка, повідомте мені, якщо у вас є питання щодо зустрічі або \
+         нового розкладу на наступний тиждень. це дуже важливо для всіх нас, її ґанок є.",
+    ),
+    (
+        "bg",
+        "хората в тази страна имат право да знаят какво се случва и защо. бихме искали да ви \
+         благодарим за помощта по проекта и ще ви изпратим резултатите веднага щом са готови. не \
+         е лесно да се намери добро място за живеене в града, но има много възможности, ако \
+         знаеш къде да търсиш. тя каза, че ще се срещнат с него след работа, което беше малко \
+         по-късно от планираното. моля, уведомете ме, ако имате въпроси относно срещата или \
+         новия график за следващата седмица. това е много важно за всички нас.",
+    ),
+    (
+        "sr",
+        "људи у овој земљи имају право да знају шта се дешава и зашто. желели бисмо да вам \
+         захвалимо на помоћи у пројекту и послаћемо вам резултате чим буду спремни. није лако \
+         наћи добро место за живот у граду, али постоји много могућности ако знаш где да тражиш. \
+         рекла је да ће се наћи са њим после посла, што је било мало касније него што је \
+         планирано. молим вас јавите ми ако имате питања о састанку или новом распореду за \
+         следећу недељу. то је веома важно за све нас, ђак и џеп.",
+    ),
+    (
+        "mk",
+        "луѓето во оваа земја имаат право да знаат што се случува и зошто. би сакале да ви се \
+         заблагодариме за помошта во проектот и ќе ви ги испратиме резултатите штом ќе бидат \
+         подготвени. не е лесно да се најде добро место за живеење во градот, но има многу \
+         можности ако знаеш каде да бараш. таа рече дека ќе се сретнат со него после работа, \
+         што беше малку подоцна од планираното. ве молам известете ме ако имате прашања за \
+         средбата или новиот распоред за следната недела. ѕвезда.",
+    ),
+    (
+        "be",
+        "людзі ў гэтай краіне маюць права ведаць, што адбываецца і чаму. мы хацелі б падзякаваць \
+         вам за дапамогу ў праекце і дашлём вам вынікі, як толькі яны будуць гатовыя. у горадзе \
+         няпроста знайсці добрае месца для жыцця, але ёсць шмат варыянтаў, калі ведаеш, дзе \
+         шукаць. яна сказала, што яны сустрэнуцца з ім пасля працы, што было крыху пазней, чым \
+         планавалася. калі ласка, паведаміце мне, калі ў вас ёсць пытанні пра сустрэчу або \
+         новы расклад на наступны тыдзень.",
+    ),
+    (
+        "kk",
+        "бұл елдің адамдарының не болып жатқанын және неліктен екенін білуге құқығы бар. біз \
+         сізге жобадағы көмегіңіз үшін алғыс айтқымыз келеді және нәтижелер дайын болған бойда \
+         сізге жібереміз. қалада тұруға жақсы орын табу оңай емес, бірақ қайдан іздеу керектігін \
+         білсеңіз, көптеген нұсқалар бар. ол жұмыстан кейін онымен кездесетіндерін айтты, бұл \
+         жоспарланғаннан сәл кешірек болды. кездесу немесе келесі аптаның жаңа кестесі туралы \
+         сұрақтарыңыз болса, маған хабарлаңыз.",
+    ),
+];
+
+pub const ARABIC_CORPORA: &[(&str, &str)] = &[
+    (
+        "ar",
+        "من حق الناس في هذا البلد أن يعرفوا ما الذي يحدث ولماذا. نود أن نشكرك على مساعدتك في \
+         المشروع وسنرسل لك النتائج بمجرد أن تكون جاهزة. ليس من السهل العثور على مكان جيد للعيش في \
+         المدينة، لكن هناك العديد من الخيارات إذا كنت تعرف أين تبحث. قالت إنهم سيلتقون به بعد \
+         العمل، وهو ما كان متأخرا قليلا عما كان مخططا له. يرجى إعلامي إذا كانت لديك أي أسئلة حول \
+         الاجتماع أو الجدول الجديد للأسبوع القادم. هذا مهم جدا لنا جميعا.",
+    ),
+    (
+        "fa",
+        "مردم این کشور حق دارند بدانند چه اتفاقی می افتد و چرا. می خواهیم از کمک شما در این پروژه \
+         تشکر کنیم و به محض آماده شدن نتایج را برای شما می فرستیم. پیدا کردن یک جای خوب برای \
+         زندگی در شهر آسان نیست، اما اگر بدانید کجا را بگردید گزینه های زیادی وجود دارد. او گفت \
+         که بعد از کار با او ملاقات خواهند کرد که کمی دیرتر از برنامه بود. لطفا اگر درباره جلسه \
+         یا برنامه جدید هفته آینده سوالی دارید به من بگویید. این برای همه ما خیلی مهم است.",
+    ),
+    (
+        "ur",
+        "اس ملک کے لوگوں کو یہ جاننے کا حق ہے کہ کیا ہو رہا ہے اور کیوں۔ ہم منصوبے میں آپ کی مدد \
+         کے لیے آپ کا شکریہ ادا کرنا چاہتے ہیں اور نتائج تیار ہوتے ہی آپ کو بھیج دیں گے۔ شہر میں \
+         رہنے کے لیے اچھی جگہ تلاش کرنا آسان نہیں ہے، لیکن اگر آپ جانتے ہیں کہ کہاں تلاش کرنا ہے \
+         تو بہت سے اختیارات ہیں۔ اس نے کہا کہ وہ کام کے بعد اس سے ملیں گے، جو منصوبے سے تھوڑا \
+         دیر سے تھا۔ براہ کرم مجھے بتائیں اگر آپ کے پاس اگلے ہفتے کے نئے شیڈول کے بارے میں کوئی \
+         سوال ہے۔ یہ ہم سب کے لیے بہت اہم ہے۔",
+    ),
+];
+
+pub const DEVANAGARI_CORPORA: &[(&str, &str)] = &[
+    (
+        "hi",
+        "इस देश के लोगों को यह जानने का अधिकार है कि क्या हो रहा है और क्यों। हम परियोजना में \
+         आपकी मदद के लिए आपका धन्यवाद करना चाहते हैं और परिणाम तैयार होते ही आपको भेज देंगे। \
+         शहर में रहने के लिए अच्छी जगह ढूंढना आसान नहीं है, लेकिन अगर आप जानते हैं कि कहां \
+         खोजना है तो बहुत सारे विकल्प हैं। उसने कहा कि वे काम के बाद उससे मिलेंगे, जो योजना से \
+         थोड़ा देर से था। कृपया मुझे बताएं अगर आपके पास बैठक या अगले सप्ताह के नए कार्यक्रम के \
+         बारे में कोई प्रश्न है। यह हम सबके लिए बहुत महत्वपूर्ण है।",
+    ),
+    (
+        "mr",
+        "या देशातील लोकांना काय घडत आहे आणि का हे जाणून घेण्याचा अधिकार आहे. प्रकल्पातील \
+         तुमच्या मदतीबद्दल आम्ही तुमचे आभार मानू इच्छितो आणि निकाल तयार होताच आम्ही ते तुम्हाला \
+         पाठवू. शहरात राहण्यासाठी चांगली जागा शोधणे सोपे नाही, पण कुठे शोधायचे हे माहीत असेल \
+         तर बरेच पर्याय आहेत. ती म्हणाली की ते कामानंतर त्याला भेटतील, जे नियोजित वेळेपेक्षा \
+         थोडे उशिरा होते. कृपया बैठकीबद्दल किंवा पुढील आठवड्याच्या नवीन वेळापत्रकाबद्दल काही \
+         प्रश्न असल्यास मला कळवा. हे आपल्या सर्वांसाठी खूप महत्त्वाचे आहे.",
+    ),
+    (
+        "ne",
+        "यस देशका मानिसहरूलाई के भइरहेको छ र किन भन्ने थाहा पाउने अधिकार छ। हामी परियोजनामा \
+         तपाईंको सहयोगको लागि धन्यवाद दिन चाहन्छौं र नतिजा तयार हुने बित्तिकै तपाईंलाई पठाउनेछौं। \
+         सहरमा बस्नको लागि राम्रो ठाउँ खोज्न सजिलो छैन, तर कहाँ खोज्ने भन्ने थाहा छ भने धेरै \
+         विकल्पहरू छन्। उनले भनिन् कि उनीहरू कामपछि उसलाई भेट्नेछन्, जुन योजना भन्दा अलि ढिलो \
+         थियो। कृपया बैठक वा अर्को हप्ताको नयाँ तालिकाबारे कुनै प्रश्न छ भने मलाई भन्नुहोस्। यो \
+         हामी सबैको लागि धेरै महत्त्वपूर्ण छ।",
+    ),
+];
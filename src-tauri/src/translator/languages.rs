@@ -0,0 +1,192 @@
+use crate::settings::{LanguageRule, Settings};
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LanguagePair {
+    pub source: String,
+    pub target: String,
+}
+
+const ISO_639_1: &[&str] = &[
+    "aa", "ab", "ae", "af", "ak", "am", "an", "ar", "as", "av", "ay", "az", "ba", "be", "bg", "bi",
+    "bm", "bn", "bo", "br", "bs", "ca", "ce", "ch", "co", "cr", "cs", "cu", "cv", "cy", "da", "de",
+    "dv", "dz", "ee", "el", "en", "eo", "es", "et", "eu", "fa", "ff", "fi", "fj", "fo", "fr", "fy",
+    "ga", "gd", "gl", "gn", "gu", "gv", "ha", "he", "hi", "ho", "hr", "ht", "hu", "hy", "hz", "ia",
+    "id", "ie", "ig", "ii", "ik", "io", "is", "it", "iu", "ja", "jv", "ka", "kg", "ki", "kj", "kk",
+    "kl", "km", "kn", "ko", "kr", "ks", "ku", "kv", "kw", "ky", "la", "lb", "lg", "li", "ln", "lo",
+    "lt", "lu", "lv", "mg", "mh", "mi", "mk", "ml", "mn", "mr", "ms", "mt", "my", "na", "nb", "nd",
+    "ne", "ng", "nl", "nn", "no", "nr", "nv", "ny", "oc", "oj", "om", "or", "os", "pa", "pi", "pl",
+    "ps", "pt", "qu", "rm", "rn", "ro", "ru", "rw", "sa", "sc", "sd", "se", "sg", "si", "sk", "sl",
+    "sm", "sn", "so", "sq", "sr", "ss", "st", "su", "sv", "sw", "ta", "te", "tg", "th", "ti", "tk",
+    "tl", "tn", "to", "tr", "ts", "tt", "tw", "ty", "ug", "uk", "ur", "uz", "ve", "vi", "vo", "wa",
+    "wo", "xh", "yi", "yo", "za", "zh", "zu",
+];
+
+pub fn is_known_language(code: &str) -> bool {
+    ISO_639_1.contains(&code)
+}
+
+pub fn normalize_code(code: &str) -> String {
+    code.trim()
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+pub fn canonical_code(code: &str) -> String {
+    code.trim()
+        .split(['-', '_'])
+        .filter(|part| !part.is_empty())
+        .enumerate()
+        .map(|(index, part)| match (index, part.len()) {
+            (0, _) => part.to_lowercase(),
+            (_, 4) if part.chars().all(|c| c.is_ascii_alphabetic()) => {
+                let mut chars = part.chars();
+                let first = chars.next().unwrap_or_default().to_ascii_uppercase();
+                std::iter::once(first)
+                    .chain(chars.map(|c| c.to_ascii_lowercase()))
+                    .collect()
+            }
+            (_, 2) | (_, 3) => part.to_uppercase(),
+            _ => part.to_lowercase(),
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn rule_matches(rule: &LanguageRule, source: &str, my_languages: &[String]) -> bool {
+    match rule.source.as_str() {
+        "*" => true,
+        "known" => my_languages
+            .iter()
+            .any(|lang| normalize_code(lang) == source),
+        "unknown" => !my_languages
+            .iter()
+            .any(|lang| normalize_code(lang) == source),
+        code => normalize_code(code) == source,
+    }
+}
+
+fn resolve_rule_target(target: &str, my_languages: &[String]) -> Option<String> {
+    match target {
+        "primary" => my_languages.first().cloned(),
+        "secondary" => my_languages.get(1).cloned(),
+        code => Some(canonical_code(code)).filter(|code| !code.is_empty()),
+    }
+}
+
+pub fn resolve_language_pair(source_lang: &str, settings: &Settings) -> LanguagePair {
+    let source = normalize_code(source_lang);
+    let my_languages: Vec<String> = settings
+        .my_languages
+        .iter()
+        .map(|lang| canonical_code(lang))
+        .filter(|lang| !lang.is_empty())
+        .collect();
+
+    let from_rules = settings
+        .language_rules
+        .iter()
+        .filter(|rule| rule_matches(rule, &source, &my_languages))
+        .find_map(|rule| {
+            resolve_rule_target(&rule.target, &my_languages)
+                .filter(|target| normalize_code(target) != source)
+        });
+
+    let target = from_rules.unwrap_or_else(|| {
+        let primary = my_languages
+            .first()
+            .cloned()
+            .unwrap_or_else(|| "en".to_string());
+        if source == normalize_code(&primary) {
+            my_languages
+                .get(1)
+                .cloned()
+                .unwrap_or_else(|| if source == "en" { "de" } else { "en" }.to_string())
+        } else {
+            primary
+        }
+    });
+
+    LanguagePair { source, target }
+}
+
+pub fn resolve_with_override(
+    source_lang: &str,
+    target_lang: Option<&str>,
+    settings: &Settings,
+) -> LanguagePair {
+    match target_lang
+        .map(canonical_code)
+        .filter(|lang| !lang.is_empty())
+    {
+        Some(target) => LanguagePair {
+            source: normalize_code(source_lang),
+            target,
+        },
+        None => resolve_language_pair(source_lang, settings),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(my_languages: &[&str], rules: &[(&str, &str)]) -> Settings {
+        Settings {
+            my_languages: my_languages.iter().map(|lang| lang.to_string()).collect(),
+            language_rules: rules
+                .iter()
+                .map(|(source, target)| LanguageRule {
+                    source: source.to_string(),
+                    target: target.to_string(),
+                })
+                .collect(),
+            ..Settings::default()
+        }
+    }
+
+    #[test]
+    fn canonical_code_keeps_region_and_script() {
+        assert_eq!(canonical_code("pt_br"), "pt-BR");
+        assert_eq!(canonical_code(" ZH-hant-tw "), "zh-Hant-TW");
+        assert_eq!(canonical_code("es-419"), "es-419");
+        assert_eq!(canonical_code("DE"), "de");
+        assert_eq!(normalize_code("pt-BR"), "pt");
+    }
+
+    #[test]
+    fn regional_targets_are_kept() {
+        let settings = settings(&["pt-br", "en"], &[]);
+        let pair = resolve_language_pair("en-US", &settings);
+        assert_eq!(pair.source, "en");
+        assert_eq!(pair.target, "pt-BR");
+
+        let pair = resolve_language_pair("pt", &settings);
+        assert_eq!(pair.target, "en");
+
+        let pair = resolve_with_override("de", Some("zh_tw"), &settings);
+        assert_eq!(pair.target, "zh-TW");
+    }
+
+    #[test]
+    fn rule_targeting_the_source_falls_through_to_next_rule() {
+        let settings = settings(&["de", "en"], &[("*", "fr"), ("*", "es")]);
+        let pair = resolve_language_pair("fr", &settings);
+        assert_eq!(pair.target, "es");
+
+        let pair = resolve_language_pair("it", &settings);
+        assert_eq!(pair.target, "fr");
+    }
+
+    #[test]
+    fn regional_primary_falls_back_away_from_source() {
+        let settings = settings(&["en-US"], &[]);
+        let pair = resolve_language_pair("en", &settings);
+        assert_eq!(pair.target, "de");
+
+        let pair = resolve_language_pair("fr", &settings);
+        assert_eq!(pair.target, "en-US");
+    }
+}
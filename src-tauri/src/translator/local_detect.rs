@@ -0,0 +1,321 @@
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::language_profiles::{
+    ARABIC_CORPORA, CYRILLIC_CORPORA, DEVANAGARI_CORPORA, LATIN_CORPORA,
+};
+
+const SMOOTHING: f64 = 0.5;
+const VOCABULARY_SIZE: f64 = 8192.0;
+const SCORE_SCALE: f64 = 0.6;
+const MIN_CONFIDENT_TRIGRAMS: usize = 12;
+pub const LOCAL_CONFIDENCE_THRESHOLD: f64 = 0.6;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageCandidate {
+    pub language: String,
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Script {
+    Latin,
+    Cyrillic,
+    Greek,
+    Arabic,
+    Hebrew,
+    Devanagari,
+    Bengali,
+    Gurmukhi,
+    Gujarati,
+    Tamil,
+    Telugu,
+    Kannada,
+    Malayalam,
+    Sinhala,
+    Thai,
+    Lao,
+    Khmer,
+    Myanmar,
+    Georgian,
+    Armenian,
+    Ethiopic,
+    Hangul,
+    Kana,
+    Han,
+}
+
+impl Script {
+    fn of(c: char) -> Option<Script> {
+        let script = match c as u32 {
+            0x0041..=0x005A | 0x0061..=0x007A | 0x00C0..=0x024F | 0x1E00..=0x1EFF => Script::Latin,
+            0x0400..=0x052F => Script::Cyrillic,
+            0x0370..=0x03FF | 0x1F00..=0x1FFF => Script::Greek,
+            0x0590..=0x05FF => Script::Hebrew,
+            0x0600..=0x06FF | 0x0750..=0x077F | 0xFB50..=0xFDFF | 0xFE70..=0xFEFF => Script::Arabic,
+            0x0900..=0x097F => Script::Devanagari,
+            0x0980..=0x09FF => Script::Bengali,
+            0x0A00..=0x0A7F => Script::Gurmukhi,
+            0x0A80..=0x0AFF => Script::Gujarati,
+            0x0B80..=0x0BFF => Script::Tamil,
+            0x0C00..=0x0C7F => Script::Telugu,
+            0x0C80..=0x0CFF => Script::Kannada,
+            0x0D00..=0x0D7F => Script::Malayalam,
+            0x0D80..=0x0DFF => Script::Sinhala,
+            0x0E00..=0x0E7F => Script::Thai,
+            0x0E80..=0x0EFF => Script::Lao,
+            0x1780..=0x17FF => Script::Khmer,
+            0x1000..=0x109F => Script::Myanmar,
+            0x10A0..=0x10FF => Script::Georgian,
+            0x0530..=0x058F => Script::Armenian,
+            0x1200..=0x139F => Script::Ethiopic,
+            0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Script::Hangul,
+            0x3040..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F => Script::Kana,
+            0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => Script::Han,
+            _ => return None,
+        };
+        Some(script)
+    }
+
+    fn single_language(self) -> Option<&'static str> {
+        let language = match self {
+            Script::Greek => "el",
+            Script::Hebrew => "he",
+            Script::Bengali => "bn",
+            Script::Gurmukhi => "pa",
+            Script::Gujarati => "gu",
+            Script::Tamil => "ta",
+            Script::Telugu => "te",
+            Script::Kannada => "kn",
+            Script::Malayalam => "ml",
+            Script::Sinhala => "si",
+            Script::Thai => "th",
+            Script::Lao => "lo",
+            Script::Khmer => "km",
+            Script::Myanmar => "my",
+            Script::Georgian => "ka",
+            Script::Armenian => "hy",
+            Script::Ethiopic => "am",
+            Script::Hangul => "ko",
+            Script::Kana => "ja",
+            Script::Han => "zh",
+            _ => return None,
+        };
+        Some(language)
+    }
+
+    fn corpora(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Script::Latin => LATIN_CORPORA,
+            Script::Cyrillic => CYRILLIC_CORPORA,
+            Script::Arabic => ARABIC_CORPORA,
+            Script::Devanagari => DEVANAGARI_CORPORA,
+            _ => &[],
+        }
+    }
+}
+
+struct TrigramProfile {
+    language: &'static str,
+    counts: HashMap<String, u32>,
+    total: u32,
+}
+
+impl TrigramProfile {
+    fn from_corpus(language: &'static str, corpus: &str) -> Self {
+        let mut counts = HashMap::new();
+        let mut total = 0;
+        for trigram in trigrams(corpus) {
+            *counts.entry(trigram).or_insert(0) += 1;
+            total += 1;
+        }
+        TrigramProfile {
+            language,
+            counts,
+            total,
+        }
+    }
+
+    fn log_likelihood(&self, trigram: &str) -> f64 {
+        let count = self.counts.get(trigram).copied().unwrap_or(0) as f64;
+        ((count + SMOOTHING) / (self.total as f64 + SMOOTHING * VOCABULARY_SIZE)).ln()
+    }
+}
+
+static PROFILES: Lazy<HashMap<Script, Vec<TrigramProfile>>> = Lazy::new(|| {
+    [
+        Script::Latin,
+        Script::Cyrillic,
+        Script::Arabic,
+        Script::Devanagari,
+    ]
+    .into_iter()
+    .map(|script| {
+        let profiles = script
+            .corpora()
+            .iter()
+            .map(|(language, corpus)| TrigramProfile::from_corpus(language, corpus))
+            .collect();
+        (script, profiles)
+    })
+    .collect()
+});
+
+fn trigrams(text: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    for word in text
+        .split(|c: char| !c.is_alphabetic() && c != '\'')
+        .filter(|word| !word.is_empty())
+    {
+        let padded: Vec<char> = std::iter::once(' ')
+            .chain(word.chars().flat_map(char::to_lowercase))
+            .chain(std::iter::once(' '))
+            .collect();
+        result.extend(
+            padded
+                .windows(3)
+                .map(|window| window.iter().collect::<String>()),
+        );
+    }
+    result
+}
+
+fn dominant_script(text: &str) -> Option<(Script, usize)> {
+    let mut counts: HashMap<Script, usize> = HashMap::new();
+    for script in text.chars().filter_map(Script::of) {
+        *counts.entry(script).or_insert(0) += 1;
+    }
+
+    if counts.contains_key(&Script::Kana) {
+        let japanese = counts.get(&Script::Kana).copied().unwrap_or(0)
+            + counts.remove(&Script::Han).unwrap_or(0);
+        counts.insert(Script::Kana, japanese);
+    }
+
+    counts.into_iter().max_by_key(|(_, count)| *count)
+}
+
+fn rank_by_profiles(script: Script, text: &str) -> Vec<LanguageCandidate> {
+    let text_trigrams = trigrams(text);
+    let profiles = match PROFILES.get(&script) {
+        Some(profiles) if !profiles.is_empty() && !text_trigrams.is_empty() => profiles,
+        _ => return Vec::new(),
+    };
+
+    let scores: Vec<(&str, f64)> = profiles
+        .iter()
+        .map(|profile| {
+            let score: f64 = text_trigrams
+                .iter()
+                .map(|trigram| profile.log_likelihood(trigram))
+                .sum();
+            (profile.language, score * SCORE_SCALE)
+        })
+        .collect();
+
+    let best = scores
+        .iter()
+        .map(|(_, score)| *score)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let weights: Vec<f64> = scores
+        .iter()
+        .map(|(_, score)| (score - best).exp())
+        .collect();
+    let total: f64 = weights.iter().sum();
+
+    let length_factor = (text_trigrams.len() as f64 / MIN_CONFIDENT_TRIGRAMS as f64).min(1.0);
+
+    let mut candidates: Vec<LanguageCandidate> = scores
+        .iter()
+        .zip(weights)
+        .map(|((language, _), weight)| LanguageCandidate {
+            language: language.to_string(),
+            confidence: weight / total * length_factor,
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    candidates
+}
+
+pub fn detect_local(text: &str) -> Vec<LanguageCandidate> {
+    let Some((script, letter_count)) = dominant_script(text) else {
+        return Vec::new();
+    };
+
+    if let Some(language) = script.single_language() {
+        let confidence = (letter_count as f64 / 4.0).min(1.0);
+        return vec![LanguageCandidate {
+            language: language.to_string(),
+            confidence,
+        }];
+    }
+
+    rank_by_profiles(script, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn best(text: &str) -> LanguageCandidate {
+        detect_local(text).into_iter().next().expect("a candidate")
+    }
+
+    #[test]
+    fn sentences_are_matched_to_their_corpus_language() {
+        let samples = [
+            (
+                "en",
+                "We will send you the results as soon as they are ready.",
+            ),
+            (
+                "de",
+                "Wir schicken Ihnen die Ergebnisse, sobald sie fertig sind.",
+            ),
+            (
+                "fr",
+                "Nous vous enverrons les résultats dès qu'ils seront prêts.",
+            ),
+            ("es", "Le enviaremos los resultados en cuanto estén listos."),
+            (
+                "ru",
+                "Мы отправим вам результаты, как только они будут готовы.",
+            ),
+        ];
+        for (language, text) in samples {
+            let candidate = best(text);
+            assert_eq!(candidate.language, language, "{}", text);
+            assert!(
+                candidate.confidence >= LOCAL_CONFIDENCE_THRESHOLD,
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn single_language_scripts_skip_trigram_scoring() {
+        let candidates = detect_local("こんにちは、世界");
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].language, "ja");
+        assert_eq!(candidates[0].confidence, 1.0);
+
+        let candidates = detect_local("안녕");
+        assert_eq!(candidates[0].language, "ko");
+        assert_eq!(candidates[0].confidence, 0.5);
+    }
+
+    #[test]
+    fn short_latin_text_stays_below_the_remote_threshold() {
+        let candidate = best("Hola");
+        assert!(candidate.confidence < LOCAL_CONFIDENCE_THRESHOLD);
+    }
+
+    #[test]
+    fn empty_or_symbol_only_input_has_no_candidates() {
+        assert!(detect_local("").is_empty());
+        assert!(detect_local("  123 !? ").is_empty());
+    }
+}
@@ -0,0 +1,583 @@
+use anyhow::Result;
+use parking_lot::Mutex;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::hashing::stable_hash;
+use super::jobs::latest_job_id;
+use super::languages::normalize_code;
+use super::LanguagePair;
+use crate::settings::load_settings;
+
+const MAX_REFERENCES: usize = 3;
+const TMX_INLINE_CODES: [&[u8]; 5] = [b"bpt", b"ept", b"ph", b"it", b"ut"];
+
+static MEMORY: Mutex<Option<Vec<MemoryUnit>>> = Mutex::new(None);
+static PENDING: Mutex<Option<PendingTranslation>> = Mutex::new(None);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryUnit {
+    pub source_lang: String,
+    pub target_lang: String,
+    pub source: String,
+    pub target: String,
+    #[serde(default)]
+    pub updated_at: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct MemoryMatch {
+    pub source: String,
+    pub target: String,
+    pub score: u8,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryImportSummary {
+    pub imported: usize,
+    pub pairs: Vec<LanguagePair>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryStats {
+    pub units: usize,
+    pub pairs: Vec<LanguagePair>,
+}
+
+struct TmxDocument {
+    source_lang: Option<String>,
+    entries: Vec<Vec<(String, String)>>,
+}
+
+struct PendingTranslation {
+    job_id: u64,
+    pair: LanguagePair,
+    source: String,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+fn normalize_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn get_memory_path() -> Option<PathBuf> {
+    let app_dir = dirs::data_dir()?.join("langra");
+    fs::create_dir_all(&app_dir).ok()?;
+    Some(app_dir.join("translation_memory.json"))
+}
+
+fn load_units() -> Vec<MemoryUnit> {
+    let Some(path) = get_memory_path() else {
+        return Vec::new();
+    };
+    let units: Vec<MemoryUnit> = match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log_line!("[MEMORY] Failed to parse translation memory: {:?}", e);
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    };
+    log_line!("[MEMORY] Loaded {} translation units", units.len());
+    units
+}
+
+fn persist(units: &[MemoryUnit]) -> Result<(), String> {
+    let path = get_memory_path().ok_or("Could not resolve the data directory")?;
+    let json = serde_json::to_string(units)
+        .map_err(|e| format!("Failed to serialize translation memory: {}", e))?;
+    fs::write(path, json).map_err(|e| format!("Failed to write translation memory: {}", e))
+}
+
+fn with_memory<T>(f: impl FnOnce(&mut Vec<MemoryUnit>) -> T) -> T {
+    let mut memory_lock = MEMORY.lock();
+    let units = memory_lock.get_or_insert_with(load_units);
+    f(units)
+}
+
+fn unit_key(unit: &MemoryUnit) -> (String, String, String) {
+    (
+        unit.source_lang.clone(),
+        unit.target_lang.clone(),
+        normalize_text(&unit.source),
+    )
+}
+
+fn merge_units(units: &mut Vec<MemoryUnit>, incoming: Vec<MemoryUnit>) {
+    let mut index: HashMap<(String, String, String), usize> = units
+        .iter()
+        .enumerate()
+        .map(|(position, unit)| (unit_key(unit), position))
+        .collect();
+
+    for mut unit in incoming {
+        unit.source_lang = normalize_code(&unit.source_lang);
+        unit.target_lang = normalize_code(&unit.target_lang);
+        match index.get(&unit_key(&unit)) {
+            Some(position) => units[*position] = unit,
+            None => {
+                index.insert(unit_key(&unit), units.len());
+                units.push(unit);
+            }
+        }
+    }
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, left) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, right) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(left != right);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+fn best_matches(
+    units: &[MemoryUnit],
+    text: &str,
+    pair: &LanguagePair,
+    threshold: u8,
+) -> Vec<MemoryMatch> {
+    let source_lang = normalize_code(&pair.source);
+    let target_lang = normalize_code(&pair.target);
+    let normalized = normalize_text(text);
+    if normalized.is_empty() {
+        return Vec::new();
+    }
+    let query: Vec<char> = normalized.to_lowercase().chars().collect();
+    let threshold = f64::from(threshold) / 100.0;
+
+    let mut matches = Vec::new();
+    for unit in units
+        .iter()
+        .filter(|unit| unit.source_lang == source_lang && unit.target_lang == target_lang)
+    {
+        let candidate = normalize_text(&unit.source);
+        if candidate == normalized {
+            return vec![MemoryMatch {
+                source: unit.source.clone(),
+                target: unit.target.clone(),
+                score: 100,
+            }];
+        }
+
+        let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+        let longer = query.len().max(candidate.len());
+        let shorter = query.len().min(candidate.len());
+        if (shorter as f64) < longer as f64 * threshold {
+            continue;
+        }
+        let score = 1.0 - levenshtein(&query, &candidate) as f64 / longer as f64;
+        if score >= threshold {
+            matches.push(MemoryMatch {
+                source: unit.source.clone(),
+                target: unit.target.clone(),
+                score: ((score * 100.0).floor() as u8).min(99),
+            });
+        }
+    }
+
+    matches.sort_by_key(|found| std::cmp::Reverse(found.score));
+    matches.truncate(MAX_REFERENCES);
+    matches
+}
+
+pub fn lookup(text: &str, pair: &LanguagePair) -> Vec<MemoryMatch> {
+    let settings = load_settings();
+    if !settings.translation_memory_enabled {
+        return Vec::new();
+    }
+    let matches =
+        with_memory(|units| best_matches(units, text, pair, settings.memory_fuzzy_threshold));
+    if let Some(best) = matches.first() {
+        log_line!(
+            "[MEMORY] Found {} matches, best score {}%",
+            matches.len(),
+            best.score
+        );
+    }
+    matches
+}
+
+pub fn fingerprint(matches: &[MemoryMatch]) -> Option<String> {
+    if matches.is_empty() {
+        return None;
+    }
+    let scores: Vec<String> = matches
+        .iter()
+        .map(|entry| entry.score.to_string())
+        .collect();
+    Some(stable_hash(matches.iter().zip(&scores).flat_map(
+        |(entry, score)| [entry.source.as_str(), entry.target.as_str(), score.as_str()],
+    )))
+}
+
+pub fn remember_pending(job_id: u64, source: &str, pair: &LanguagePair) {
+    *PENDING.lock() = Some(PendingTranslation {
+        job_id,
+        pair: pair.clone(),
+        source: source.to_string(),
+    });
+}
+
+pub fn confirm_translation(target: &str) {
+    let Some(pending) = PENDING.lock().take() else {
+        return;
+    };
+    if pending.job_id != latest_job_id()
+        || !load_settings().translation_memory_enabled
+        || pending.source.trim().is_empty()
+        || target.trim().is_empty()
+    {
+        return;
+    }
+
+    log_line!(
+        "[MEMORY] Storing confirmed translation for {} -> {}",
+        pending.pair.source,
+        pending.pair.target
+    );
+    let unit = MemoryUnit {
+        source_lang: pending.pair.source,
+        target_lang: pending.pair.target,
+        source: pending.source.trim().to_string(),
+        target: target.trim().to_string(),
+        updated_at: now_secs(),
+    };
+    with_memory(|units| {
+        merge_units(units, vec![unit]);
+        if let Err(e) = persist(units) {
+            log_line!("[MEMORY] {}", e);
+        }
+    });
+}
+
+fn attribute(element: &BytesStart, name: &[u8]) -> Result<Option<String>> {
+    Ok(element
+        .attributes()
+        .flatten()
+        .find(|attribute| attribute.key.local_name().as_ref() == name)
+        .map(|attribute| attribute.unescape_value().map(|value| value.to_string()))
+        .transpose()?)
+}
+
+fn read_tmx(content: &str) -> Result<TmxDocument> {
+    let mut reader = Reader::from_str(content);
+
+    let mut source_lang = None;
+    let mut entries = Vec::new();
+    let mut variants: Vec<(String, String)> = Vec::new();
+    let mut current_lang: Option<String> = None;
+    let mut in_seg = false;
+    let mut skip_depth = 0usize;
+    let mut seg = String::new();
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) => match element.local_name().as_ref() {
+                b"header" => source_lang = attribute(&element, b"srclang")?,
+                b"tu" => variants.clear(),
+                b"tuv" => current_lang = attribute(&element, b"lang")?,
+                b"seg" => {
+                    in_seg = true;
+                    seg.clear();
+                }
+                name if in_seg && TMX_INLINE_CODES.contains(&name) => skip_depth += 1,
+                _ => {}
+            },
+            Event::Empty(element) if element.local_name().as_ref() == b"header" => {
+                source_lang = attribute(&element, b"srclang")?;
+            }
+            Event::Text(text) if in_seg && skip_depth == 0 => seg.push_str(&text.unescape()?),
+            Event::CData(text) if in_seg && skip_depth == 0 => {
+                seg.push_str(&String::from_utf8_lossy(&text.into_inner()))
+            }
+            Event::End(element) => match element.local_name().as_ref() {
+                name if skip_depth > 0 && TMX_INLINE_CODES.contains(&name) => skip_depth -= 1,
+                b"seg" => {
+                    in_seg = false;
+                    let text = seg.trim();
+                    if let Some(lang) = &current_lang {
+                        if !text.is_empty() {
+                            variants.push((lang.clone(), text.to_string()));
+                        }
+                    }
+                }
+                b"tuv" => current_lang = None,
+                b"tu" if variants.len() > 1 => entries.push(std::mem::take(&mut variants)),
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(TmxDocument {
+        source_lang: source_lang.filter(|lang| lang != "*all*"),
+        entries,
+    })
+}
+
+fn import_tmx(content: &str, default_pair: Option<&LanguagePair>) -> Result<Vec<MemoryUnit>> {
+    let TmxDocument {
+        source_lang,
+        entries,
+    } = read_tmx(content)?;
+    let now = now_secs();
+    let unit = |source_lang: &str, target_lang: &str, source: &str, target: &str| MemoryUnit {
+        source_lang: normalize_code(source_lang),
+        target_lang: normalize_code(target_lang),
+        source: source.to_string(),
+        target: target.to_string(),
+        updated_at: now,
+    };
+
+    let mut units = Vec::new();
+    for variants in entries {
+        let find = |lang: &str| {
+            variants
+                .iter()
+                .find(|(code, _)| normalize_code(code) == normalize_code(lang))
+        };
+
+        match default_pair {
+            Some(pair) => {
+                if let (Some((_, source)), Some((_, target))) =
+                    (find(&pair.source), find(&pair.target))
+                {
+                    units.push(unit(&pair.source, &pair.target, source, target));
+                }
+            }
+            None => {
+                let (lang, source) = source_lang
+                    .as_deref()
+                    .and_then(find)
+                    .unwrap_or(&variants[0]);
+                for (target_lang, target) in &variants {
+                    if normalize_code(target_lang) != normalize_code(lang) {
+                        units.push(unit(lang, target_lang, source, target));
+                    }
+                }
+            }
+        }
+    }
+    Ok(units)
+}
+
+fn escape_xml(text: &str) -> String {
+    quick_xml::escape::escape(text).into_owned()
+}
+
+fn export_tmx(units: &[MemoryUnit]) -> String {
+    let source_lang = match units.first() {
+        Some(first)
+            if units
+                .iter()
+                .all(|unit| unit.source_lang == first.source_lang) =>
+        {
+            first.source_lang.as_str()
+        }
+        _ => "*all*",
+    };
+
+    let mut xml =
+        String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<tmx version=\"1.4\">\n");
+    xml.push_str(&format!(
+        "  <header creationtool=\"Langra\" creationtoolversion=\"{}\" datatype=\"plaintext\" segtype=\"sentence\" adminlang=\"en\" srclang=\"{}\" o-tmf=\"Langra\"/>\n  <body>\n",
+        env!("CARGO_PKG_VERSION"),
+        escape_xml(source_lang)
+    ));
+    for unit in units {
+        xml.push_str("    <tu>\n");
+        for (lang, text) in [
+            (&unit.source_lang, &unit.source),
+            (&unit.target_lang, &unit.target),
+        ] {
+            xml.push_str(&format!(
+                "      <tuv xml:lang=\"{}\">\n        <seg>{}</seg>\n      </tuv>\n",
+                escape_xml(lang),
+                escape_xml(text)
+            ));
+        }
+        xml.push_str("    </tu>\n");
+    }
+    xml.push_str("  </body>\n</tmx>\n");
+    xml
+}
+
+fn pairs_of(units: &[MemoryUnit]) -> Vec<LanguagePair> {
+    let mut pairs: Vec<LanguagePair> = Vec::new();
+    for unit in units {
+        let pair = LanguagePair {
+            source: unit.source_lang.clone(),
+            target: unit.target_lang.clone(),
+        };
+        if !pairs.contains(&pair) {
+            pairs.push(pair);
+        }
+    }
+    pairs
+}
+
+#[tauri::command]
+pub fn import_translation_memory(
+    path: String,
+    source_lang: Option<String>,
+    target_lang: Option<String>,
+) -> Result<MemoryImportSummary, String> {
+    log_line!("[MEMORY] Importing TMX from {}", path);
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let default_pair = match (source_lang, target_lang) {
+        (Some(source), Some(target)) => Some(LanguagePair { source, target }),
+        _ => None,
+    };
+    let units = import_tmx(&content, default_pair.as_ref())
+        .map_err(|e| format!("Failed to import translation memory: {:#}", e))?;
+
+    let imported = units.len();
+    let pairs = pairs_of(&units);
+    with_memory(|memory| {
+        merge_units(memory, units);
+        persist(memory)
+    })?;
+
+    log_line!("[MEMORY] Imported {} translation units", imported);
+    Ok(MemoryImportSummary { imported, pairs })
+}
+
+#[tauri::command]
+pub fn export_translation_memory(
+    path: String,
+    source_lang: Option<String>,
+    target_lang: Option<String>,
+) -> Result<usize, String> {
+    let source_lang = source_lang.map(|lang| normalize_code(&lang));
+    let target_lang = target_lang.map(|lang| normalize_code(&lang));
+    let units: Vec<MemoryUnit> = with_memory(|units| {
+        units
+            .iter()
+            .filter(|unit| {
+                source_lang
+                    .as_ref()
+                    .is_none_or(|lang| *lang == unit.source_lang)
+            })
+            .filter(|unit| {
+                target_lang
+                    .as_ref()
+                    .is_none_or(|lang| *lang == unit.target_lang)
+            })
+            .cloned()
+            .collect()
+    });
+    log_line!(
+        "[MEMORY] Exporting {} translation units to {}",
+        units.len(),
+        path
+    );
+
+    fs::write(&path, export_tmx(&units)).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(units.len())
+}
+
+#[tauri::command]
+pub fn get_translation_memory_stats() -> MemoryStats {
+    with_memory(|units| MemoryStats {
+        units: units.len(),
+        pairs: pairs_of(units),
+    })
+}
+
+#[tauri::command]
+pub fn clear_translation_memory() -> Result<(), String> {
+    log_line!("[MEMORY] Clearing translation memory");
+    with_memory(|units| {
+        units.clear();
+        persist(units)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_TMX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<tmx version="1.4">
+  <header creationtool="Vendor" segtype="sentence" adminlang="en" srclang="en-US" datatype="plaintext" o-tmf="x"/>
+  <body>
+    <tu>
+      <tuv xml:lang="en-US"><seg>Click <bpt i="1">&lt;b&gt;</bpt>Save<ept i="1">&lt;/b&gt;</ept> to continue.</seg></tuv>
+      <tuv xml:lang="de-DE"><seg>Klicken Sie auf <bpt i="1">&lt;b&gt;</bpt>Speichern<ept i="1">&lt;/b&gt;</ept>, um fortzufahren.</seg></tuv>
+      <tuv xml:lang="fr-FR"><seg>Cliquez sur Enregistrer pour continuer.</seg></tuv>
+    </tu>
+    <tu>
+      <tuv xml:lang="en-US"><seg>Only one language.</seg></tuv>
+    </tu>
+  </body>
+</tmx>"#;
+
+    fn pair(source: &str, target: &str) -> LanguagePair {
+        LanguagePair {
+            source: source.to_string(),
+            target: target.to_string(),
+        }
+    }
+
+    #[test]
+    fn tmx_import_skips_inline_codes() {
+        let units = import_tmx(SAMPLE_TMX, None).unwrap();
+
+        assert_eq!(units.len(), 2);
+        assert_eq!(units[0].source, "Click Save to continue.");
+        assert_eq!(
+            units[0].target,
+            "Klicken Sie auf Speichern, um fortzufahren."
+        );
+        assert_eq!(
+            (units[1].source_lang.as_str(), units[1].target_lang.as_str()),
+            ("en", "fr")
+        );
+    }
+
+    #[test]
+    fn tmx_export_round_trips() {
+        let units = import_tmx(SAMPLE_TMX, Some(&pair("en", "de"))).unwrap();
+        let exported = export_tmx(&units);
+        let reimported = import_tmx(&exported, None).unwrap();
+
+        assert!(exported.contains("srclang=\"en\""));
+        assert_eq!(reimported.len(), 1);
+        assert_eq!(reimported[0].source, units[0].source);
+        assert_eq!(reimported[0].target, units[0].target);
+    }
+
+    #[test]
+    fn exact_match_wins_over_fuzzy() {
+        let units = import_tmx(SAMPLE_TMX, None).unwrap();
+
+        let exact = best_matches(&units, "Click  Save to continue.", &pair("en", "de"), 75);
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].score, 100);
+
+        let fuzzy = best_matches(&units, "Click Save to continue now.", &pair("en", "de"), 75);
+        assert_eq!(fuzzy.len(), 1);
+        assert!(fuzzy[0].score < 100);
+
+        assert!(best_matches(&units, "Something else entirely", &pair("en", "de"), 75).is_empty());
+    }
+}
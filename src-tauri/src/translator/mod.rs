@@ -1,7 +1,37 @@
-mod translate;
-mod enhance;
+mod alternatives;
+pub mod batch;
+pub mod cache;
 mod detect_language;
+pub mod diff;
+mod enhance;
+mod error;
+pub mod events;
+mod format;
+pub mod glossary;
+mod hashing;
+mod jobs;
+mod language_profiles;
+mod languages;
+mod local_detect;
+pub mod memory;
+pub mod protect;
+mod providers;
+mod segment;
+mod sse;
+pub mod stream_events;
+mod style;
+mod translate;
+pub mod usage;
 
-pub use translate::translate_stream;
-pub use enhance::{enhance_stream, enhance_stream_with_instruction};
+pub use alternatives::translate_alternatives;
 pub use detect_language::detect_language;
+pub use enhance::{enhance_stream, enhance_stream_with_instruction, enhance_with_corrections};
+pub use error::TranslatorError;
+pub use events::EventSink;
+pub use format::{detect_format, html_flavor_text, TextFormat};
+pub use jobs::{begin_job, cancel_active_job, emit_job_complete, emit_job_error};
+pub use languages::{resolve_language_pair, resolve_with_override, LanguagePair};
+pub use local_detect::{detect_local, LanguageCandidate};
+pub use providers::{provider_from_settings, requires_login};
+pub use style::{resolve_style, AppliedStyle, Formality};
+pub use translate::translate_stream;
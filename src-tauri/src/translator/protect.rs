@@ -0,0 +1,264 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::ops::Range;
+
+use super::error::TranslatorError;
+use crate::settings::load_settings;
+
+const TOKEN_OPEN: char = '⟦';
+const TOKEN_CLOSE: char = '⟧';
+
+static TOKEN: Lazy<Regex> = Lazy::new(|| Regex::new(r"⟦\d+⟧").expect("valid token pattern"));
+
+static BUILTIN_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    [
+        r"(?s)```.*?```",
+        r"`[^`\n]+`",
+        r#"\b(?:https?|ftp)://[^\s<>"'`]+[^\s<>"'`.,;:!?)\]]"#,
+        r"\bwww\.[^\s<>]+[^\s<>.,;:!?)\]]",
+        r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}\b",
+        r"\{\{[^{}\n]*\}\}",
+        r"\$\{[^{}\n]*\}",
+        r"\{[A-Za-z0-9_.:-]*\}",
+        r"%(?:\d+\$)?(?:\([A-Za-z0-9_]+\))?[-+0#]*\d*(?:\.\d+)?[sdifuxXeEgGcp@]",
+        r"\b[A-Za-z][A-Za-z0-9]*(?:_[A-Za-z0-9]+)+\b",
+        r"\b[a-z]+[A-Z][A-Za-z0-9]*\b",
+        r"\b[A-Za-z_][A-Za-z0-9_]*(?:\.[A-Za-z_][A-Za-z0-9_]*)*\(\)",
+    ]
+    .iter()
+    .map(|pattern| Regex::new(pattern).expect("valid builtin pattern"))
+    .collect()
+});
+
+#[derive(Debug, Clone, Default)]
+pub struct MaskedText {
+    pub text: String,
+    spans: Vec<String>,
+}
+
+fn token(index: usize) -> String {
+    format!("{}{}{}", TOKEN_OPEN, index, TOKEN_CLOSE)
+}
+
+pub fn token_ranges(text: &str) -> Vec<Range<usize>> {
+    TOKEN.find_iter(text).map(|found| found.range()).collect()
+}
+
+pub fn compile_user_patterns(patterns: &[String]) -> Result<Vec<Regex>, String> {
+    patterns
+        .iter()
+        .map(|pattern| pattern.trim())
+        .filter(|pattern| !pattern.is_empty())
+        .map(|pattern| {
+            Regex::new(pattern)
+                .map_err(|e| format!("Invalid protected pattern '{}': {}", pattern, e))
+        })
+        .collect()
+}
+
+pub fn mask(text: &str, patterns: &[Regex], protected: &[Range<usize>]) -> MaskedText {
+    let overlaps_protected = |start: usize, end: usize| {
+        protected
+            .iter()
+            .any(|range| start < range.end && range.start < end)
+    };
+    let mut ranges: Vec<(usize, usize)> = patterns
+        .iter()
+        .flat_map(|pattern| {
+            pattern
+                .find_iter(text)
+                .map(|found| (found.start(), found.end()))
+        })
+        .filter(|(start, end)| !overlaps_protected(*start, *end))
+        .chain(protected.iter().map(|range| (range.start, range.end)))
+        .filter(|(start, end)| start < end)
+        .collect();
+    ranges.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+
+    let mut masked = String::with_capacity(text.len());
+    let mut spans = Vec::new();
+    let mut cursor = 0;
+    for (start, end) in ranges {
+        if start < cursor {
+            continue;
+        }
+        masked.push_str(&text[cursor..start]);
+        masked.push_str(&token(spans.len()));
+        spans.push(text[start..end].to_string());
+        cursor = end;
+    }
+    masked.push_str(&text[cursor..]);
+
+    MaskedText {
+        text: masked,
+        spans,
+    }
+}
+
+pub fn mask_with_settings(text: &str, protected: &[Range<usize>]) -> MaskedText {
+    let settings = load_settings();
+    let mut patterns = Vec::new();
+    if settings.mask_protected_text {
+        patterns.extend(BUILTIN_PATTERNS.iter().cloned());
+        patterns.extend(
+            compile_user_patterns(&settings.protected_patterns).unwrap_or_else(|e| {
+                log_line!("[PROTECT] {}", e);
+                Vec::new()
+            }),
+        );
+    }
+
+    let masked = mask(text, &patterns, protected);
+    if masked.is_masked() {
+        log_line!("[PROTECT] Masked {} protected spans", masked.spans.len());
+    }
+    masked
+}
+
+impl MaskedText {
+    pub fn is_masked(&self) -> bool {
+        !self.spans.is_empty()
+    }
+
+    fn replace_tokens(&self, masked: &str) -> String {
+        let mut result = masked.to_string();
+        for (index, span) in self.spans.iter().enumerate().rev() {
+            result = result.replace(&token(index), span);
+        }
+        result
+    }
+
+    pub fn unmask_partial(&self, masked: &str) -> String {
+        if !self.is_masked() {
+            return masked.to_string();
+        }
+
+        let mut visible = masked;
+        if let Some(open) = masked.rfind(TOKEN_OPEN) {
+            if !masked[open..].contains(TOKEN_CLOSE) {
+                visible = &masked[..open];
+            }
+        }
+        self.replace_tokens(visible)
+    }
+
+    pub fn unmask(&self, masked: &str) -> Result<String, TranslatorError> {
+        let count = |index: usize| masked.matches(&token(index)).count();
+        let missing: Vec<&str> = self
+            .spans
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| count(*index) == 0)
+            .map(|(_, span)| span.as_str())
+            .collect();
+        let duplicated: Vec<&str> = self
+            .spans
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| count(*index) > 1)
+            .map(|(_, span)| span.as_str())
+            .collect();
+
+        if !missing.is_empty() {
+            return Err(TranslatorError::ValidationFailed(format!(
+                "Protected text was lost during translation: {}",
+                missing.join(", ")
+            )));
+        }
+        if !duplicated.is_empty() {
+            return Err(TranslatorError::ValidationFailed(format!(
+                "Protected text was duplicated during translation: {}",
+                duplicated.join(", ")
+            )));
+        }
+        Ok(self.replace_tokens(masked))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mask_builtin(text: &str) -> MaskedText {
+        mask(text, &BUILTIN_PATTERNS, &[])
+    }
+
+    fn masked_spans(text: &str) -> Vec<String> {
+        mask_builtin(text).spans
+    }
+
+    #[test]
+    fn code_spans_are_masked() {
+        assert_eq!(
+            masked_spans("Run `cargo build` first"),
+            vec!["`cargo build`"]
+        );
+        assert_eq!(
+            masked_spans("Example:\n```\nlet x = 1;\n```\ndone"),
+            vec!["```\nlet x = 1;\n```"]
+        );
+    }
+
+    #[test]
+    fn urls_and_emails_are_masked() {
+        assert_eq!(
+            masked_spans("See https://example.com/docs?a=1. Or mail dev@example.org, thanks"),
+            vec!["https://example.com/docs?a=1", "dev@example.org"]
+        );
+    }
+
+    #[test]
+    fn placeholders_are_masked() {
+        assert_eq!(
+            masked_spans("Hello {name}, you have %s messages in {{folder}}"),
+            vec!["{name}", "%s", "{{folder}}"]
+        );
+    }
+
+    #[test]
+    fn user_patterns_and_protected_ranges_are_masked() {
+        let patterns = compile_user_patterns(&["ACME-\\d+".to_string(), " ".to_string()]).unwrap();
+        let masked = mask("Fix ACME-42 in Langra", &patterns, &[0..3, 15..21]);
+        assert_eq!(masked.text, "⟦0⟧ ⟦1⟧ in ⟦2⟧");
+        assert!(compile_user_patterns(&["(".to_string()]).is_err());
+    }
+
+    #[test]
+    fn unmask_restores_every_span() {
+        let text = "Open `config.toml` at https://example.com for {user}";
+        let masked = mask_builtin(text);
+        assert_eq!(masked.text, "Open ⟦0⟧ at ⟦1⟧ for ⟦2⟧");
+        let translated = "Öffne ⟦0⟧ unter ⟦1⟧ für ⟦2⟧";
+        assert_eq!(
+            masked.unmask(translated).unwrap(),
+            "Öffne `config.toml` unter https://example.com für {user}"
+        );
+    }
+
+    #[test]
+    fn unmask_partial_hides_an_incomplete_token() {
+        let masked = mask_builtin("Use `npm ci` now");
+        assert_eq!(
+            masked.unmask_partial("Nutze ⟦0⟧ jetzt ⟦"),
+            "Nutze `npm ci` jetzt "
+        );
+    }
+
+    #[test]
+    fn missing_token_fails_validation() {
+        let masked = mask_builtin("Hello {name}");
+        assert!(matches!(
+            masked.unmask("Hallo"),
+            Err(TranslatorError::ValidationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn duplicated_token_fails_validation() {
+        let masked = mask_builtin("Hello {name}");
+        assert!(matches!(
+            masked.unmask("Hallo ⟦0⟧ ⟦0⟧"),
+            Err(TranslatorError::ValidationFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,80 @@
+use anyhow::Result;
+use futures_util::future::BoxFuture;
+
+use super::openai::ChatCompletionsClient;
+use super::{
+    CorrectionRequest, EnhanceRequest, ProposedCorrection, TextStream, TranslateRequest,
+    TranslationAlternative, TranslationProvider,
+};
+use crate::settings::ApiKeySettings;
+use crate::translator::TranslatorError;
+
+const AZURE_API_VERSION: &str = "2024-10-21";
+
+pub struct AzureOpenAiProvider {
+    chat: ChatCompletionsClient,
+}
+
+impl AzureOpenAiProvider {
+    pub fn from_settings(api_settings: &ApiKeySettings) -> Result<Self> {
+        let endpoint = api_settings.azure_endpoint.trim().trim_end_matches('/');
+        let api_key = api_settings.azure_api_key.trim();
+        let deployment = api_settings.azure_deployment.trim();
+
+        if endpoint.is_empty() || api_key.is_empty() || deployment.is_empty() {
+            return Err(TranslatorError::Unauthorized(
+                "Invalid Azure credentials: endpoint, API key and deployment are required"
+                    .to_string(),
+            )
+            .into());
+        }
+
+        let url = format!(
+            "{}/openai/deployments/{}/chat/completions?api-version={}",
+            endpoint, deployment, AZURE_API_VERSION
+        );
+
+        Ok(AzureOpenAiProvider {
+            chat: ChatCompletionsClient::new(
+                url,
+                ("api-key", api_key.to_string()),
+                None,
+                "azure",
+                "Azure OpenAI API",
+            )?,
+        })
+    }
+}
+
+impl TranslationProvider for AzureOpenAiProvider {
+    fn name(&self) -> &'static str {
+        "azure"
+    }
+
+    fn translate(&self, request: TranslateRequest) -> BoxFuture<'_, Result<TextStream>> {
+        self.chat.translate(request)
+    }
+
+    fn alternatives(
+        &self,
+        request: TranslateRequest,
+        count: usize,
+    ) -> BoxFuture<'_, Result<Vec<TranslationAlternative>>> {
+        self.chat.alternatives(request, count)
+    }
+
+    fn enhance(&self, request: EnhanceRequest) -> BoxFuture<'_, Result<TextStream>> {
+        self.chat.enhance(request)
+    }
+
+    fn corrections(
+        &self,
+        request: CorrectionRequest,
+    ) -> BoxFuture<'_, Result<Vec<ProposedCorrection>>> {
+        self.chat.corrections(request)
+    }
+
+    fn detect(&self, text: String) -> BoxFuture<'_, Result<TextStream>> {
+        self.chat.detect(text)
+    }
+}
@@ -0,0 +1,196 @@
+use anyhow::Result;
+use futures_util::future::{self, BoxFuture, FutureExt};
+use futures_util::stream::StreamExt;
+
+use super::{
+    ensure_success, missing_language, single_result, sse_data, CorrectionRequest, EnhanceRequest,
+    ProposedCorrection, StreamUpdate, TextStream, TranslateRequest, TranslationAlternative,
+    TranslationProvider,
+};
+use crate::backend_config::backend_endpoint;
+use crate::get_access_token;
+use crate::http::{self, Retry};
+use crate::translator::usage::{self, UsageMeter, UsageOperation};
+use crate::translator::TranslatorError;
+
+pub struct BackendProvider {
+    client: reqwest::Client,
+    token: String,
+}
+
+impl BackendProvider {
+    pub fn new() -> Result<Self> {
+        let token = get_access_token().map_err(TranslatorError::Unauthorized)?;
+        Ok(BackendProvider {
+            client: http::client()?,
+            token,
+        })
+    }
+
+    async fn post(
+        &self,
+        path: &str,
+        body: serde_json::Value,
+        retry: Retry,
+    ) -> Result<reqwest::Response> {
+        let request = self
+            .client
+            .post(backend_endpoint(path).map_err(TranslatorError::Configuration)?)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .json(&body);
+        let response = http::send(request, retry).await?;
+        ensure_success(response, "Backend API").await
+    }
+
+    fn parse_update(data: &str) -> Option<StreamUpdate> {
+        let json = serde_json::from_str::<serde_json::Value>(data).ok()?;
+        if let Some(delta) = json["delta"].as_str() {
+            return Some(StreamUpdate::Delta(delta.to_string()));
+        }
+        let content = json["content"].as_str()?.to_string();
+        match json["type"].as_str() {
+            Some("delta") => Some(StreamUpdate::Delta(content)),
+            _ => Some(StreamUpdate::Snapshot(content)),
+        }
+    }
+
+    fn content_stream(response: reqwest::Response) -> TextStream {
+        sse_data(response)
+            .filter_map(|data| {
+                let update = match data {
+                    Ok(data) => Self::parse_update(&data).map(Ok),
+                    Err(e) => Some(Err(e)),
+                };
+                future::ready(update)
+            })
+            .boxed()
+    }
+}
+
+impl TranslationProvider for BackendProvider {
+    fn name(&self) -> &'static str {
+        "backend"
+    }
+
+    fn translate(&self, request: TranslateRequest) -> BoxFuture<'_, Result<TextStream>> {
+        async move {
+            let meter =
+                UsageMeter::start(self.name(), "", UsageOperation::Translate, &request.text);
+            let body = serde_json::json!({
+                "text": request.text,
+                "source_lang": request.pair.source,
+                "target_lang": request.pair.target,
+                "style": request.style.style,
+                "formality": request.style.formality,
+                "register": request.style.register,
+                "glossary": request.glossary,
+                "references": request.references,
+                "context": request.context,
+                "notes": request.notes,
+            });
+            let response = self.post("/api/translate", body, Retry::Never).await?;
+            Ok(usage::metered(Self::content_stream(response), meter))
+        }
+        .boxed()
+    }
+
+    fn alternatives(
+        &self,
+        request: TranslateRequest,
+        count: usize,
+    ) -> BoxFuture<'_, Result<Vec<TranslationAlternative>>> {
+        async move {
+            let meter =
+                UsageMeter::start(self.name(), "", UsageOperation::Translate, &request.text);
+            let body = serde_json::json!({
+                "text": request.text,
+                "source_lang": request.pair.source,
+                "target_lang": request.pair.target,
+                "style": request.style.style,
+                "formality": request.style.formality,
+                "register": request.style.register,
+                "glossary": request.glossary,
+                "references": request.references,
+                "count": count,
+            });
+            let response = self
+                .post("/api/translate/alternatives", body, Retry::Never)
+                .await?;
+            let result: serde_json::Value = response.json().await?;
+            let alternatives: Vec<TranslationAlternative> =
+                serde_json::from_value(result["alternatives"].clone())?;
+            let output: Vec<&str> = alternatives
+                .iter()
+                .map(|alternative| alternative.text.as_str())
+                .collect();
+            meter.finish(&output.join("\n"));
+            Ok(alternatives)
+        }
+        .boxed()
+    }
+
+    fn enhance(&self, request: EnhanceRequest) -> BoxFuture<'_, Result<TextStream>> {
+        async move {
+            let meter = UsageMeter::start(self.name(), "", UsageOperation::Enhance, &request.text);
+            let mut body = serde_json::json!({
+                "text": request.text,
+                "language": request.language,
+                "style": request.style.style,
+                "formality": request.style.formality,
+                "register": request.style.register,
+            });
+            if let Some(instruction) = request.instruction {
+                body["instruction"] = serde_json::json!(instruction);
+            }
+            let response = self.post("/api/enhance", body, Retry::Never).await?;
+            Ok(usage::metered(Self::content_stream(response), meter))
+        }
+        .boxed()
+    }
+
+    fn corrections(
+        &self,
+        request: CorrectionRequest,
+    ) -> BoxFuture<'_, Result<Vec<ProposedCorrection>>> {
+        async move {
+            let meter = UsageMeter::start(self.name(), "", UsageOperation::Enhance, &request.text);
+            let body = serde_json::json!({
+                "text": request.text,
+                "language": request.language,
+                "explanation_language": request.explanation_language,
+                "style": request.style.style,
+                "formality": request.style.formality,
+                "register": request.style.register,
+            });
+            let response = self
+                .post("/api/enhance/corrections", body, Retry::Never)
+                .await?;
+            let result: serde_json::Value = response.json().await?;
+            meter.finish(&result["corrections"].to_string());
+            let corrections = serde_json::from_value(result["corrections"].clone())?;
+            Ok(corrections)
+        }
+        .boxed()
+    }
+
+    fn detect(&self, text: String) -> BoxFuture<'_, Result<TextStream>> {
+        async move {
+            let meter = UsageMeter::start(self.name(), "", UsageOperation::Detect, &text);
+            let response = self
+                .post(
+                    "/api/detect-language",
+                    serde_json::json!({ "text": text }),
+                    Retry::Idempotent,
+                )
+                .await?;
+            let result: serde_json::Value = response.json().await?;
+            let language = result["language"]
+                .as_str()
+                .ok_or_else(|| missing_language(self.name()))?
+                .to_string();
+            meter.finish(&language);
+            Ok(single_result(language))
+        }
+        .boxed()
+    }
+}
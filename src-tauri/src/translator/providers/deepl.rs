@@ -0,0 +1,408 @@
+use anyhow::Result;
+use futures_util::future::{self, BoxFuture, FutureExt};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use super::{
+    ensure_success, missing_language, single_result, EnhanceRequest, TextStream, TranslateRequest,
+    TranslationAlternative, TranslationProvider,
+};
+use crate::http::{self, Retry};
+use crate::settings::{get_config_file_path, load_settings, ApiKeySettings};
+use crate::translator::glossary;
+use crate::translator::hashing::stable_hash;
+use crate::translator::languages::{canonical_code, normalize_code};
+use crate::translator::usage::{UsageMeter, UsageOperation};
+use crate::translator::{AppliedStyle, Formality, LanguagePair, TranslatorError};
+
+const DEEPL_FREE_URL: &str = "https://api-free.deepl.com/v2";
+const DEEPL_PRO_URL: &str = "https://api.deepl.com/v2";
+
+static REMOTE_GLOSSARIES: Mutex<Option<Vec<RemoteGlossary>>> = Mutex::new(None);
+static GLOSSARY_SYNC: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RemoteGlossary {
+    account: String,
+    source_lang: String,
+    target_lang: String,
+    fingerprint: String,
+    glossary_id: String,
+}
+
+pub struct DeepLProvider {
+    client: reqwest::Client,
+    base_url: &'static str,
+    api_key: String,
+}
+
+fn deepl_target_code(language: &str) -> String {
+    let language = canonical_code(language);
+    match language.as_str() {
+        "en" => "EN-US".to_string(),
+        "pt" => "PT-PT".to_string(),
+        "zh" => "ZH-HANS".to_string(),
+        "zh-TW" | "zh-HK" | "zh-MO" => "ZH-HANT".to_string(),
+        other if other.starts_with("zh-Hant") => "ZH-HANT".to_string(),
+        other if other.starts_with("zh-") => "ZH-HANS".to_string(),
+        other => other.to_uppercase(),
+    }
+}
+
+fn get_remote_glossaries_path() -> std::path::PathBuf {
+    get_config_file_path("deepl_glossaries.json")
+}
+
+fn load_remote_glossaries() -> Vec<RemoteGlossary> {
+    let mut remote_lock = REMOTE_GLOSSARIES.lock();
+    if let Some(remote) = remote_lock.as_ref() {
+        return remote.clone();
+    }
+
+    let remote = match fs::read_to_string(get_remote_glossaries_path()) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log_line!("[DEEPL] Failed to parse stored glossary ids: {:?}", e);
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    };
+    *remote_lock = Some(remote.clone());
+    remote
+}
+
+fn save_remote_glossaries(remote: Vec<RemoteGlossary>) {
+    match serde_json::to_string_pretty(&remote) {
+        Ok(json) => {
+            if let Err(e) = fs::write(get_remote_glossaries_path(), json) {
+                log_line!("[DEEPL] Failed to write glossary ids: {:?}", e);
+            }
+        }
+        Err(e) => log_line!("[DEEPL] Failed to serialize glossary ids: {:?}", e),
+    }
+    *REMOTE_GLOSSARIES.lock() = Some(remote);
+}
+
+fn deepl_formality(style: &AppliedStyle) -> &'static str {
+    match style.formality {
+        Formality::Formal => "prefer_more",
+        Formality::Informal => "prefer_less",
+        Formality::Neutral => "default",
+    }
+}
+
+fn deepl_writing_style(style: &AppliedStyle) -> &'static str {
+    match style.formality {
+        Formality::Formal => "prefer_business",
+        Formality::Informal => "prefer_casual",
+        Formality::Neutral => "default",
+    }
+}
+
+impl DeepLProvider {
+    pub fn from_settings(api_settings: &ApiKeySettings) -> Result<Self> {
+        let api_key = api_settings.deepl_api_key.trim();
+        if api_key.is_empty() {
+            return Err(TranslatorError::Unauthorized(
+                "Invalid DeepL credentials: API key is missing".to_string(),
+            )
+            .into());
+        }
+
+        let base_url = if api_key.ends_with(":fx") {
+            DEEPL_FREE_URL
+        } else {
+            DEEPL_PRO_URL
+        };
+
+        Ok(DeepLProvider {
+            client: http::client()?,
+            base_url,
+            api_key: api_key.to_string(),
+        })
+    }
+
+    async fn post(
+        &self,
+        path: &str,
+        body: serde_json::Value,
+        retry: Retry,
+    ) -> Result<serde_json::Value> {
+        let request = self
+            .client
+            .post(format!("{}{}", self.base_url, path))
+            .header("Authorization", format!("DeepL-Auth-Key {}", self.api_key))
+            .json(&body);
+        let response = http::send(request, retry).await?;
+        let response = ensure_success(response, "DeepL API").await?;
+        Ok(response.json().await?)
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        let request = self
+            .client
+            .delete(format!("{}{}", self.base_url, path))
+            .header("Authorization", format!("DeepL-Auth-Key {}", self.api_key));
+        let response = http::send(request, Retry::Idempotent).await?;
+        if response.status() != reqwest::StatusCode::NOT_FOUND {
+            ensure_success(response, "DeepL API").await?;
+        }
+        Ok(())
+    }
+
+    fn account(&self) -> String {
+        stable_hash([self.api_key.as_str()])
+    }
+
+    async fn glossary_id(&self, pair: &LanguagePair) -> Option<String> {
+        let _sync = GLOSSARY_SYNC.lock().await;
+        let account = self.account();
+        let source = normalize_code(&pair.source);
+        let target = normalize_code(&pair.target);
+        let terms = glossary::terms_for(pair);
+        let fingerprint = glossary::fingerprint(&terms);
+
+        let mut remote = load_remote_glossaries();
+        let existing = remote.iter().position(|glossary| {
+            glossary.account == account
+                && glossary.source_lang == source
+                && glossary.target_lang == target
+        });
+        if let Some(index) = existing {
+            if Some(&remote[index].fingerprint) == fingerprint.as_ref() {
+                return Some(remote[index].glossary_id.clone());
+            }
+            let stale = remote.remove(index);
+            match self
+                .delete(&format!("/glossaries/{}", stale.glossary_id))
+                .await
+            {
+                Ok(()) => log_line!(
+                    "[DEEPL] Deleted outdated glossary {} for {} -> {}",
+                    stale.glossary_id,
+                    source,
+                    target
+                ),
+                Err(e) => log_line!(
+                    "[DEEPL] Failed to delete outdated glossary {}: {}",
+                    stale.glossary_id,
+                    e
+                ),
+            }
+            save_remote_glossaries(remote.clone());
+        }
+        let fingerprint = fingerprint?;
+
+        let entries: Vec<String> = terms
+            .iter()
+            .map(|term| {
+                format!(
+                    "{}\t{}",
+                    term.source.replace('\t', " "),
+                    term.target.replace('\t', " ")
+                )
+            })
+            .collect();
+        let body = serde_json::json!({
+            "name": format!("langra-{}-{}", source, target),
+            "source_lang": source,
+            "target_lang": target,
+            "entries": entries.join("\n"),
+            "entries_format": "tsv",
+        });
+
+        match self.post("/glossaries", body, Retry::Never).await {
+            Ok(result) => {
+                let id = result["glossary_id"].as_str()?.to_string();
+                log_line!(
+                    "[DEEPL] Created glossary {} with {} terms for {} -> {}",
+                    id,
+                    terms.len(),
+                    source,
+                    target
+                );
+                remote.push(RemoteGlossary {
+                    account,
+                    source_lang: source,
+                    target_lang: target,
+                    fingerprint,
+                    glossary_id: id.clone(),
+                });
+                save_remote_glossaries(remote);
+                Some(id)
+            }
+            Err(e) => {
+                log_line!(
+                    "[DEEPL] Failed to create glossary, continuing without it: {}",
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    fn translate_body(
+        text: String,
+        source: Option<&str>,
+        target: &str,
+        style: Option<&AppliedStyle>,
+    ) -> serde_json::Value {
+        let mut body = serde_json::json!({
+            "text": [text],
+            "target_lang": deepl_target_code(target),
+        });
+        if let Some(style) = style {
+            body["formality"] = serde_json::json!(deepl_formality(style));
+        }
+        if let Some(source) = source.filter(|source| !source.is_empty()) {
+            body["source_lang"] = serde_json::json!(source.to_uppercase());
+        }
+        body
+    }
+
+    async fn translate_text(
+        &self,
+        body: serde_json::Value,
+        retry: Retry,
+    ) -> Result<serde_json::Value> {
+        let result = self.post("/translate", body, retry).await?;
+        Ok(result["translations"][0].clone())
+    }
+}
+
+fn uses_glossary(request: &TranslateRequest) -> bool {
+    !request.pair.source.is_empty()
+        && !request.glossary.is_empty()
+        && load_settings().glossary_mode != "off"
+}
+
+impl TranslationProvider for DeepLProvider {
+    fn name(&self) -> &'static str {
+        "deepl"
+    }
+
+    fn translate(&self, request: TranslateRequest) -> BoxFuture<'_, Result<TextStream>> {
+        async move {
+            let meter =
+                UsageMeter::start(self.name(), "", UsageOperation::Translate, &request.text);
+            let glossary_id = match uses_glossary(&request) {
+                true => self.glossary_id(&request.pair).await,
+                false => None,
+            };
+            let mut body = Self::translate_body(
+                request.text,
+                Some(&request.pair.source),
+                &request.pair.target,
+                Some(&request.style),
+            );
+            if let Some(glossary_id) = glossary_id {
+                body["glossary_id"] = serde_json::json!(glossary_id);
+            }
+            let context: Vec<String> = request.notes.into_iter().chain(request.context).collect();
+            if !context.is_empty() {
+                body["context"] = serde_json::json!(context.join("\n"));
+            }
+            let translation = self.translate_text(body, Retry::Never).await?;
+            let text = translation["text"].as_str().unwrap_or_default().to_string();
+            meter.finish(&text);
+            Ok(single_result(text))
+        }
+        .boxed()
+    }
+
+    fn alternatives(
+        &self,
+        request: TranslateRequest,
+        count: usize,
+    ) -> BoxFuture<'_, Result<Vec<TranslationAlternative>>> {
+        async move {
+            let glossary_id = match uses_glossary(&request) {
+                true => self.glossary_id(&request.pair).await,
+                false => None,
+            };
+
+            let preferred = deepl_formality(&request.style);
+            let mut variants = vec![(preferred, "Matches your style setting")];
+            variants.extend(
+                [
+                    ("prefer_more", "More formal wording"),
+                    ("prefer_less", "More casual wording"),
+                    ("default", "Neutral wording"),
+                ]
+                .into_iter()
+                .filter(|(formality, _)| *formality != preferred),
+            );
+
+            let requests = variants.into_iter().take(count).map(|(formality, note)| {
+                let mut body = Self::translate_body(
+                    request.text.clone(),
+                    Some(&request.pair.source),
+                    &request.pair.target,
+                    None,
+                );
+                body["formality"] = serde_json::json!(formality);
+                if let Some(glossary_id) = &glossary_id {
+                    body["glossary_id"] = serde_json::json!(glossary_id);
+                }
+                let meter =
+                    UsageMeter::start(self.name(), "", UsageOperation::Translate, &request.text);
+                async move {
+                    let translation = self.translate_text(body, Retry::Never).await?;
+                    let text = translation["text"].as_str().unwrap_or_default().to_string();
+                    meter.finish(&text);
+                    Ok::<_, anyhow::Error>(TranslationAlternative {
+                        text,
+                        note: note.to_string(),
+                    })
+                }
+            });
+            future::try_join_all(requests).await
+        }
+        .boxed()
+    }
+
+    fn enhance(&self, request: EnhanceRequest) -> BoxFuture<'_, Result<TextStream>> {
+        async move {
+            if request.instruction.is_some() {
+                return Err(TranslatorError::InvalidInput(
+                    "DeepL does not support custom enhancement instructions".to_string(),
+                )
+                .into());
+            }
+            let meter = UsageMeter::start(self.name(), "", UsageOperation::Enhance, &request.text);
+            let body = serde_json::json!({
+                "text": [request.text],
+                "target_lang": deepl_target_code(&request.language),
+                "writing_style": deepl_writing_style(&request.style),
+            });
+            let result = self.post("/write/rephrase", body, Retry::Never).await?;
+            let text = result["improvements"][0]["text"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            meter.finish(&text);
+            Ok(single_result(text))
+        }
+        .boxed()
+    }
+
+    fn detect(&self, text: String) -> BoxFuture<'_, Result<TextStream>> {
+        async move {
+            let sample: String = text.chars().take(500).collect();
+            let meter = UsageMeter::start(self.name(), "", UsageOperation::Detect, &sample);
+            let translation = self
+                .translate_text(
+                    Self::translate_body(sample, None, "en", None),
+                    Retry::Idempotent,
+                )
+                .await?;
+            let language = translation["detected_source_language"]
+                .as_str()
+                .ok_or_else(|| missing_language(self.name()))?
+                .to_lowercase();
+            meter.finish(&language);
+            Ok(single_result(language))
+        }
+        .boxed()
+    }
+}
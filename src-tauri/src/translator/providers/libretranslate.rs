@@ -0,0 +1,154 @@
+use anyhow::Result;
+use futures_util::future::{BoxFuture, FutureExt};
+
+use super::{
+    ensure_success, missing_language, single_result, EnhanceRequest, TextStream, TranslateRequest,
+    TranslationAlternative, TranslationProvider,
+};
+use crate::http::{self, Retry};
+use crate::settings::ApiKeySettings;
+use crate::translator::languages::normalize_code;
+use crate::translator::usage::{UsageMeter, UsageOperation};
+use crate::translator::TranslatorError;
+
+pub struct LibreTranslateProvider {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl LibreTranslateProvider {
+    pub fn from_settings(api_settings: &ApiKeySettings) -> Result<Self> {
+        let base_url = api_settings.libretranslate_url.trim().trim_end_matches('/');
+        if base_url.is_empty() {
+            return Err(
+                TranslatorError::InvalidInput("LibreTranslate URL is missing".to_string()).into(),
+            );
+        }
+
+        let api_key = Some(api_settings.libretranslate_api_key.trim().to_string())
+            .filter(|key| !key.is_empty());
+
+        Ok(LibreTranslateProvider {
+            client: http::client()?,
+            base_url: base_url.to_string(),
+            api_key,
+        })
+    }
+
+    async fn post(
+        &self,
+        path: &str,
+        mut body: serde_json::Value,
+        retry: Retry,
+    ) -> Result<serde_json::Value> {
+        if let Some(api_key) = &self.api_key {
+            body["api_key"] = serde_json::json!(api_key);
+        }
+        let request = self
+            .client
+            .post(format!("{}{}", self.base_url, path))
+            .json(&body);
+        let response = http::send(request, retry).await?;
+        let response = ensure_success(response, "LibreTranslate API").await?;
+        Ok(response.json().await?)
+    }
+}
+
+impl TranslationProvider for LibreTranslateProvider {
+    fn name(&self) -> &'static str {
+        "libretranslate"
+    }
+
+    fn translate(&self, request: TranslateRequest) -> BoxFuture<'_, Result<TextStream>> {
+        async move {
+            let meter =
+                UsageMeter::start(self.name(), "", UsageOperation::Translate, &request.text);
+            let body = serde_json::json!({
+                "q": request.text,
+                "source": request.pair.source,
+                "target": normalize_code(&request.pair.target),
+                "format": "text",
+            });
+            let result = self.post("/translate", body, Retry::Never).await?;
+            let text = result["translatedText"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            meter.finish(&text);
+            Ok(single_result(text))
+        }
+        .boxed()
+    }
+
+    fn alternatives(
+        &self,
+        request: TranslateRequest,
+        count: usize,
+    ) -> BoxFuture<'_, Result<Vec<TranslationAlternative>>> {
+        async move {
+            let meter =
+                UsageMeter::start(self.name(), "", UsageOperation::Translate, &request.text);
+            let body = serde_json::json!({
+                "q": request.text,
+                "source": request.pair.source,
+                "target": normalize_code(&request.pair.target),
+                "format": "text",
+                "alternatives": count.saturating_sub(1),
+            });
+            let result = self.post("/translate", body, Retry::Never).await?;
+
+            let primary = result["translatedText"].as_str().unwrap_or_default();
+            let others = result["alternatives"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|alternative| alternative.as_str());
+            let alternatives: Vec<TranslationAlternative> =
+                std::iter::once((primary, "Primary translation"))
+                    .chain(others.map(|text| (text, "Alternative wording")))
+                    .map(|(text, note)| TranslationAlternative {
+                        text: text.to_string(),
+                        note: note.to_string(),
+                    })
+                    .collect();
+            let output: Vec<&str> = alternatives
+                .iter()
+                .map(|alternative| alternative.text.as_str())
+                .collect();
+            meter.finish(&output.join("\n"));
+            Ok(alternatives)
+        }
+        .boxed()
+    }
+
+    fn enhance(&self, _request: EnhanceRequest) -> BoxFuture<'_, Result<TextStream>> {
+        async move {
+            Err(TranslatorError::InvalidInput(
+                "LibreTranslate does not support text enhancement".to_string(),
+            )
+            .into())
+        }
+        .boxed()
+    }
+
+    fn detect(&self, text: String) -> BoxFuture<'_, Result<TextStream>> {
+        async move {
+            let meter = UsageMeter::start(self.name(), "", UsageOperation::Detect, &text);
+            let result = self
+                .post(
+                    "/detect",
+                    serde_json::json!({ "q": text }),
+                    Retry::Idempotent,
+                )
+                .await?;
+            let language = result[0]["language"]
+                .as_str()
+                .ok_or_else(|| missing_language(self.name()))?
+                .to_string();
+            meter.finish(&language);
+            Ok(single_result(language))
+        }
+        .boxed()
+    }
+}
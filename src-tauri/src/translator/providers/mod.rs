@@ -0,0 +1,344 @@
+mod azure;
+mod backend;
+mod deepl;
+mod libretranslate;
+mod openai;
+
+use anyhow::Result;
+use futures_util::future::{self, BoxFuture, FutureExt};
+use futures_util::stream::{self, BoxStream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::diff;
+use super::error::TranslatorError;
+use super::glossary::GlossaryTerm;
+use super::memory::MemoryMatch;
+use super::sse::sse_events;
+use super::style::AppliedStyle;
+use super::usage;
+use super::LanguagePair;
+use crate::http;
+use crate::settings::{load_api_keys, ApiKeySettings};
+
+pub use azure::AzureOpenAiProvider;
+pub use backend::BackendProvider;
+pub use deepl::DeepLProvider;
+pub use libretranslate::LibreTranslateProvider;
+pub use openai::OpenAiProvider;
+
+pub type TextStream = BoxStream<'static, Result<StreamUpdate>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamUpdate {
+    Delta(String),
+    Snapshot(String),
+}
+
+impl StreamUpdate {
+    pub fn apply_to(&self, text: &mut String) {
+        match self {
+            StreamUpdate::Delta(delta) => text.push_str(delta),
+            StreamUpdate::Snapshot(snapshot) => {
+                text.clear();
+                text.push_str(snapshot);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TranslateRequest {
+    pub text: String,
+    pub pair: LanguagePair,
+    pub style: AppliedStyle,
+    pub glossary: Vec<GlossaryTerm>,
+    pub references: Vec<MemoryMatch>,
+    pub context: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranslationAlternative {
+    pub text: String,
+    #[serde(default)]
+    pub note: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct EnhanceRequest {
+    pub text: String,
+    pub language: String,
+    pub instruction: Option<String>,
+    pub style: AppliedStyle,
+}
+
+#[derive(Debug, Clone)]
+pub struct CorrectionRequest {
+    pub text: String,
+    pub language: String,
+    pub explanation_language: String,
+    pub style: AppliedStyle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CorrectionCategory {
+    Spelling,
+    Grammar,
+    Punctuation,
+    #[serde(other)]
+    Style,
+}
+
+impl CorrectionCategory {
+    fn classify(original: &str, replacement: &str) -> Self {
+        let letters = |text: &str| -> String {
+            text.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect()
+        };
+        if letters(original) == letters(replacement) {
+            CorrectionCategory::Punctuation
+        } else if !original.trim().contains(char::is_whitespace)
+            && !replacement.trim().contains(char::is_whitespace)
+        {
+            CorrectionCategory::Spelling
+        } else {
+            CorrectionCategory::Grammar
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposedCorrection {
+    pub original: String,
+    pub replacement: String,
+    pub category: CorrectionCategory,
+    #[serde(default)]
+    pub explanation: String,
+}
+
+pub trait TranslationProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn translate(&self, request: TranslateRequest) -> BoxFuture<'_, Result<TextStream>>;
+
+    fn alternatives(
+        &self,
+        request: TranslateRequest,
+        _count: usize,
+    ) -> BoxFuture<'_, Result<Vec<TranslationAlternative>>> {
+        async move {
+            let text = collect_last(self.translate(request).await?).await?;
+            Ok(vec![TranslationAlternative {
+                text,
+                note: "Standard translation".to_string(),
+            }])
+        }
+        .boxed()
+    }
+
+    fn enhance(&self, request: EnhanceRequest) -> BoxFuture<'_, Result<TextStream>>;
+
+    fn corrections(
+        &self,
+        request: CorrectionRequest,
+    ) -> BoxFuture<'_, Result<Vec<ProposedCorrection>>> {
+        async move {
+            let original = request.text.clone();
+            let corrected = collect_last(
+                self.enhance(EnhanceRequest {
+                    text: request.text,
+                    language: request.language,
+                    instruction: None,
+                    style: request.style,
+                })
+                .await?,
+            )
+            .await?;
+            let corrections = diff::compute_diff(0, &original, &corrected)
+                .hunks
+                .into_iter()
+                .filter(|hunk| !hunk.original.trim().is_empty())
+                .map(|hunk| ProposedCorrection {
+                    category: CorrectionCategory::classify(&hunk.original, &hunk.enhanced),
+                    original: hunk.original,
+                    replacement: hunk.enhanced,
+                    explanation: String::new(),
+                })
+                .collect();
+            Ok(corrections)
+        }
+        .boxed()
+    }
+
+    fn detect(&self, text: String) -> BoxFuture<'_, Result<TextStream>>;
+}
+
+pub fn provider_from_settings(
+    api_settings: &ApiKeySettings,
+) -> Result<Box<dyn TranslationProvider>> {
+    let provider: Box<dyn TranslationProvider> = match api_settings.provider.as_str() {
+        "" | "backend" => Box::new(BackendProvider::new()?),
+        "openai" => Box::new(OpenAiProvider::from_settings(api_settings)?),
+        "azure" => Box::new(AzureOpenAiProvider::from_settings(api_settings)?),
+        "deepl" => Box::new(DeepLProvider::from_settings(api_settings)?),
+        "libretranslate" => Box::new(LibreTranslateProvider::from_settings(api_settings)?),
+        other => {
+            return Err(TranslatorError::InvalidInput(format!(
+                "Unknown translation provider: {}",
+                other
+            ))
+            .into())
+        }
+    };
+    Ok(provider)
+}
+
+pub fn cache_scope(api_settings: &ApiKeySettings) -> String {
+    match api_settings.provider.as_str() {
+        "" | "backend" => "backend".to_string(),
+        "openai" => OpenAiProvider::cache_scope(api_settings),
+        "azure" => format!(
+            "azure:{}:{}",
+            api_settings.azure_endpoint.trim().trim_end_matches('/'),
+            api_settings.azure_deployment.trim()
+        ),
+        "libretranslate" => format!(
+            "libretranslate:{}",
+            api_settings.libretranslate_url.trim().trim_end_matches('/')
+        ),
+        other => other.to_string(),
+    }
+}
+
+pub fn active_provider() -> Result<Box<dyn TranslationProvider>> {
+    usage::ensure_within_budget()?;
+    let provider = provider_from_settings(&load_api_keys())?;
+    log_line!("[PROVIDER] Using provider: {}", provider.name());
+    Ok(provider)
+}
+
+pub fn requires_login() -> bool {
+    matches!(load_api_keys().provider.as_str(), "" | "backend")
+}
+
+pub fn single_result(text: String) -> TextStream {
+    stream::once(future::ready(Ok(StreamUpdate::Snapshot(text)))).boxed()
+}
+
+pub fn missing_language(provider: &str) -> TranslatorError {
+    TranslatorError::BackendError {
+        status: None,
+        message: format!("{} did not return a detected language", provider),
+    }
+}
+
+pub fn parse_json_array<T: DeserializeOwned>(raw: &str, label: &str) -> Result<Vec<T>> {
+    let json = match (raw.find('['), raw.rfind(']')) {
+        (Some(start), Some(end)) if start < end => &raw[start..=end],
+        _ => raw,
+    };
+    serde_json::from_str(json).map_err(|e| {
+        TranslatorError::BackendError {
+            status: None,
+            message: format!("Invalid {} response: {}", label, e),
+        }
+        .into()
+    })
+}
+
+pub fn sse_data(response: reqwest::Response) -> BoxStream<'static, Result<String>> {
+    sse_events(response.bytes_stream())
+        .map(|event| event.map(|event| event.data))
+        .boxed()
+}
+
+pub async fn ensure_success(response: reqwest::Response, label: &str) -> Result<reqwest::Response> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status();
+    let retry_after = http::retry_after(response.headers());
+    let error_text = response.text().await.unwrap_or_default();
+    Err(TranslatorError::from_status(label, status, retry_after, &error_text).into())
+}
+
+pub async fn collect_last(mut stream: TextStream) -> Result<String> {
+    let mut result = String::new();
+    while let Some(update) = stream.next().await {
+        update?.apply_to(&mut result);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn collected(updates: Vec<StreamUpdate>) -> String {
+        collect_last(stream::iter(updates.into_iter().map(Ok)).boxed())
+            .await
+            .expect("text")
+    }
+
+    #[tokio::test]
+    async fn snapshots_replace_the_text() {
+        let text = collected(vec![
+            StreamUpdate::Snapshot("Hal".to_string()),
+            StreamUpdate::Snapshot("Hallo".to_string()),
+            StreamUpdate::Snapshot("Hallo Welt".to_string()),
+        ])
+        .await;
+
+        assert_eq!(text, "Hallo Welt");
+    }
+
+    #[tokio::test]
+    async fn deltas_are_appended() {
+        let text = collected(vec![
+            StreamUpdate::Delta("Hal".to_string()),
+            StreamUpdate::Delta("lo".to_string()),
+            StreamUpdate::Delta(" Welt".to_string()),
+        ])
+        .await;
+
+        assert_eq!(text, "Hallo Welt");
+    }
+
+    #[tokio::test]
+    async fn repeated_snapshot_is_not_mistaken_for_a_delta() {
+        let text = collected(vec![
+            StreamUpdate::Snapshot("a".to_string()),
+            StreamUpdate::Snapshot("a".to_string()),
+        ])
+        .await;
+
+        assert_eq!(text, "a");
+    }
+
+    #[tokio::test]
+    async fn repeated_delta_is_appended_again() {
+        let text = collected(vec![
+            StreamUpdate::Delta("a".to_string()),
+            StreamUpdate::Delta("a".to_string()),
+        ])
+        .await;
+
+        assert_eq!(text, "aa");
+    }
+
+    #[tokio::test]
+    async fn snapshot_after_deltas_replaces_them() {
+        let text = collected(vec![
+            StreamUpdate::Delta("Hallo ".to_string()),
+            StreamUpdate::Delta("Wlt".to_string()),
+            StreamUpdate::Snapshot("Hallo Welt".to_string()),
+        ])
+        .await;
+
+        assert_eq!(text, "Hallo Welt");
+    }
+}
@@ -0,0 +1,362 @@
+use anyhow::Result;
+use futures_util::future::{self, BoxFuture, FutureExt};
+use futures_util::stream::StreamExt;
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+use super::{
+    collect_last, ensure_success, parse_json_array, sse_data, CorrectionRequest, EnhanceRequest,
+    ProposedCorrection, StreamUpdate, TextStream, TranslateRequest, TranslationAlternative,
+    TranslationProvider,
+};
+use crate::http::{self, Retry};
+use crate::settings::ApiKeySettings;
+use crate::translator::usage::{UsageMeter, UsageOperation};
+use crate::translator::TranslatorError;
+
+const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+const DEFAULT_OPENAI_MODEL: &str = "gpt-4o-mini";
+
+pub struct ChatCompletionsClient {
+    client: reqwest::Client,
+    url: String,
+    auth_header: (&'static str, String),
+    model: Option<String>,
+    provider: &'static str,
+    label: &'static str,
+}
+
+impl ChatCompletionsClient {
+    pub fn new(
+        url: String,
+        auth_header: (&'static str, String),
+        model: Option<String>,
+        provider: &'static str,
+        label: &'static str,
+    ) -> Result<Self> {
+        Ok(ChatCompletionsClient {
+            client: http::client()?,
+            url,
+            auth_header,
+            model,
+            provider,
+            label,
+        })
+    }
+
+    pub async fn stream(
+        &self,
+        system_prompt: String,
+        user_text: String,
+        operation: UsageOperation,
+        retry: Retry,
+    ) -> Result<TextStream> {
+        let meter = UsageMeter::start_chat(
+            self.provider,
+            self.model.as_deref().unwrap_or_default(),
+            operation,
+            &system_prompt,
+            &user_text,
+        );
+        let mut body = serde_json::json!({
+            "stream": true,
+            "stream_options": { "include_usage": true },
+            "temperature": 0.2,
+            "messages": [
+                { "role": "system", "content": system_prompt },
+                { "role": "user", "content": user_text },
+            ],
+        });
+        if let Some(model) = &self.model {
+            body["model"] = serde_json::json!(model);
+        }
+
+        let request = self
+            .client
+            .post(&self.url)
+            .header(self.auth_header.0, &self.auth_header.1)
+            .json(&body);
+        let response = http::send(request, retry).await?;
+        let response = ensure_success(response, self.label).await?;
+
+        let meter = Arc::new(Mutex::new(meter));
+        let reporter = meter.clone();
+        let deltas = sse_data(response)
+            .filter_map(move |data| {
+                let delta = match data {
+                    Ok(data) => serde_json::from_str::<serde_json::Value>(&data)
+                        .ok()
+                        .and_then(|json| {
+                            if let Some(reported) = json["usage"].as_object() {
+                                reporter.lock().report(
+                                    json["model"].as_str(),
+                                    reported["prompt_tokens"].as_u64().unwrap_or_default(),
+                                    reported["completion_tokens"].as_u64().unwrap_or_default(),
+                                );
+                            }
+                            json["choices"][0]["delta"]["content"]
+                                .as_str()
+                                .map(|delta| StreamUpdate::Delta(delta.to_string()))
+                        })
+                        .map(Ok),
+                    Err(e) => Some(Err(e)),
+                };
+                future::ready(delta)
+            })
+            .boxed();
+
+        Ok(deltas
+            .map(move |item| {
+                if let Ok(update) = &item {
+                    meter.lock().observe_update(update);
+                }
+                item
+            })
+            .boxed())
+    }
+
+    pub fn translate(&self, request: TranslateRequest) -> BoxFuture<'_, Result<TextStream>> {
+        let prompt = translate_prompt(&request);
+        self.stream(
+            prompt,
+            request.text,
+            UsageOperation::Translate,
+            Retry::Never,
+        )
+        .boxed()
+    }
+
+    pub fn alternatives(
+        &self,
+        request: TranslateRequest,
+        count: usize,
+    ) -> BoxFuture<'_, Result<Vec<TranslationAlternative>>> {
+        let prompt = alternatives_prompt(&request, count);
+        async move {
+            let stream = self
+                .stream(
+                    prompt,
+                    request.text,
+                    UsageOperation::Translate,
+                    Retry::Never,
+                )
+                .await?;
+            parse_json_array(&collect_last(stream).await?, "alternatives")
+        }
+        .boxed()
+    }
+
+    pub fn enhance(&self, request: EnhanceRequest) -> BoxFuture<'_, Result<TextStream>> {
+        let prompt = enhance_prompt(&request);
+        self.stream(prompt, request.text, UsageOperation::Enhance, Retry::Never)
+            .boxed()
+    }
+
+    pub fn corrections(
+        &self,
+        request: CorrectionRequest,
+    ) -> BoxFuture<'_, Result<Vec<ProposedCorrection>>> {
+        let prompt = corrections_prompt(&request);
+        async move {
+            let stream = self
+                .stream(prompt, request.text, UsageOperation::Enhance, Retry::Never)
+                .await?;
+            parse_json_array(&collect_last(stream).await?, "corrections")
+        }
+        .boxed()
+    }
+
+    pub fn detect(&self, text: String) -> BoxFuture<'_, Result<TextStream>> {
+        let sample: String = text.chars().take(500).collect();
+        self.stream(
+            detect_prompt(),
+            sample,
+            UsageOperation::Detect,
+            Retry::Idempotent,
+        )
+        .boxed()
+    }
+}
+
+pub fn translate_prompt(request: &TranslateRequest) -> String {
+    translate_prompt_with(request, "Reply with the translation only.")
+}
+
+pub fn alternatives_prompt(request: &TranslateRequest, count: usize) -> String {
+    translate_prompt_with(
+        request,
+        &format!(
+            "Instead of a single translation, provide {} distinct alternatives, best first, that \
+             differ in approach, for example more literal, more idiomatic or shorter. Reply only \
+             with a JSON array of objects with the fields \"text\" (the translation) and \
+             \"note\" (a few words on how it differs).",
+            count
+        ),
+    )
+}
+
+fn translate_prompt_with(request: &TranslateRequest, reply: &str) -> String {
+    let mut prompt = format!(
+        "Translate the user's text from the language with ISO 639-1 code '{}' into the language \
+         with BCP 47 language tag '{}'. Preserve meaning, tone and formatting. {} Keep placeholder \
+         tokens such as ⟦0⟧ exactly as they are. {}",
+        request.pair.source,
+        request.pair.target,
+        reply,
+        request.style.prompt_instruction()
+    );
+    if !request.glossary.is_empty() {
+        let terms: Vec<String> = request
+            .glossary
+            .iter()
+            .map(|term| format!("\"{}\" -> \"{}\"", term.source, term.target))
+            .collect();
+        prompt.push_str(&format!(
+            " Always use these glossary translations: {}.",
+            terms.join("; ")
+        ));
+    }
+    if !request.references.is_empty() {
+        let references: Vec<String> = request
+            .references
+            .iter()
+            .map(|reference| format!("\"{}\" -> \"{}\"", reference.source, reference.target))
+            .collect();
+        prompt.push_str(&format!(
+            " Approved translations of similar texts, to follow for wording and terminology: {}.",
+            references.join("; ")
+        ));
+    }
+    if let Some(context) = &request.context {
+        prompt.push_str(&format!(
+            " The text continues a document whose preceding source passage was: \"{}\". \
+             Keep terminology consistent with it and do not translate or repeat it.",
+            context
+        ));
+    }
+    if let Some(notes) = &request.notes {
+        prompt.push_str(&format!(
+            " Notes for the translator, which must not be translated or included in the reply: {}",
+            notes
+        ));
+    }
+    prompt
+}
+
+pub fn enhance_prompt(request: &EnhanceRequest) -> String {
+    let mut prompt = format!(
+        "Correct spelling, grammar and punctuation in the user's text, which is written in the \
+         language with ISO 639-1 code '{}'. Keep the original language, meaning and formatting. \
+         Reply with the corrected text only. Keep placeholder tokens such as ⟦0⟧ exactly as \
+         they are. {}",
+        request.language,
+        request.style.prompt_instruction()
+    );
+    if let Some(instruction) = &request.instruction {
+        prompt.push_str(&format!(" Additional instruction: {}", instruction));
+    }
+    prompt
+}
+
+pub fn corrections_prompt(request: &CorrectionRequest) -> String {
+    format!(
+        "Find the spelling, grammar, punctuation and style mistakes in the user's text, which is \
+         written in the language with ISO 639-1 code '{}'. Reply only with a JSON array of \
+         objects in text order with the fields \"original\" (the exact erroneous passage copied \
+         from the text, as short as possible), \"replacement\" (the corrected passage), \
+         \"category\" (one of \"spelling\", \"grammar\", \"punctuation\", \"style\") and \
+         \"explanation\" (one short sentence written in the language with ISO 639-1 code '{}'). \
+         Reply with an empty array if the text is correct. Never change placeholder tokens such \
+         as ⟦0⟧. {}",
+        request.language,
+        request.explanation_language,
+        request.style.prompt_instruction()
+    )
+}
+
+pub fn detect_prompt() -> String {
+    "Identify the language of the user's text. Reply with its lowercase ISO 639-1 code only."
+        .to_string()
+}
+
+pub struct OpenAiProvider {
+    chat: ChatCompletionsClient,
+}
+
+impl OpenAiProvider {
+    pub fn cache_scope(api_settings: &ApiKeySettings) -> String {
+        let base_url = match api_settings.openai_base_url.trim() {
+            "" => DEFAULT_OPENAI_BASE_URL,
+            url => url.trim_end_matches('/'),
+        };
+        let model = match api_settings.openai_model.trim() {
+            "" => DEFAULT_OPENAI_MODEL,
+            model => model,
+        };
+        format!("openai:{}:{}", base_url, model)
+    }
+
+    pub fn from_settings(api_settings: &ApiKeySettings) -> Result<Self> {
+        if api_settings.openai_api_key.trim().is_empty() {
+            return Err(TranslatorError::Unauthorized(
+                "Invalid OpenAI credentials: API key is missing".to_string(),
+            )
+            .into());
+        }
+
+        let base_url = match api_settings.openai_base_url.trim() {
+            "" => DEFAULT_OPENAI_BASE_URL,
+            url => url,
+        };
+        let model = match api_settings.openai_model.trim() {
+            "" => DEFAULT_OPENAI_MODEL,
+            model => model,
+        };
+
+        Ok(OpenAiProvider {
+            chat: ChatCompletionsClient::new(
+                format!("{}/chat/completions", base_url.trim_end_matches('/')),
+                (
+                    "Authorization",
+                    format!("Bearer {}", api_settings.openai_api_key.trim()),
+                ),
+                Some(model.to_string()),
+                "openai",
+                "OpenAI API",
+            )?,
+        })
+    }
+}
+
+impl TranslationProvider for OpenAiProvider {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    fn translate(&self, request: TranslateRequest) -> BoxFuture<'_, Result<TextStream>> {
+        self.chat.translate(request)
+    }
+
+    fn alternatives(
+        &self,
+        request: TranslateRequest,
+        count: usize,
+    ) -> BoxFuture<'_, Result<Vec<TranslationAlternative>>> {
+        self.chat.alternatives(request, count)
+    }
+
+    fn enhance(&self, request: EnhanceRequest) -> BoxFuture<'_, Result<TextStream>> {
+        self.chat.enhance(request)
+    }
+
+    fn corrections(
+        &self,
+        request: CorrectionRequest,
+    ) -> BoxFuture<'_, Result<Vec<ProposedCorrection>>> {
+        self.chat.corrections(request)
+    }
+
+    fn detect(&self, text: String) -> BoxFuture<'_, Result<TextStream>> {
+        self.chat.detect(text)
+    }
+}
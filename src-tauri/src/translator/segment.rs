@@ -0,0 +1,108 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+static PARAGRAPH_BREAK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\n[ \t]*\n\s*").expect("valid paragraph break pattern"));
+
+#[derive(Debug, Clone)]
+pub(super) struct Chunk {
+    pub range: Range<usize>,
+    pub protected: Vec<Range<usize>>,
+}
+
+fn char_count(text: &str) -> usize {
+    text.chars().count()
+}
+
+fn trim_range(text: &str, range: Range<usize>) -> Option<Range<usize>> {
+    let slice = &text[range.clone()];
+    let start = range.start + (slice.len() - slice.trim_start().len());
+    let end = range.end - (slice.len() - slice.trim_end().len());
+    (start < end).then_some(start..end)
+}
+
+fn paragraphs(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut cursor = 0;
+    for found in PARAGRAPH_BREAK.find_iter(text) {
+        ranges.extend(trim_range(text, cursor..found.start()));
+        cursor = found.end();
+    }
+    ranges.extend(trim_range(text, cursor..text.len()));
+    ranges
+}
+
+fn sentences(text: &str, range: Range<usize>) -> Vec<Range<usize>> {
+    text[range.clone()]
+        .split_sentence_bound_indices()
+        .filter_map(|(offset, sentence)| {
+            let start = range.start + offset;
+            trim_range(text, start..start + sentence.len())
+        })
+        .collect()
+}
+
+fn hard_split(text: &str, range: Range<usize>, max_chars: usize) -> Vec<Range<usize>> {
+    let mut pieces = Vec::new();
+    let mut start = range.start;
+    while char_count(&text[start..range.end]) > max_chars {
+        let window_end = text[start..range.end]
+            .char_indices()
+            .nth(max_chars)
+            .map(|(offset, _)| start + offset)
+            .unwrap_or(range.end);
+        let cut = text[start..window_end]
+            .rfind(char::is_whitespace)
+            .map(|offset| start + offset)
+            .filter(|cut| *cut > start)
+            .unwrap_or(window_end);
+        pieces.extend(trim_range(text, start..cut));
+        start = cut;
+    }
+    pieces.extend(trim_range(text, start..range.end));
+    pieces
+}
+
+pub(super) fn split(text: &str, max_chars: usize) -> Vec<Chunk> {
+    let max_chars = max_chars.max(1);
+    if char_count(text) <= max_chars {
+        return vec![Chunk {
+            range: 0..text.len(),
+            protected: Vec::new(),
+        }];
+    }
+
+    let fits = |range: &Range<usize>| char_count(&text[range.clone()]) <= max_chars;
+    let mut units = Vec::new();
+    for paragraph in paragraphs(text) {
+        if fits(&paragraph) {
+            units.push(paragraph);
+            continue;
+        }
+        for sentence in sentences(text, paragraph) {
+            if fits(&sentence) {
+                units.push(sentence);
+            } else {
+                units.extend(hard_split(text, sentence, max_chars));
+            }
+        }
+    }
+
+    let mut chunks: Vec<Range<usize>> = Vec::new();
+    for unit in units {
+        match chunks.last_mut() {
+            Some(last) if fits(&(last.start..unit.end)) => last.end = unit.end,
+            _ => chunks.push(unit),
+        }
+    }
+
+    chunks
+        .into_iter()
+        .map(|range| Chunk {
+            range,
+            protected: Vec::new(),
+        })
+        .collect()
+}
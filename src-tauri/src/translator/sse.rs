@@ -0,0 +1,421 @@
+use futures_util::stream::{self, BoxStream, Stream, StreamExt};
+use std::collections::VecDeque;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent {
+    pub event: String,
+    pub data: String,
+    pub id: Option<String>,
+    pub retry: Option<u64>,
+}
+
+impl SseEvent {
+    pub fn is_done(&self) -> bool {
+        self.data.trim() == "[DONE]"
+    }
+
+    pub fn server_error(&self) -> Option<SseServerError> {
+        if self.event == "error" {
+            return Some(SseServerError::from_payload(&self.data));
+        }
+
+        let json = serde_json::from_str::<serde_json::Value>(&self.data).ok()?;
+        if json.get("error").is_none_or(serde_json::Value::is_null) {
+            return None;
+        }
+        Some(SseServerError::from_payload(&self.data))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseServerError {
+    pub code: Option<String>,
+    pub status: Option<u16>,
+    pub message: String,
+}
+
+impl SseServerError {
+    pub fn from_payload(data: &str) -> Self {
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(data) else {
+            return SseServerError {
+                code: None,
+                status: None,
+                message: data.trim().to_string(),
+            };
+        };
+
+        let error = json.get("error").unwrap_or(&json);
+        let message = error
+            .get("message")
+            .and_then(|message| message.as_str())
+            .or_else(|| error.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| error.to_string());
+        let code = error
+            .get("code")
+            .or_else(|| error.get("type"))
+            .and_then(|code| match code {
+                serde_json::Value::String(code) => Some(code.clone()),
+                serde_json::Value::Number(code) => Some(code.to_string()),
+                _ => None,
+            });
+
+        let status = ["status", "status_code"]
+            .iter()
+            .find_map(|field| error.get(field).or_else(|| json.get(field)))
+            .and_then(|status| match status {
+                serde_json::Value::Number(status) => status.as_u64(),
+                serde_json::Value::String(status) => status.trim().parse().ok(),
+                _ => None,
+            })
+            .and_then(|status| u16::try_from(status).ok());
+
+        SseServerError {
+            code,
+            status,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for SseServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.code {
+            Some(code) => write!(f, "Server stream error ({}): {}", code, self.message),
+            None => write!(f, "Server stream error: {}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for SseServerError {}
+
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buffer: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+    id: Option<String>,
+    retry: Option<u64>,
+    last_event_id: Option<String>,
+}
+
+impl SseDecoder {
+    pub fn new() -> Self {
+        SseDecoder::default()
+    }
+
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut events = Vec::new();
+        let mut line_start = 0;
+        let mut index = 0;
+
+        while index < self.buffer.len() {
+            let terminator_len = match self.buffer[index] {
+                b'\n' => 1,
+                b'\r' => match self.buffer.get(index + 1) {
+                    Some(b'\n') => 2,
+                    Some(_) => 1,
+                    None => break,
+                },
+                _ => {
+                    index += 1;
+                    continue;
+                }
+            };
+
+            let line = String::from_utf8_lossy(&self.buffer[line_start..index]).into_owned();
+            if let Some(event) = self.process_line(&line) {
+                events.push(event);
+            }
+            index += terminator_len;
+            line_start = index;
+        }
+
+        self.buffer.drain(..line_start);
+        events
+    }
+
+    pub fn finish(&mut self) -> Option<SseEvent> {
+        let remaining = std::mem::take(&mut self.buffer);
+        let remaining = String::from_utf8_lossy(&remaining);
+        let line = remaining.trim_end_matches('\r');
+        if !line.is_empty() {
+            if let Some(event) = self.process_line(line) {
+                return Some(event);
+            }
+        }
+        self.dispatch()
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+
+        match field {
+            "data" => self.data.push(value.to_string()),
+            "event" => self.event = Some(value.to_string()),
+            "id" if !value.contains('\0') => self.id = Some(value.to_string()),
+            "retry" => self.retry = value.parse().ok().or(self.retry),
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        if let Some(id) = self.id.take() {
+            self.last_event_id = Some(id);
+        }
+
+        let event = self.event.take();
+        let retry = self.retry.take();
+        if self.data.is_empty() {
+            return None;
+        }
+
+        Some(SseEvent {
+            event: event
+                .filter(|event| !event.is_empty())
+                .unwrap_or_else(|| "message".to_string()),
+            data: std::mem::take(&mut self.data).join("\n"),
+            id: self.last_event_id.clone(),
+            retry,
+        })
+    }
+}
+
+struct SseStreamState<B, E> {
+    bytes: BoxStream<'static, Result<B, E>>,
+    decoder: SseDecoder,
+    pending: VecDeque<SseEvent>,
+    finished: bool,
+}
+
+pub fn sse_events<S, B, E>(bytes: S) -> BoxStream<'static, anyhow::Result<SseEvent>>
+where
+    S: Stream<Item = Result<B, E>> + Send + 'static,
+    B: AsRef<[u8]> + Send + 'static,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let state = SseStreamState {
+        bytes: bytes.boxed(),
+        decoder: SseDecoder::new(),
+        pending: VecDeque::new(),
+        finished: false,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(event) = state.pending.pop_front() {
+                if event.is_done() {
+                    return None;
+                }
+                if let Some(error) = event.server_error() {
+                    state.pending.clear();
+                    state.finished = true;
+                    return Some((Err(anyhow::Error::new(error)), state));
+                }
+                return Some((Ok(event), state));
+            }
+
+            if state.finished {
+                return None;
+            }
+
+            match state.bytes.next().await {
+                Some(Ok(chunk)) => {
+                    let events = state.decoder.push(chunk.as_ref());
+                    state.pending.extend(events);
+                }
+                Some(Err(e)) => {
+                    state.finished = true;
+                    return Some((Err(e.into()), state));
+                }
+                None => {
+                    state.finished = true;
+                    state.pending.extend(state.decoder.finish());
+                }
+            }
+        }
+    })
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_in_chunks(input: &[u8], chunk_size: usize) -> Vec<SseEvent> {
+        let mut decoder = SseDecoder::new();
+        let mut events = Vec::new();
+        for chunk in input.chunks(chunk_size) {
+            events.extend(decoder.push(chunk));
+        }
+        events.extend(decoder.finish());
+        events
+    }
+
+    fn decode_at_every_split(input: &[u8]) -> Vec<SseEvent> {
+        let expected = decode_in_chunks(input, input.len());
+        for split in 1..input.len() {
+            let mut decoder = SseDecoder::new();
+            let mut events = decoder.push(&input[..split]);
+            events.extend(decoder.push(&input[split..]));
+            events.extend(decoder.finish());
+            assert_eq!(events, expected, "split at byte {}", split);
+        }
+        assert_eq!(decode_in_chunks(input, 1), expected);
+        expected
+    }
+
+    #[test]
+    fn data_line_split_across_chunks() {
+        let input = b"data: {\"content\":\"Hallo Welt\"}\n\ndata: [DONE]\n\n";
+        let events = decode_at_every_split(input);
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event, "message");
+        assert_eq!(events[0].data, "{\"content\":\"Hallo Welt\"}");
+        assert!(events[1].is_done());
+    }
+
+    #[test]
+    fn multibyte_characters_split_across_chunks() {
+        let input = "data: {\"content\":\"Grüße, 世界 👋\"}\n\n".as_bytes();
+        let events = decode_at_every_split(input);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "{\"content\":\"Grüße, 世界 👋\"}");
+    }
+
+    #[test]
+    fn crlf_and_cr_line_endings() {
+        let input = b"data: first\r\n\r\ndata: second\r\rdata: third\n\n";
+        let events = decode_at_every_split(input);
+
+        let data: Vec<&str> = events.iter().map(|event| event.data.as_str()).collect();
+        assert_eq!(data, vec!["first", "second", "third"]);
+    }
+
+    #[test]
+    fn multi_line_data_event_and_id_fields() {
+        let input =
+            b": keep-alive\nid: 7\nevent: delta\ndata: line one\ndata:line two\nretry: 3000\n\n";
+        let events = decode_at_every_split(input);
+
+        assert_eq!(
+            events,
+            vec![SseEvent {
+                event: "delta".to_string(),
+                data: "line one\nline two".to_string(),
+                id: Some("7".to_string()),
+                retry: Some(3000),
+            }]
+        );
+    }
+
+    #[test]
+    fn id_persists_for_following_events() {
+        let mut decoder = SseDecoder::new();
+        let events = decoder.push(b"id: 42\ndata: a\n\ndata: b\n\n");
+
+        assert_eq!(events[0].id.as_deref(), Some("42"));
+        assert_eq!(events[1].id.as_deref(), Some("42"));
+    }
+
+    #[test]
+    fn event_without_data_is_not_dispatched() {
+        let events = decode_at_every_split(b"event: ping\n\nid: 1\n\n");
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn trailing_event_without_blank_line_is_flushed() {
+        let events = decode_at_every_split(b"data: {\"content\":\"tail\"}");
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "{\"content\":\"tail\"}");
+    }
+
+    #[test]
+    fn error_event_is_typed() {
+        let input = b"event: error\ndata: {\"error\":{\"message\":\"Quota exceeded\",\"code\":\"quota\"}}\n\n";
+        let events = decode_at_every_split(input);
+
+        assert_eq!(
+            events[0].server_error(),
+            Some(SseServerError {
+                code: Some("quota".to_string()),
+                status: None,
+                message: "Quota exceeded".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn error_status_is_read_from_the_payload() {
+        let events = decode_at_every_split(
+            b"event: error\ndata: {\"error\":{\"message\":\"Slow down\",\"status\":429}}\n\n",
+        );
+
+        let error = events[0].server_error().expect("error payload");
+        assert_eq!(error.status, Some(429));
+        assert_eq!(error.code, None);
+    }
+
+    #[test]
+    fn error_payload_in_message_event_is_typed() {
+        let events = decode_at_every_split(b"data: {\"error\":\"Unauthorized\"}\n\n");
+
+        let error = events[0].server_error().expect("error payload");
+        assert_eq!(error.message, "Unauthorized");
+        assert_eq!(error.code, None);
+    }
+
+    fn byte_chunks(input: &'static [u8], size: usize) -> Vec<Result<Vec<u8>, std::io::Error>> {
+        input.chunks(size).map(|chunk| Ok(chunk.to_vec())).collect()
+    }
+
+    #[tokio::test]
+    async fn stream_stops_at_done_marker() {
+        let input = b"data: one\n\ndata: [DONE]\n\ndata: ignored\n\n";
+        let events: Vec<String> = sse_events(stream::iter(byte_chunks(input, 3)))
+            .map(|event| event.expect("event").data)
+            .collect()
+            .await;
+
+        assert_eq!(events, vec!["one".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn stream_surfaces_server_error_and_ends() {
+        let input = b"data: partial\n\nevent: error\ndata: {\"error\":{\"message\":\"boom\"}}\n\ndata: after\n\n";
+        let results: Vec<anyhow::Result<SseEvent>> =
+            sse_events(stream::iter(byte_chunks(input, 5)))
+                .collect()
+                .await;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().expect("event").data, "partial");
+        let error = results[1].as_ref().expect_err("server error");
+        let server_error = error.downcast_ref::<SseServerError>().expect("typed error");
+        assert_eq!(server_error.message, "boom");
+    }
+
+    #[test]
+    fn regular_payload_is_not_an_error() {
+        let events = decode_at_every_split(b"data: {\"content\":\"ok\",\"error\":null}\n\n");
+        assert_eq!(events[0].server_error(), None);
+    }
+}
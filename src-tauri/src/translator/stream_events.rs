@@ -0,0 +1,198 @@
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use super::events::EventSink;
+
+pub const STREAM_PROTOCOL_VERSION: u32 = 1;
+
+static LAST_SNAPSHOT: Mutex<Option<StreamSnapshot>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamKind {
+    Translation,
+    Enhancement,
+}
+
+impl StreamKind {
+    fn event(self) -> &'static str {
+        match self {
+            StreamKind::Translation => "translation-delta",
+            StreamKind::Enhancement => "enhancement-delta",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StreamDelta {
+    pub version: u32,
+    pub job_id: u64,
+    pub seq: u64,
+    pub offset: usize,
+    pub delta: String,
+    #[serde(rename = "final")]
+    pub is_final: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StreamSnapshot {
+    pub version: u32,
+    pub job_id: u64,
+    pub seq: u64,
+    pub text: String,
+    #[serde(rename = "final")]
+    pub is_final: bool,
+}
+
+fn common_prefix(previous: &str, next: &str) -> (usize, usize) {
+    let mut bytes = 0;
+    let mut utf16 = 0;
+    for (a, b) in previous.chars().zip(next.chars()) {
+        if a != b {
+            break;
+        }
+        bytes += a.len_utf8();
+        utf16 += a.len_utf16();
+    }
+    (bytes, utf16)
+}
+
+#[derive(Debug, Default)]
+struct DeltaState {
+    seq: u64,
+    sent: String,
+}
+
+impl DeltaState {
+    fn next(&mut self, job_id: u64, text: &str, is_final: bool) -> Option<StreamDelta> {
+        if !is_final && text == self.sent {
+            return None;
+        }
+
+        let (bytes, offset) = common_prefix(&self.sent, text);
+        self.sent.truncate(bytes);
+        self.sent.push_str(&text[bytes..]);
+        self.seq += 1;
+
+        Some(StreamDelta {
+            version: STREAM_PROTOCOL_VERSION,
+            job_id,
+            seq: self.seq,
+            offset,
+            delta: text[bytes..].to_string(),
+            is_final,
+        })
+    }
+}
+
+pub struct DeltaEmitter<'a> {
+    events: &'a dyn EventSink,
+    kind: StreamKind,
+    job_id: u64,
+    state: DeltaState,
+}
+
+impl<'a> DeltaEmitter<'a> {
+    pub fn new(events: &'a dyn EventSink, job_id: u64, kind: StreamKind) -> Self {
+        DeltaEmitter {
+            events,
+            kind,
+            job_id,
+            state: DeltaState::default(),
+        }
+    }
+
+    pub fn update(&mut self, text: &str) {
+        self.send(text, false);
+    }
+
+    pub fn finish(&mut self, text: &str) {
+        self.send(text, true);
+    }
+
+    fn send(&mut self, text: &str, is_final: bool) {
+        let Some(delta) = self.state.next(self.job_id, text, is_final) else {
+            return;
+        };
+
+        {
+            let mut snapshot = LAST_SNAPSHOT.lock();
+            if snapshot
+                .as_ref()
+                .is_none_or(|last| last.job_id <= self.job_id)
+            {
+                *snapshot = Some(StreamSnapshot {
+                    version: STREAM_PROTOCOL_VERSION,
+                    job_id: self.job_id,
+                    seq: delta.seq,
+                    text: self.state.sent.clone(),
+                    is_final,
+                });
+            }
+        }
+
+        self.events.emit(self.kind.event(), delta);
+    }
+}
+
+#[tauri::command]
+pub fn get_stream_snapshot(job_id: u64) -> Option<StreamSnapshot> {
+    LAST_SNAPSHOT
+        .lock()
+        .clone()
+        .filter(|snapshot| snapshot.job_id == job_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replay(deltas: &[StreamDelta]) -> String {
+        let mut text: Vec<u16> = Vec::new();
+        for delta in deltas {
+            text.truncate(delta.offset);
+            text.extend(delta.delta.encode_utf16());
+        }
+        String::from_utf16(&text).unwrap()
+    }
+
+    #[test]
+    fn appended_text_is_sent_as_delta() {
+        let mut state = DeltaState::default();
+        let first = state.next(7, "Hallo", false).unwrap();
+        let second = state.next(7, "Hallo Welt", false).unwrap();
+
+        assert_eq!(
+            (first.seq, first.offset, first.delta.as_str()),
+            (1, 0, "Hallo")
+        );
+        assert_eq!(
+            (second.seq, second.offset, second.delta.as_str()),
+            (2, 5, " Welt")
+        );
+        assert_eq!(second.job_id, 7);
+    }
+
+    #[test]
+    fn unchanged_text_is_skipped_until_final() {
+        let mut state = DeltaState::default();
+        state.next(1, "abc", false).unwrap();
+        assert!(state.next(1, "abc", false).is_none());
+
+        let last = state.next(1, "abc", true).unwrap();
+        assert!(last.is_final);
+        assert_eq!((last.seq, last.offset, last.delta.as_str()), (2, 3, ""));
+    }
+
+    #[test]
+    fn rewritten_text_uses_utf16_offsets() {
+        let mut state = DeltaState::default();
+        let deltas = vec![
+            state.next(1, "👋 Grüße", false).unwrap(),
+            state.next(1, "👋 Grüße, Welt", false).unwrap(),
+            state.next(1, "👋 Grüsse, Welt!", true).unwrap(),
+        ];
+
+        assert_eq!(deltas[2].offset, 6);
+        assert_eq!(replay(&deltas), "👋 Grüsse, Welt!");
+    }
+}
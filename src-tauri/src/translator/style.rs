@@ -0,0 +1,105 @@
+use serde::Serialize;
+
+use super::languages::normalize_code;
+use crate::settings::Settings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Formality {
+    Formal,
+    Neutral,
+    Informal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AppliedStyle {
+    pub language: String,
+    pub style: String,
+    pub formality: Formality,
+    pub register: Option<String>,
+}
+
+fn formality_for(style: &str) -> Formality {
+    match style {
+        "formal" => Formality::Formal,
+        "casual" => Formality::Informal,
+        _ => Formality::Neutral,
+    }
+}
+
+fn register_for(language: &str, formality: Formality) -> Option<&'static str> {
+    let (formal, informal) = match language {
+        "de" => ("Sie", "du"),
+        "fr" => ("vous", "tu"),
+        "es" => ("usted", "tú"),
+        "it" => ("Lei", "tu"),
+        "pt" => ("o senhor / a senhora", "você"),
+        "nl" => ("u", "jij"),
+        "pl" => ("Pan / Pani", "ty"),
+        "ru" => ("вы", "ты"),
+        "uk" => ("ви", "ти"),
+        "cs" => ("vy", "ty"),
+        "sv" => ("ni", "du"),
+        "tr" => ("siz", "sen"),
+        "ja" => ("敬語", "タメ口"),
+        "ko" => ("존댓말", "반말"),
+        _ => return None,
+    };
+    match formality {
+        Formality::Formal => Some(formal),
+        Formality::Informal => Some(informal),
+        Formality::Neutral => None,
+    }
+}
+
+pub fn resolve_style(language: &str, settings: &Settings) -> AppliedStyle {
+    let language = normalize_code(language);
+    let override_rule = settings
+        .language_styles
+        .iter()
+        .find(|rule| normalize_code(&rule.language) == language);
+
+    let style = override_rule
+        .map(|rule| rule.style.clone())
+        .unwrap_or_else(|| settings.style.clone());
+    let formality = formality_for(&style);
+    let register = override_rule
+        .map(|rule| rule.register.trim())
+        .filter(|register| !register.is_empty())
+        .map(str::to_string)
+        .or_else(|| register_for(&language, formality).map(str::to_string));
+
+    AppliedStyle {
+        language,
+        style,
+        formality,
+        register,
+    }
+}
+
+impl AppliedStyle {
+    pub fn cache_tag(&self) -> String {
+        format!(
+            "{}/{:?}/{}",
+            self.style,
+            self.formality,
+            self.register.as_deref().unwrap_or_default()
+        )
+    }
+
+    pub fn prompt_instruction(&self) -> String {
+        let tone = match self.style.as_str() {
+            "formal" => "formal and professional",
+            "casual" => "casual and relaxed",
+            _ => "friendly and natural",
+        };
+        let mut instruction = format!("Use a {} tone.", tone);
+        if let Some(register) = &self.register {
+            instruction.push_str(&format!(
+                " Address the reader using the \"{}\" form of address.",
+                register
+            ));
+        }
+        instruction
+    }
+}
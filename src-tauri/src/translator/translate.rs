@@ -1,56 +1,409 @@
 use anyhow::Result;
-use tauri::{AppHandle, Emitter};
-use crate::get_access_token;
-
-pub async fn translate_stream(text: &str, source_lang: &str, app: &AppHandle) -> Result<String> {
-    println!("[TRANSLATOR] Starting translation...");
-    println!("[TRANSLATOR] Source language: {}", source_lang);
-
-    let token = get_access_token().map_err(|e| anyhow::anyhow!(e))?;
-    let target_lang = if source_lang == "de" { "en" } else { "de" };
-
-    println!("[TRANSLATOR] Using backend API");
-
-    let client = reqwest::Client::new();
-    let response = client
-        .post("https://white-bush-0ea25dc03.3.azurestaticapps.net/api/translate")
-        .header("Authorization", format!("Bearer {}", token))
-        .json(&serde_json::json!({
-            "text": text,
-            "source_lang": source_lang,
-            "target_lang": target_lang,
-        }))
-        .send()
-        .await?;
+use futures_util::{stream, StreamExt, TryStreamExt};
+use parking_lot::Mutex;
+use std::future::Future;
+use std::ops::Range;
 
-    if !response.status().is_success() {
-        let error_text = response.text().await?;
-        anyhow::bail!("Backend API error: {}", error_text);
-    }
+use super::cache::{self, CacheKey};
+use super::events::EventSink;
+use super::format::{self, TextFormat};
+use super::glossary::{self, GlossaryViolation};
+use super::hashing::stable_hash;
+use super::memory;
+use super::protect::{self, MaskedText};
+use super::providers::{active_provider, TranslateRequest};
+use super::segment::{self, Chunk};
+use super::stream_events::{DeltaEmitter, StreamKind};
+use super::{AppliedStyle, LanguagePair};
+use crate::settings::load_settings;
 
-    let mut result = String::new();
-    let mut stream = response.bytes_stream();
+const CONTEXT_MAX_CHARS: usize = 600;
 
-    use futures_util::StreamExt;
-    while let Some(chunk) = stream.next().await {
-        let chunk = chunk?;
-        let chunk_str = String::from_utf8_lossy(&chunk);
+pub(super) struct SegmentTranslation {
+    pub text: String,
+    pub violations: Vec<GlossaryViolation>,
+}
 
-        for line in chunk_str.lines() {
-            if line.starts_with("data: ") {
-                let data = &line[6..];
-                if data == "[DONE]" {
-                    break;
-                }
-                if let Ok(json) = serde_json::from_str::<serde_json::Value>(data) {
-                    if let Some(content) = json["content"].as_str() {
-                        result = content.to_string();
-                        let _ = app.emit("translation-partial", result.clone());
-                    }
+struct ChunkProgress<'a> {
+    text: &'a str,
+    chunks: &'a [Chunk],
+    partials: Vec<String>,
+    outputs: Vec<Option<String>>,
+    emitter: DeltaEmitter<'a>,
+}
+
+impl ChunkProgress<'_> {
+    fn render(&self) -> String {
+        let mut output = String::with_capacity(self.text.len());
+        let mut cursor = 0;
+        for (index, chunk) in self.chunks.iter().enumerate() {
+            output.push_str(&self.text[cursor..chunk.range.start]);
+            match &self.outputs[index] {
+                Some(translated) => output.push_str(translated),
+                None => {
+                    output.push_str(&self.partials[index]);
+                    return output;
                 }
             }
+            cursor = chunk.range.end;
         }
+        output.push_str(&self.text[cursor..]);
+        output
+    }
+
+    fn publish(&mut self) {
+        let rendered = self.render();
+        self.emitter.update(&rendered);
+    }
+}
+
+fn context_tail(text: &str) -> String {
+    let skip = text.chars().count().saturating_sub(CONTEXT_MAX_CHARS);
+    text.chars().skip(skip).collect()
+}
+
+fn context_groups(count: usize, parallel: usize, continuous: bool) -> Vec<Range<usize>> {
+    let size = match continuous {
+        true => count.div_ceil(parallel.max(1)).max(1),
+        false => 1,
+    };
+    (0..count)
+        .step_by(size)
+        .map(|start| start..(start + size).min(count))
+        .collect()
+}
+
+pub(super) async fn translate_pipelined<F, Fut>(
+    sources: &[&str],
+    parallel: usize,
+    continuous: bool,
+    translate: F,
+) -> Result<Vec<SegmentTranslation>>
+where
+    F: Fn(usize, Option<String>) -> Fut,
+    Fut: Future<Output = Result<SegmentTranslation>>,
+{
+    let translate = &translate;
+    let tasks: Vec<_> = context_groups(sources.len(), parallel, continuous)
+        .into_iter()
+        .map(|group| async move {
+            let mut context = match continuous {
+                true => group
+                    .start
+                    .checked_sub(1)
+                    .map(|previous| context_tail(sources[previous])),
+                false => None,
+            };
+            let mut translations = Vec::with_capacity(group.len());
+            for index in group {
+                let translation = translate(index, context.take()).await?;
+                if continuous {
+                    context = Some(context_tail(&translation.text));
+                }
+                translations.push(translation);
+            }
+            Ok::<_, anyhow::Error>(translations)
+        })
+        .collect();
+    let groups: Vec<Vec<SegmentTranslation>> = stream::iter(tasks)
+        .buffered(parallel.max(1))
+        .try_collect()
+        .await?;
+    Ok(groups.into_iter().flatten().collect())
+}
+
+pub(super) async fn translate_segment<F>(
+    source: &str,
+    masked: &MaskedText,
+    pair: &LanguagePair,
+    style: &AppliedStyle,
+    context: Option<String>,
+    notes: Option<String>,
+    mut on_partial: F,
+) -> Result<SegmentTranslation>
+where
+    F: FnMut(&str) + Send,
+{
+    let glossary_mode = load_settings().glossary_mode;
+    let glossary_terms = match glossary_mode.as_str() {
+        "off" => Vec::new(),
+        _ => glossary::matching_terms(source, pair),
+    };
+    if !glossary_terms.is_empty() {
+        log_line!(
+            "[TRANSLATOR] Applying {} glossary terms",
+            glossary_terms.len()
+        );
+    }
+
+    let references = memory::lookup(source, pair);
+    if let Some(exact) = references
+        .first()
+        .filter(|reference| reference.score == 100)
+    {
+        log_line!("[TRANSLATOR] Reusing exact translation memory match");
+        on_partial(&exact.target);
+        return Ok(SegmentTranslation {
+            text: exact.target.clone(),
+            violations: Vec::new(),
+        });
+    }
+
+    let mut mode = match glossary::fingerprint(&glossary_terms) {
+        Some(fingerprint) => format!("translate:glossary:{}", fingerprint),
+        None => "translate".to_string(),
+    };
+    if let Some(fingerprint) = memory::fingerprint(&references) {
+        mode.push_str(&format!(":memory:{}", fingerprint));
+    }
+    if let Some(notes) = &notes {
+        mode.push_str(&format!(":notes:{}", stable_hash([notes.as_str()])));
+    }
+    let cache_key = CacheKey::new(
+        source,
+        &pair.source,
+        &pair.target,
+        &mode,
+        &style.cache_tag(),
+    );
+    if let Some(cached) = cache::lookup(&cache_key) {
+        on_partial(&cached);
+        return Ok(SegmentTranslation {
+            text: cached,
+            violations: Vec::new(),
+        });
     }
 
+    let provider = active_provider()?;
+    let mut stream = provider
+        .translate(TranslateRequest {
+            text: masked.text.clone(),
+            pair: pair.clone(),
+            style: style.clone(),
+            glossary: glossary_terms.clone(),
+            references,
+            context,
+            notes,
+        })
+        .await?;
+
+    let mut raw = String::new();
+    while let Some(update) = stream.next().await {
+        update?.apply_to(&mut raw);
+        on_partial(&masked.unmask_partial(&raw));
+    }
+
+    let mut violations = Vec::new();
+    let mut enforced = false;
+    if !glossary_terms.is_empty() {
+        let (checked, found) = glossary::enforce(&raw, &glossary_terms, glossary_mode == "fix");
+        enforced = checked != raw;
+        raw = checked;
+        violations = found;
+    }
+    let result = masked.unmask(&raw)?;
+    if masked.is_masked() || enforced {
+        on_partial(&result);
+    }
+
+    cache::store(cache_key, &result);
+    Ok(SegmentTranslation {
+        text: result,
+        violations,
+    })
+}
+
+fn emit_glossary_violations(violations: &[GlossaryViolation], events: &dyn EventSink) {
+    if !violations.is_empty() {
+        log_line!(
+            "[TRANSLATOR] Glossary check found {} violations",
+            violations.len()
+        );
+        events.emit("glossary-check", violations);
+    }
+}
+
+async fn translate_chunks(
+    text: &str,
+    chunks: &[Chunk],
+    pair: &LanguagePair,
+    style: &AppliedStyle,
+    job_id: u64,
+    events: &dyn EventSink,
+) -> Result<String> {
+    let parallel = load_settings().max_parallel_requests.max(1);
+    if chunks.len() > 1 {
+        log_line!(
+            "[TRANSLATOR] Translating {} chunks with up to {} parallel requests",
+            chunks.len(),
+            parallel
+        );
+    }
+
+    let progress = Mutex::new(ChunkProgress {
+        text,
+        chunks,
+        partials: vec![String::new(); chunks.len()],
+        outputs: vec![None; chunks.len()],
+        emitter: DeltaEmitter::new(events, job_id, StreamKind::Translation),
+    });
+
+    let sources: Vec<&str> = chunks
+        .iter()
+        .map(|chunk| &text[chunk.range.clone()])
+        .collect();
+    let progress = &progress;
+    let translations = translate_pipelined(&sources, parallel, true, |index, context| {
+        let source = sources[index];
+        let chunk = &chunks[index];
+        async move {
+            let masked = protect::mask_with_settings(source, &chunk.protected);
+            let translation =
+                translate_segment(source, &masked, pair, style, context, None, |partial| {
+                    let mut progress = progress.lock();
+                    progress.partials[index] = partial.to_string();
+                    progress.publish();
+                })
+                .await?;
+
+            let mut progress = progress.lock();
+            progress.outputs[index] = Some(translation.text.clone());
+            progress.publish();
+            Ok(translation)
+        }
+    })
+    .await?;
+
+    let violations: Vec<GlossaryViolation> = translations
+        .into_iter()
+        .flat_map(|translation| translation.violations)
+        .collect();
+    emit_glossary_violations(&violations, events);
+
+    let mut progress = progress.lock();
+    let result = progress.render();
+    progress.emitter.finish(&result);
+    Ok(result)
+}
+
+pub async fn translate_stream(
+    text: &str,
+    pair: &LanguagePair,
+    style: &AppliedStyle,
+    job_id: u64,
+    events: &dyn EventSink,
+) -> Result<String> {
+    log_line!("[TRANSLATOR] Starting translation...");
+    log_line!(
+        "[TRANSLATOR] Language pair: {} -> {}",
+        pair.source,
+        pair.target
+    );
+
+    let text_format = format::detect_format(text);
+    let chunks = match text_format {
+        TextFormat::Plain => segment::split(text, load_settings().chunk_max_chars),
+        _ => {
+            let chunks = format::document_chunks(text, text_format);
+            log_line!(
+                "[FORMAT] Translating {:?} document with {} blocks",
+                text_format,
+                chunks.len()
+            );
+            chunks
+        }
+    };
+
+    let result = translate_chunks(text, &chunks, pair, style, job_id, events).await?;
+    memory::remember_pending(job_id, text, pair);
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translated(index: usize) -> SegmentTranslation {
+        SegmentTranslation {
+            text: format!("translated {}", index),
+            violations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn continuous_chunks_are_split_into_contiguous_groups() {
+        assert_eq!(context_groups(5, 2, true), vec![0..3, 3..5]);
+        assert_eq!(context_groups(3, 8, true), vec![0..1, 1..2, 2..3]);
+        assert_eq!(context_groups(3, 1, false), vec![0..1, 1..2, 2..3]);
+        assert!(context_groups(0, 4, true).is_empty());
+    }
+
+    #[tokio::test]
+    async fn each_chunk_gets_the_previous_translated_output_as_context() {
+        let sources = ["one", "two", "three", "four"];
+        let seen = Mutex::new(Vec::new());
+        let seen_ref = &seen;
+        let translations = translate_pipelined(&sources, 2, true, |index, context| async move {
+            seen_ref.lock().push((index, context));
+            Ok(translated(index))
+        })
+        .await
+        .unwrap();
+
+        let texts: Vec<String> = translations.into_iter().map(|t| t.text).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "translated 0",
+                "translated 1",
+                "translated 2",
+                "translated 3"
+            ]
+        );
+        let mut seen = seen.into_inner();
+        seen.sort_by_key(|(index, _)| *index);
+        assert_eq!(
+            seen,
+            vec![
+                (0, None),
+                (1, Some("translated 0".to_string())),
+                (2, Some("two".to_string())),
+                (3, Some("translated 2".to_string())),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn a_chunk_starts_only_after_the_previous_one_finished() {
+        let sources = ["a", "b", "c"];
+        let finished = Mutex::new(Vec::new());
+        let finished_ref = &finished;
+        translate_pipelined(&sources, 1, true, |index, _| async move {
+            assert_eq!(*finished_ref.lock(), (0..index).collect::<Vec<_>>());
+            tokio::task::yield_now().await;
+            finished_ref.lock().push(index);
+            Ok(translated(index))
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn independent_segments_get_no_context() {
+        let sources = ["a", "b"];
+        translate_pipelined(&sources, 2, false, |index, context| async move {
+            assert_eq!(context, None);
+            Ok(translated(index))
+        })
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn context_keeps_the_end_of_long_chunks() {
+        let long = "a".repeat(CONTEXT_MAX_CHARS) + "tail";
+        let context = context_tail(&long);
+        assert_eq!(context.chars().count(), CONTEXT_MAX_CHARS);
+        assert!(context.ends_with("tail"));
+    }
+}
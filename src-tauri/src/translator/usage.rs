@@ -0,0 +1,487 @@
+use chrono::{Local, TimeZone};
+use futures_util::StreamExt;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::error::TranslatorError;
+use super::providers::{StreamUpdate, TextStream};
+use crate::settings::load_settings;
+
+const CHAT_MESSAGE_OVERHEAD: u64 = 11;
+
+const MODEL_PRICES: &[(&str, f64, f64)] = &[
+    ("gpt-4.1-nano", 0.10, 0.40),
+    ("gpt-4.1-mini", 0.40, 1.60),
+    ("gpt-4.1", 2.00, 8.00),
+    ("gpt-4o-mini", 0.15, 0.60),
+    ("gpt-4o", 2.50, 10.00),
+    ("gpt-4-turbo", 10.00, 30.00),
+    ("gpt-4", 30.00, 60.00),
+    ("gpt-35-turbo", 0.50, 1.50),
+    ("gpt-3.5-turbo", 0.50, 1.50),
+    ("o4-mini", 1.10, 4.40),
+    ("o3-mini", 1.10, 4.40),
+];
+
+static RECORDS: Mutex<Option<Vec<UsageRecord>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageOperation {
+    Translate,
+    Enhance,
+    Detect,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub timestamp: u64,
+    pub provider: String,
+    pub model: String,
+    pub operation: UsageOperation,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub estimated: bool,
+    pub latency_ms: u64,
+    pub cost_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageAggregate {
+    pub period: String,
+    pub requests: u64,
+    pub estimated_requests: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub cost_usd: f64,
+    pub average_latency_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetStatus {
+    pub period: String,
+    pub limit_usd: f64,
+    pub spent_usd: f64,
+    pub exceeded: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageSummary {
+    pub daily: Vec<UsageAggregate>,
+    pub monthly: Vec<UsageAggregate>,
+    pub budget: Option<BudgetStatus>,
+}
+
+pub struct UsageMeter {
+    provider: &'static str,
+    model: String,
+    operation: UsageOperation,
+    prompt_estimate: u64,
+    reported: Option<(u64, u64)>,
+    output: String,
+    responded: bool,
+    started: Instant,
+}
+
+impl UsageMeter {
+    pub fn start(
+        provider: &'static str,
+        model: &str,
+        operation: UsageOperation,
+        prompt: &str,
+    ) -> Self {
+        UsageMeter {
+            provider,
+            model: model.to_string(),
+            operation,
+            prompt_estimate: estimate_tokens(prompt),
+            reported: None,
+            output: String::new(),
+            responded: false,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn start_chat(
+        provider: &'static str,
+        model: &str,
+        operation: UsageOperation,
+        system_prompt: &str,
+        user_text: &str,
+    ) -> Self {
+        let mut meter = UsageMeter::start(provider, model, operation, system_prompt);
+        meter.prompt_estimate += estimate_tokens(user_text) + CHAT_MESSAGE_OVERHEAD;
+        meter
+    }
+
+    pub fn report(&mut self, model: Option<&str>, prompt_tokens: u64, completion_tokens: u64) {
+        if let Some(model) = model.filter(|model| !model.is_empty()) {
+            self.model = model.to_string();
+        }
+        self.reported = Some((prompt_tokens, completion_tokens));
+        self.responded = true;
+    }
+
+    pub fn observe(&mut self, output: &str) {
+        self.responded = true;
+        self.output.clear();
+        self.output.push_str(output);
+    }
+
+    pub fn observe_update(&mut self, update: &StreamUpdate) {
+        self.responded = true;
+        update.apply_to(&mut self.output);
+    }
+
+    pub fn finish(mut self, output: &str) {
+        self.observe(output);
+    }
+}
+
+impl UsageMeter {
+    fn take_record(&mut self) -> Option<UsageRecord> {
+        if !std::mem::take(&mut self.responded) {
+            log_line!(
+                "[USAGE] {} {:?} via {} failed before a response, not recorded",
+                self.model,
+                self.operation,
+                self.provider
+            );
+            return None;
+        }
+        let (prompt_tokens, completion_tokens, estimated) = match self.reported {
+            Some((prompt, completion)) => (prompt, completion, false),
+            None => (self.prompt_estimate, estimate_tokens(&self.output), true),
+        };
+        Some(UsageRecord {
+            timestamp: now_secs(),
+            provider: self.provider.to_string(),
+            model: self.model.clone(),
+            operation: self.operation,
+            prompt_tokens,
+            completion_tokens,
+            estimated,
+            latency_ms: self.started.elapsed().as_millis() as u64,
+            cost_usd: estimate_cost(&self.model, prompt_tokens, completion_tokens),
+        })
+    }
+}
+
+impl Drop for UsageMeter {
+    fn drop(&mut self) {
+        if let Some(usage) = self.take_record() {
+            record(usage);
+        }
+    }
+}
+
+pub fn metered(stream: TextStream, mut meter: UsageMeter) -> TextStream {
+    stream
+        .map(move |item| {
+            if let Ok(update) = &item {
+                meter.observe_update(update);
+            }
+            item
+        })
+        .boxed()
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF
+    )
+}
+
+pub fn estimate_tokens(text: &str) -> u64 {
+    text.split_word_bounds()
+        .map(|segment| {
+            let chars = segment.chars().count() as u64;
+            if segment.trim().is_empty() {
+                0
+            } else if segment.chars().any(is_cjk) {
+                chars
+            } else if segment.chars().any(char::is_alphanumeric) {
+                chars.div_ceil(4).max(1)
+            } else {
+                chars
+            }
+        })
+        .sum()
+}
+
+fn estimate_cost(model: &str, prompt_tokens: u64, completion_tokens: u64) -> f64 {
+    let model = model.to_lowercase();
+    MODEL_PRICES
+        .iter()
+        .filter(|(prefix, _, _)| model.starts_with(prefix))
+        .max_by_key(|(prefix, _, _)| prefix.len())
+        .map(|(_, input, output)| {
+            (prompt_tokens as f64 * input + completion_tokens as f64 * output) / 1_000_000.0
+        })
+        .unwrap_or(0.0)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+fn local_key(timestamp: u64, format: &str) -> String {
+    Local
+        .timestamp_opt(timestamp as i64, 0)
+        .single()
+        .map(|time| time.format(format).to_string())
+        .unwrap_or_default()
+}
+
+fn day_key(timestamp: u64) -> String {
+    local_key(timestamp, "%Y-%m-%d")
+}
+
+fn month_key(timestamp: u64) -> String {
+    local_key(timestamp, "%Y-%m")
+}
+
+fn period_key(period: &str, timestamp: u64) -> String {
+    match period {
+        "daily" => day_key(timestamp),
+        _ => month_key(timestamp),
+    }
+}
+
+fn get_usage_path() -> Option<PathBuf> {
+    let app_dir = dirs::data_dir()?.join("langra");
+    fs::create_dir_all(&app_dir).ok()?;
+    Some(app_dir.join("usage.jsonl"))
+}
+
+fn load_records() -> Vec<UsageRecord> {
+    let Some(path) = get_usage_path() else {
+        return Vec::new();
+    };
+    let content = fs::read_to_string(path).unwrap_or_default();
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn with_records<T>(f: impl FnOnce(&mut Vec<UsageRecord>) -> T) -> T {
+    let mut records_lock = RECORDS.lock();
+    let records = records_lock.get_or_insert_with(load_records);
+    f(records)
+}
+
+fn append_to_disk(record: &UsageRecord) -> Result<(), String> {
+    let path = get_usage_path().ok_or("Could not resolve the data directory")?;
+    let line = serde_json::to_string(record)
+        .map_err(|e| format!("Failed to serialize usage record: {}", e))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open usage log: {}", e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("Failed to write usage log: {}", e))
+}
+
+fn record(record: UsageRecord) {
+    log_line!(
+        "[USAGE] {} {:?} via {}: {} prompt + {} completion tokens{}, {}ms, ${:.6}",
+        record.model,
+        record.operation,
+        record.provider,
+        record.prompt_tokens,
+        record.completion_tokens,
+        if record.estimated { " (estimated)" } else { "" },
+        record.latency_ms,
+        record.cost_usd
+    );
+    if let Err(e) = append_to_disk(&record) {
+        log_line!("[USAGE] {}", e);
+    }
+    with_records(|records| records.push(record));
+}
+
+fn aggregate(
+    records: &[UsageRecord],
+    key: impl Fn(u64) -> String,
+    limit: usize,
+) -> Vec<UsageAggregate> {
+    let mut periods: BTreeMap<String, (UsageAggregate, u64)> = BTreeMap::new();
+    for record in records {
+        let (entry, latency) = periods.entry(key(record.timestamp)).or_default();
+        entry.requests += 1;
+        entry.estimated_requests += u64::from(record.estimated);
+        entry.prompt_tokens += record.prompt_tokens;
+        entry.completion_tokens += record.completion_tokens;
+        entry.cost_usd += record.cost_usd;
+        *latency += record.latency_ms;
+    }
+
+    periods
+        .into_iter()
+        .rev()
+        .take(limit)
+        .map(|(period, (aggregate, latency))| UsageAggregate {
+            period,
+            average_latency_ms: latency / aggregate.requests.max(1),
+            ..aggregate
+        })
+        .collect()
+}
+
+fn budget_status(records: &[UsageRecord], now: u64) -> Option<BudgetStatus> {
+    let settings = load_settings();
+    if settings.budget_limit_usd <= 0.0 {
+        return None;
+    }
+    let period = settings.budget_period;
+    let current = period_key(&period, now);
+    let spent_usd: f64 = records
+        .iter()
+        .filter(|record| period_key(&period, record.timestamp) == current)
+        .map(|record| record.cost_usd)
+        .sum();
+    Some(BudgetStatus {
+        exceeded: spent_usd >= settings.budget_limit_usd,
+        limit_usd: settings.budget_limit_usd,
+        spent_usd,
+        period,
+    })
+}
+
+pub fn ensure_within_budget() -> Result<(), TranslatorError> {
+    let status = with_records(|records| budget_status(records, now_secs()));
+    match status {
+        Some(status) if status.exceeded => {
+            log_line!(
+                "[USAGE] Blocking request, {} budget exceeded: ${:.2} of ${:.2}",
+                status.period,
+                status.spent_usd,
+                status.limit_usd
+            );
+            Err(TranslatorError::BudgetExceeded(format!(
+                "The {} budget of ${:.2} has been used up (${:.2} spent)",
+                status.period, status.limit_usd, status.spent_usd
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[tauri::command]
+pub fn get_usage_summary(days: Option<usize>, months: Option<usize>) -> UsageSummary {
+    with_records(|records| UsageSummary {
+        daily: aggregate(records, day_key, days.unwrap_or(30)),
+        monthly: aggregate(records, month_key, months.unwrap_or(12)),
+        budget: budget_status(records, now_secs()),
+    })
+}
+
+#[tauri::command]
+pub fn clear_usage_records() -> Result<(), String> {
+    log_line!("[USAGE] Clearing usage records");
+    if let Some(path) = get_usage_path() {
+        if path.exists() {
+            fs::remove_file(path).map_err(|e| format!("Failed to clear usage log: {}", e))?;
+        }
+    }
+    with_records(|records| records.clear());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(timestamp: u64, cost_usd: f64) -> UsageRecord {
+        UsageRecord {
+            timestamp,
+            provider: "openai".to_string(),
+            model: "gpt-4o-mini".to_string(),
+            operation: UsageOperation::Translate,
+            prompt_tokens: 100,
+            completion_tokens: 50,
+            estimated: false,
+            latency_ms: 200,
+            cost_usd,
+        }
+    }
+
+    fn local_timestamp(year: i32, month: u32, day: u32, hour: u32) -> u64 {
+        Local
+            .with_ymd_and_hms(year, month, day, hour, 30, 0)
+            .single()
+            .unwrap()
+            .timestamp() as u64
+    }
+
+    #[test]
+    fn timestamps_map_to_local_calendar_periods() {
+        assert_eq!(day_key(local_timestamp(2000, 2, 29, 12)), "2000-02-29");
+        assert_eq!(day_key(local_timestamp(2026, 3, 1, 0)), "2026-03-01");
+        assert_eq!(month_key(local_timestamp(2026, 12, 31, 23)), "2026-12");
+    }
+
+    #[test]
+    fn cost_uses_longest_model_prefix() {
+        let mini = estimate_cost("gpt-4o-mini-2024-07-18", 1_000_000, 0);
+        let full = estimate_cost("gpt-4o-2024-08-06", 1_000_000, 0);
+
+        assert!((mini - 0.15).abs() < 1e-9);
+        assert!((full - 2.50).abs() < 1e-9);
+        assert_eq!(estimate_cost("my-deployment", 1_000, 1_000), 0.0);
+    }
+
+    #[test]
+    fn estimate_counts_words_and_cjk() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("Hello, world!"), 6);
+        assert_eq!(estimate_tokens("你好"), 2);
+    }
+
+    #[test]
+    fn aggregates_group_by_day_newest_first() {
+        let records = vec![
+            usage(local_timestamp(2026, 3, 10, 9), 0.5),
+            usage(local_timestamp(2026, 3, 10, 17), 0.25),
+            usage(local_timestamp(2026, 3, 11, 9), 1.0),
+        ];
+        let daily = aggregate(&records, day_key, 30);
+
+        assert_eq!(daily.len(), 2);
+        assert_eq!(daily[0].period, "2026-03-11");
+        assert_eq!(daily[1].requests, 2);
+        assert!((daily[1].cost_usd - 0.75).abs() < 1e-9);
+        assert_eq!(daily[1].average_latency_ms, 200);
+    }
+
+    #[test]
+    fn failed_calls_are_not_billed() {
+        let mut meter =
+            UsageMeter::start("openai", "gpt-4o", UsageOperation::Translate, "Hello world");
+        assert_eq!(meter.take_record(), None);
+    }
+
+    #[test]
+    fn answered_calls_are_billed_once() {
+        let mut meter =
+            UsageMeter::start("openai", "gpt-4o", UsageOperation::Translate, "Hello world");
+        meter.observe_update(&StreamUpdate::Delta("Hallo ".to_string()));
+        meter.observe_update(&StreamUpdate::Delta("Welt".to_string()));
+
+        let usage = meter.take_record().unwrap();
+        assert_eq!(usage.completion_tokens, estimate_tokens("Hallo Welt"));
+        assert!(usage.estimated);
+        assert_eq!(meter.take_record(), None);
+    }
+}
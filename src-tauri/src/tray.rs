@@ -5,7 +5,7 @@ use crate::windows;
 use crate::resize;
 
 pub fn create_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    println!("[TRAY] Creating system tray");
+    log_line!("[TRAY] Creating system tray");
 
     let show_item = MenuItemBuilder::with_id("show", "Open Langra").build(app)?;
     let quit_item = MenuItemBuilder::with_id("quit", "Quit").build(app)?;
@@ -22,13 +22,13 @@ pub fn create_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         .on_menu_event(|app, event| {
             match event.id.as_ref() {
                 "show" => {
-                    println!("[TRAY] Show clicked - opening normal window");
+                    log_line!("[TRAY] Show clicked - opening normal window");
                     resize::resize_window_to_normal();
                     windows::show_translator_window(false);
                     let _ = app.emit("switch-to-normal", ());
                 }
                 "quit" => {
-                    println!("[TRAY] Quit clicked");
+                    log_line!("[TRAY] Quit clicked");
                     app.exit(0);
                 }
                 _ => {}
@@ -36,7 +36,7 @@ pub fn create_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         })
         .on_tray_icon_event(|tray, event| {
             if let TrayIconEvent::Click { .. } = event {
-                println!("[TRAY] Tray icon clicked - opening normal window");
+                log_line!("[TRAY] Tray icon clicked - opening normal window");
                 let app = tray.app_handle();
                 resize::resize_window_to_normal();
                 windows::show_translator_window(false);
@@ -45,6 +45,6 @@ pub fn create_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         })
         .build(app)?;
 
-    println!("[TRAY] System tray created successfully");
+    log_line!("[TRAY] System tray created successfully");
     Ok(())
 }
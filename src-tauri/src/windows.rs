@@ -13,19 +13,19 @@ pub fn get_mouse_location() -> Result<(i32, i32), String> {
 }
 
 pub fn get_translator_window(to_mouse_position: bool) -> tauri::WebviewWindow {
-    println!("[WINDOW] get_translator_window called");
+    log_line!("[WINDOW] get_translator_window called");
     let handle = APP_HANDLE.get().unwrap();
 
     let (window, should_reposition) = match handle.get_webview_window(TRANSLATOR_WIN_NAME) {
         Some(window) => {
-            println!("[WINDOW] Window already exists, reusing");
+            log_line!("[WINDOW] Window already exists, reusing");
             let was_visible = window.is_visible().unwrap_or(false);
-            println!("[WINDOW] Window was visible: {}", was_visible);
+            log_line!("[WINDOW] Window was visible: {}", was_visible);
             window.unminimize().unwrap();
             (window, !was_visible)
         }
         None => {
-            println!("[WINDOW] Creating new window");
+            log_line!("[WINDOW] Creating new window");
             let builder = tauri::WebviewWindowBuilder::new(
                 handle,
                 TRANSLATOR_WIN_NAME,
@@ -44,13 +44,13 @@ pub fn get_translator_window(to_mouse_position: bool) -> tauri::WebviewWindow {
             .center();
 
             let window = build_window(builder);
-            println!("[WINDOW] New window built");
+            log_line!("[WINDOW] New window built");
             (window, true)
         }
     };
 
     if to_mouse_position && should_reposition {
-        println!("[WINDOW] Repositioning window to mouse cursor");
+        log_line!("[WINDOW] Repositioning window to mouse cursor");
         let (mouse_x, mouse_y) = get_mouse_location().unwrap_or((100, 100));
         let window_size = window.outer_size().unwrap();
 
@@ -76,25 +76,26 @@ pub fn get_translator_window(to_mouse_position: bool) -> tauri::WebviewWindow {
             .set_position(PhysicalPosition::new(final_x, final_y))
             .unwrap();
     } else if !should_reposition {
-        println!("[WINDOW] Window already visible, keeping current position");
+        log_line!("[WINDOW] Window already visible, keeping current position");
     }
 
     window
 }
 
 pub fn show_translator_window(to_mouse_position: bool) -> tauri::WebviewWindow {
-    println!("[WINDOW] show_translator_window called");
+    log_line!("[WINDOW] show_translator_window called");
     let window = get_translator_window(to_mouse_position);
-    println!("[WINDOW] Window created/retrieved");
+    log_line!("[WINDOW] Window created/retrieved");
     window.show().unwrap();
-    println!("[WINDOW] Window shown");
+    log_line!("[WINDOW] Window shown");
     window.set_focus().unwrap();
-    println!("[WINDOW] Window focused");
+    log_line!("[WINDOW] Window focused");
     window
 }
 
 #[tauri::command]
 pub async fn hide_translator_window() {
+    crate::translator::cancel_active_job();
     if let Some(handle) = APP_HANDLE.get() {
         match handle.get_webview_window(TRANSLATOR_WIN_NAME) {
             Some(window) => {
@@ -116,7 +117,7 @@ pub fn set_always_on_top(on_top: bool) {
 
 #[tauri::command]
 pub fn show_window() {
-    println!("[WINDOW] show_window command called");
+    log_line!("[WINDOW] show_window command called");
     show_translator_window(false);
 }
 
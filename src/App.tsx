@@ -1,12 +1,15 @@
-import { useState, useEffect } from "react";
+import { useState, useEffect, useRef } from "react";
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { invoke } from "@tauri-apps/api/core";
-import { TranslationPopup } from "./TranslationPopup";
+import { TranslationPopup, TranslationAlternative, Correction } from "./TranslationPopup";
 import { NormalWindow } from "./NormalWindow";
 import { WelcomeScreen } from "./WelcomeScreen";
 import { SuccessScreen } from "./SuccessScreen";
 import { SettingsDialog } from "./SettingsDialog";
 import { PermissionModal } from "./PermissionModal";
+import { TranslationErrorPayload, describeTranslationError } from "./translationError";
+import { StreamAssembler, StreamDeltaEvent } from "./streamProtocol";
+import { TextDiff } from "./DiffText";
 
 function App() {
   const [viewMode, setViewMode] = useState<"popup" | "normal">("popup");
@@ -19,10 +22,15 @@ function App() {
     progress: 0,
   });
   const [mode, setMode] = useState<"translate" | "enhance">("translate");
+  const [alternatives, setAlternatives] = useState<TranslationAlternative[]>([]);
+  const [diff, setDiff] = useState<TextDiff | null>(null);
+  const [acceptedHunks, setAcceptedHunks] = useState<number[]>([]);
+  const [corrections, setCorrections] = useState<Correction[]>([]);
   const [showWelcome, setShowWelcome] = useState(false);
   const [showSuccess, setShowSuccess] = useState(false);
   const [settingsOpen, setSettingsOpen] = useState(false);
   const [showPermissionModal, setShowPermissionModal] = useState(false);
+  const currentJobIdRef = useRef<number | null>(null);
 
   useEffect(() => {
     const loadMode = async () => {
@@ -95,10 +103,18 @@ function App() {
       try {
         const currentWindow = getCurrentWebviewWindow();
 
-        const unlistenStart = await currentWindow.listen<{detected_language: string, original_text: string}>("translation-start", async (event) => {
-          console.log("[FRONTEND] Translation started - opening popup");
+        const unlistenJobStarted = await currentWindow.listen<{job_id: number}>("translation-job-started", (event) => {
+          currentJobIdRef.current = event.payload.job_id;
+        });
+        unlistenFns.push(unlistenJobStarted);
+
+        const unlistenStart = await currentWindow.listen<{job_id: number, detected_language: string, source_language: string, target_language: string, original_text: string, style: {language: string, style: string, formality: string, register: string | null}, format: "plain" | "markdown" | "html"}>("translation-start", async (event) => {
+          console.log("[FRONTEND] Translation started - opening popup, style:", event.payload.style, "format:", event.payload.format);
           await invoke("resize_window_to_popup");
           setViewMode("popup");
+          setAlternatives([]);
+          setDiff(null);
+          setCorrections([]);
           setPopup({
             isOpen: true,
             text: "",
@@ -119,44 +135,40 @@ function App() {
         });
         unlistenFns.push(unlistenProgress);
 
-        const unlistenPartial = await currentWindow.listen<string>("translation-partial", (event) => {
-          console.log("[FRONTEND] Received partial text:", event.payload.substring(0, 50) + "...");
+        const assembler = new StreamAssembler();
+        const handleDelta = async (delta: StreamDeltaEvent) => {
+          if (currentJobIdRef.current !== null && delta.job_id !== currentJobIdRef.current) {
+            return;
+          }
+          const update = await assembler.apply(delta);
+          if (!update) {
+            return;
+          }
           setPopup((prev) => {
-            console.log("[FRONTEND] Updating popup state with partial text");
             // Calculate progress based on text length
             // Assume translation will be ~1.2x the original text length
             const estimatedLength = prev.originalText.length * 1.2;
-            const currentLength = event.payload.length;
+            const currentLength = update.text.length;
             const calculatedProgress = Math.min(95, Math.floor((currentLength / estimatedLength) * 100));
 
             return {
               ...prev,
-              text: event.payload,
+              text: update.text,
               isStreaming: true,
               progress: calculatedProgress,
             };
           });
-        });
-        unlistenFns.push(unlistenPartial);
+        };
 
-        const unlistenEnhancementPartial = await currentWindow.listen<string>("enhancement-partial", (event) => {
-          console.log("[FRONTEND] Received enhancement partial text:", event.payload.substring(0, 50) + "...");
-          setPopup((prev) => {
-            console.log("[FRONTEND] Updating popup state with enhancement partial text");
-            // Calculate progress based on text length
-            const estimatedLength = prev.originalText.length * 1.2;
-            const currentLength = event.payload.length;
-            const calculatedProgress = Math.min(95, Math.floor((currentLength / estimatedLength) * 100));
+        const unlistenDelta = await currentWindow.listen<StreamDeltaEvent>("translation-delta", (event) => {
+          handleDelta(event.payload);
+        });
+        unlistenFns.push(unlistenDelta);
 
-            return {
-              ...prev,
-              text: event.payload,
-              isStreaming: true,
-              progress: calculatedProgress,
-            };
-          });
+        const unlistenEnhancementDelta = await currentWindow.listen<StreamDeltaEvent>("enhancement-delta", (event) => {
+          handleDelta(event.payload);
         });
-        unlistenFns.push(unlistenEnhancementPartial);
+        unlistenFns.push(unlistenEnhancementDelta);
 
         const unlistenChunk = await currentWindow.listen<string>("translation-chunk", (event) => {
           console.log("[FRONTEND] Received chunk");
@@ -168,18 +180,78 @@ function App() {
         });
         unlistenFns.push(unlistenChunk);
 
-        const unlistenComplete = await currentWindow.listen("translation-complete", () => {
+        const unlistenComplete = await currentWindow.listen<{job_id: number}>("translation-complete", (event) => {
+          if (event.payload.job_id !== currentJobIdRef.current) {
+            return;
+          }
           console.log("[FRONTEND] Translation complete");
           setPopup((prev) => ({ ...prev, isStreaming: false, progress: 100 }));
         });
         unlistenFns.push(unlistenComplete);
 
-        const unlistenError = await currentWindow.listen<string>("translation-error", (event) => {
-          console.error("[FRONTEND] Translation error:", event.payload);
+        const unlistenError = await currentWindow.listen<TranslationErrorPayload>("translation-error", (event) => {
+          if (event.payload.job_id !== currentJobIdRef.current) {
+            return;
+          }
+          console.error("[FRONTEND] Translation error:", event.payload.code, event.payload.message);
+          if (
+            event.payload.code === "quota_exceeded" ||
+            event.payload.code === "budget_exceeded" ||
+            event.payload.code === "rate_limited"
+          ) {
+            setPopup((prev) => ({
+              ...prev,
+              text: describeTranslationError(event.payload),
+              isStreaming: false,
+              progress: 100,
+            }));
+            return;
+          }
           setPopup((prev) => ({ ...prev, isOpen: false, text: "", isStreaming: false }));
         });
         unlistenFns.push(unlistenError);
 
+        const unlistenAlternatives = await currentWindow.listen<{job_id: number, alternatives: TranslationAlternative[]}>("translation-alternatives", (event) => {
+          if (event.payload.job_id !== currentJobIdRef.current) {
+            return;
+          }
+          console.log("[FRONTEND] Received alternatives:", event.payload.alternatives.length);
+          setAlternatives(event.payload.alternatives);
+        });
+        unlistenFns.push(unlistenAlternatives);
+
+        const unlistenDiff = await currentWindow.listen<TextDiff>("enhancement-diff", (event) => {
+          if (event.payload.job_id !== currentJobIdRef.current) {
+            return;
+          }
+          console.log("[FRONTEND] Enhancement diff with", event.payload.hunks.length, "hunks");
+          setDiff(event.payload);
+          setAcceptedHunks(event.payload.hunks.map((hunk) => hunk.id));
+        });
+        unlistenFns.push(unlistenDiff);
+
+        const unlistenCorrections = await currentWindow.listen<{job_id: number, corrected: string, corrections: Correction[]}>("enhancement-corrections", (event) => {
+          if (event.payload.job_id !== currentJobIdRef.current) {
+            return;
+          }
+          console.log("[FRONTEND] Received corrections:", event.payload.corrections.length);
+          setCorrections(event.payload.corrections);
+        });
+        unlistenFns.push(unlistenCorrections);
+
+        const unlistenGlossary = await currentWindow.listen<{source_term: string, expected_target: string, fixed: boolean}[]>("glossary-check", (event) => {
+          console.warn("[FRONTEND] Glossary check:", event.payload);
+        });
+        unlistenFns.push(unlistenGlossary);
+
+        const unlistenCancelled = await currentWindow.listen<{job_id: number}>("translation-cancelled", (event) => {
+          console.log("[FRONTEND] Translation job cancelled:", event.payload.job_id);
+          if (event.payload.job_id === currentJobIdRef.current) {
+            setPopup((prev) => ({ ...prev, isStreaming: false }));
+          }
+        });
+        unlistenFns.push(unlistenCancelled);
+
         const unlistenCredentialsMissing = await currentWindow.listen("credentials-missing", () => {
           console.log("[FRONTEND] Credentials missing, showing welcome screen");
           setShowWelcome(true);
@@ -214,9 +286,20 @@ function App() {
     };
   }, []);
 
+  const resolveOutputText = async () => {
+    if (mode !== "enhance" || !diff || diff.hunks.length === 0) {
+      return popup.text;
+    }
+    return invoke<string>("apply_diff_hunks", { jobId: diff.job_id, accepted: acceptedHunks });
+  };
+
+  const handleToggleHunk = (hunk: number) => {
+    setAcceptedHunks((prev) => prev.includes(hunk) ? prev.filter((id) => id !== hunk) : [...prev, hunk]);
+  };
+
   const handleCopy = async () => {
     try {
-      const textToCopy = popup.text;
+      const textToCopy = await resolveOutputText();
       await invoke("copy_to_clipboard", { text: textToCopy });
       setPopup({ isOpen: false, text: "", isStreaming: false, detectedLanguage: "en", originalText: "", progress: 0 });
       await invoke("hide_translator_window");
@@ -227,7 +310,7 @@ function App() {
 
   const handleReplace = async () => {
     try {
-      const textToInsert = popup.text;
+      const textToInsert = await resolveOutputText();
       console.log("[FRONTEND] Replace clicked, text to insert:", textToInsert);
       setPopup({ isOpen: false, text: "", isStreaming: false, detectedLanguage: "en", originalText: "", progress: 0 });
       console.log("[FRONTEND] Hiding window...");
@@ -247,6 +330,8 @@ function App() {
 
   const handleLanguageSwitch = () => {
     const newLang = popup.detectedLanguage === "de" ? "en" : "de";
+    setAlternatives([]);
+    setDiff(null);
     setPopup((prev) => ({ ...prev, detectedLanguage: newLang, text: "", isStreaming: true }));
     invoke("retranslate", {
       text: popup.originalText,
@@ -254,8 +339,34 @@ function App() {
     });
   };
 
+  const handleRequestAlternatives = () => {
+    setAlternatives([]);
+    setPopup((prev) => ({ ...prev, text: "", isStreaming: true }));
+    invoke("retranslate", {
+      text: popup.originalText,
+      sourceLang: popup.detectedLanguage,
+      alternatives: 3,
+    });
+  };
+
+  const handleSelectAlternative = (alternative: TranslationAlternative) => {
+    setPopup((prev) => ({ ...prev, text: alternative.text }));
+  };
+
+  const handleRequestCorrections = () => {
+    setDiff(null);
+    setCorrections([]);
+    setPopup((prev) => ({ ...prev, text: "", isStreaming: true }));
+    invoke("enhance_text_with_corrections", {
+      text: popup.originalText,
+      language: popup.detectedLanguage,
+      uiLanguage: navigator.language.split("-")[0] || "en",
+    });
+  };
 
   const handleClearAndStream = () => {
+    setDiff(null);
+    setCorrections([]);
     setPopup((prev) => ({
       ...prev,
       text: "",
@@ -263,12 +374,15 @@ function App() {
     }));
   };
 
-  const handleModeChange = (newMode: "translate" | "enhance") => {
+  const handleModeChange = async (newMode: "translate" | "enhance") => {
     console.log("[FRONTEND] Mode changed to:", newMode);
     setMode(newMode);
 
-    invoke("set_mode", { mode: newMode });
+    await invoke("set_mode", { mode: newMode });
 
+    setAlternatives([]);
+    setDiff(null);
+    setCorrections([]);
     setPopup((prev) => ({ ...prev, text: "", isStreaming: true }));
 
     if (newMode === "enhance") {
@@ -354,6 +468,12 @@ function App() {
           mode={mode}
           progress={popup.progress}
           originalText={popup.originalText}
+          alternatives={alternatives}
+          diff={diff}
+          acceptedHunks={acceptedHunks}
+          onToggleHunk={handleToggleHunk}
+          corrections={corrections}
+          onRequestCorrections={handleRequestCorrections}
           onCopy={handleCopy}
           onReplace={handleReplace}
           onClose={handleClose}
@@ -361,6 +481,8 @@ function App() {
           onModeChange={handleModeChange}
           onClearAndStream={handleClearAndStream}
           onExpandToNormal={handleExpandToNormal}
+          onRequestAlternatives={handleRequestAlternatives}
+          onSelectAlternative={handleSelectAlternative}
         />
       ) : (
         <NormalWindow />
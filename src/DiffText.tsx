@@ -0,0 +1,60 @@
+import { Box, Tooltip } from "@mui/material";
+
+export interface DiffSpan {
+  kind: "equal" | "insert" | "delete";
+  text: string;
+  hunk?: number;
+}
+
+export interface DiffHunk {
+  id: number;
+  original: string;
+  enhanced: string;
+  chars: DiffSpan[];
+}
+
+export interface TextDiff {
+  job_id: number;
+  spans: DiffSpan[];
+  hunks: DiffHunk[];
+}
+
+interface DiffTextProps {
+  diff: TextDiff;
+  accepted: number[];
+  onToggleHunk: (hunk: number) => void;
+}
+
+export function DiffText({ diff, accepted, onToggleHunk }: DiffTextProps) {
+  return (
+    <>
+      {diff.spans.map((span, index) => {
+        if (span.kind === "equal" || span.hunk === undefined) {
+          return <span key={index}>{span.text}</span>;
+        }
+
+        const hunk = span.hunk;
+        const isAccepted = accepted.includes(hunk);
+        const isActive = span.kind === "insert" ? isAccepted : !isAccepted;
+        return (
+          <Tooltip key={index} title={isAccepted ? "Click to reject" : "Click to accept"} placement="top" arrow>
+            <Box
+              component="span"
+              onClick={() => onToggleHunk(hunk)}
+              sx={{
+                cursor: "pointer",
+                borderRadius: "2px",
+                color: span.kind === "insert" ? "#81c784" : "#e57373",
+                backgroundColor: isActive ? (span.kind === "insert" ? "#1b3320" : "#3a1c1c") : "transparent",
+                textDecoration: span.kind === "delete" ? "line-through" : "none",
+                opacity: isActive ? 1 : 0.4,
+              }}
+            >
+              {span.text}
+            </Box>
+          </Tooltip>
+        );
+      })}
+    </>
+  );
+}
@@ -95,9 +95,11 @@ export function LoginDialog({ open, onClose, onLoginSuccess }: LoginDialogProps)
 
   const handleSignupClick = async () => {
     try {
-      await invoke("open_url", { url: "https://white-bush-0ea25dc03.3.azurestaticapps.net/signup" });
+      const backendUrl = await invoke<string>("get_backend_url");
+      await invoke("open_url", { url: `${backendUrl}/signup` });
     } catch (error) {
       console.error("Failed to open signup page:", error);
+      setError(String(error));
     }
   };
 
@@ -1,5 +1,5 @@
-import { Box, Typography, TextField, Button, ToggleButtonGroup, ToggleButton, Stack, CircularProgress, IconButton, Chip, Menu, MenuItem, ListItemIcon, ListItemText } from "@mui/material";
-import { useState, useEffect, useCallback } from "react";
+import { Box, Typography, TextField, Button, ToggleButtonGroup, ToggleButton, Stack, CircularProgress, IconButton, Chip, Menu, MenuItem, ListItemIcon, ListItemText, LinearProgress } from "@mui/material";
+import { useState, useEffect, useCallback, useRef } from "react";
 import ContentCopyIcon from "@mui/icons-material/ContentCopy";
 import SettingsIcon from "@mui/icons-material/Settings";
 import TuneIcon from "@mui/icons-material/Tune";
@@ -7,11 +7,48 @@ import SwapHorizIcon from "@mui/icons-material/SwapHoriz";
 import LoginIcon from "@mui/icons-material/Login";
 import LogoutIcon from "@mui/icons-material/Logout";
 import KeyIcon from "@mui/icons-material/Key";
+import CheckBoxIcon from "@mui/icons-material/CheckBox";
+import CheckBoxOutlineBlankIcon from "@mui/icons-material/CheckBoxOutlineBlank";
 import { invoke } from "@tauri-apps/api/core";
+import { TranslationErrorPayload, describeTranslationError } from "./translationError";
+import { StreamAssembler, StreamDeltaEvent } from "./streamProtocol";
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { SettingsDialog } from "./SettingsDialog";
 import { PreferencesDialog } from "./PreferencesDialog";
 
+interface BatchProgressEvent {
+  batch_id: number;
+  file_index: number;
+  file_count: number;
+  path: string;
+  stage: "started" | "segment" | "completed" | "failed";
+  completed_segments: number;
+  total_segments: number;
+  output_path?: string;
+  error?: Omit<TranslationErrorPayload, "job_id">;
+  added_keys?: LocaleKeyChange[];
+}
+
+interface LocaleKeyChange {
+  key: string;
+  source: string;
+  translation: string;
+}
+
+interface BatchFile {
+  path: string;
+  stage: BatchProgressEvent["stage"] | "queued";
+  completed: number;
+  total: number;
+  outputPath?: string;
+  error?: string;
+  addedKeys?: LocaleKeyChange[];
+}
+
+function fileName(path: string): string {
+  return path.split(/[\\/]/).pop() || path;
+}
+
 export function NormalWindow() {
   const [mode, setMode] = useState<"translate" | "enhance">("translate");
   const [sourceText, setSourceText] = useState("");
@@ -23,6 +60,10 @@ export function NormalWindow() {
   const [preferencesOpen, setPreferencesOpen] = useState(false);
   const [menuAnchorEl, setMenuAnchorEl] = useState<null | HTMLElement>(null);
   const [isLoggedIn, setIsLoggedIn] = useState(false);
+  const [batchFiles, setBatchFiles] = useState<BatchFile[]>([]);
+  const [isBatchRunning, setIsBatchRunning] = useState(false);
+  const [includeFuzzy, setIncludeFuzzy] = useState(false);
+  const includeFuzzyRef = useRef(false);
 
   const handleProcess = useCallback(async () => {
     if (!sourceText.trim()) return;
@@ -99,22 +140,26 @@ export function NormalWindow() {
     const unlistenFns: (() => void)[] = [];
 
     const setupListeners = async () => {
-      const unlistenPartial = await currentWindow.listen<string>(
-        mode === "translate" ? "translation-partial" : "enhancement-partial",
-        (event) => {
-          setResultText(event.payload);
-          setIsProcessing(true);
+      const assembler = new StreamAssembler();
+      const unlistenDelta = await currentWindow.listen<StreamDeltaEvent>(
+        mode === "translate" ? "translation-delta" : "enhancement-delta",
+        async (event) => {
+          const update = await assembler.apply(event.payload);
+          if (update) {
+            setResultText(update.text);
+            setIsProcessing(true);
+          }
         }
       );
-      unlistenFns.push(unlistenPartial);
+      unlistenFns.push(unlistenDelta);
 
       const unlistenComplete = await currentWindow.listen("translation-complete", () => {
         setIsProcessing(false);
       });
       unlistenFns.push(unlistenComplete);
 
-      const unlistenError = await currentWindow.listen<string>("translation-error", (event) => {
-        setResultText(`Error: ${event.payload}`);
+      const unlistenError = await currentWindow.listen<TranslationErrorPayload>("translation-error", (event) => {
+        setResultText(`Error: ${describeTranslationError(event.payload)}`);
         setIsProcessing(false);
       });
       unlistenFns.push(unlistenError);
@@ -127,6 +172,69 @@ export function NormalWindow() {
     };
   }, [mode]);
 
+  useEffect(() => {
+    const currentWindow = getCurrentWebviewWindow();
+    const unlistenFns: (() => void)[] = [];
+
+    const setupBatchListeners = async () => {
+      const unlistenProgress = await currentWindow.listen<BatchProgressEvent>("batch-progress", (event) => {
+        const progress = event.payload;
+        setBatchFiles((prev) => prev.map((file, index) => {
+          if (index !== progress.file_index) {
+            return file;
+          }
+          return {
+            ...file,
+            stage: progress.stage,
+            completed: progress.stage === "completed" ? file.total : progress.completed_segments,
+            total: progress.stage === "started" || progress.stage === "segment" ? progress.total_segments : file.total,
+            outputPath: progress.output_path,
+            addedKeys: progress.added_keys,
+            error: progress.error ? describeTranslationError({ ...progress.error, job_id: 0 }) : undefined,
+          };
+        }));
+      });
+      unlistenFns.push(unlistenProgress);
+
+      const unlistenDrop = await currentWindow.onDragDropEvent(async (event) => {
+        if (event.payload.type !== "drop" || event.payload.paths.length === 0) {
+          return;
+        }
+        const paths = event.payload.paths;
+        console.log("[NORMAL_WINDOW] Translating dropped files:", paths);
+        setBatchFiles(paths.map((path) => ({ path, stage: "queued", completed: 0, total: 0 })));
+        setIsBatchRunning(true);
+        try {
+          await invoke("translate_files", { paths, options: { include_fuzzy: includeFuzzyRef.current } });
+        } catch (error) {
+          console.error("[NORMAL_WINDOW] Batch translation failed:", error);
+        } finally {
+          setIsBatchRunning(false);
+        }
+      });
+      unlistenFns.push(unlistenDrop);
+    };
+
+    setupBatchListeners();
+
+    return () => {
+      unlistenFns.forEach(fn => fn());
+    };
+  }, []);
+
+  const handleToggleFuzzy = () => {
+    includeFuzzyRef.current = !includeFuzzyRef.current;
+    setIncludeFuzzy(includeFuzzyRef.current);
+  };
+
+  const handleCancelBatch = async () => {
+    try {
+      await invoke("cancel_batch_translation");
+    } catch (error) {
+      console.error("[NORMAL_WINDOW] Cancel batch failed:", error);
+    }
+  };
+
   const handleCopy = async () => {
     if (!resultText) return;
     try {
@@ -254,6 +362,29 @@ export function NormalWindow() {
             />
           </MenuItem>
 
+          <MenuItem
+            onClick={handleToggleFuzzy}
+            sx={{
+              padding: "8px 12px",
+              minHeight: "36px",
+              "&:hover": { backgroundColor: "#2a2a2a" },
+            }}
+          >
+            <ListItemIcon sx={{ color: "#64b5f6", minWidth: "32px" }}>
+              {includeFuzzy ? <CheckBoxIcon sx={{ fontSize: 16 }} /> : <CheckBoxOutlineBlankIcon sx={{ fontSize: 16 }} />}
+            </ListItemIcon>
+            <ListItemText
+              primary="Retranslate fuzzy entries"
+              primaryTypographyProps={{
+                sx: {
+                  fontSize: "13px",
+                  fontWeight: 400,
+                  color: "#e0e0e0",
+                }
+              }}
+            />
+          </MenuItem>
+
           <MenuItem
             onClick={async () => {
               setMenuAnchorEl(null);
@@ -267,7 +398,11 @@ export function NormalWindow() {
                   console.error("[NORMAL_WINDOW] Logout failed:", error);
                 }
               } else {
-                window.open("https://white-bush-0ea25dc03.3.azurestaticapps.net/auth", "_blank");
+                try {
+                  window.open(`${await invoke<string>("get_backend_url")}/auth`, "_blank");
+                } catch (error) {
+                  console.error("[NORMAL_WINDOW] Backend URL is invalid:", error);
+                }
               }
             }}
             sx={{
@@ -409,7 +544,7 @@ export function NormalWindow() {
               multiline
               value={sourceText}
               onChange={(e) => setSourceText(e.target.value)}
-              placeholder={mode === "translate" ? "Paste or type text to translate..." : "Paste or type text to fix..."}
+              placeholder={mode === "translate" ? "Paste or type text to translate, or drop documents, subtitles, PO/XLIFF catalogs or JSON/YAML/Fluent locale files..." : "Paste or type text to fix..."}
               slotProps={{
                 input: {
                   sx: {
@@ -490,6 +625,88 @@ export function NormalWindow() {
         </Box>
       </Box>
 
+      {batchFiles.length > 0 && (
+        <Box
+          sx={{
+            padding: "10px 20px",
+            borderTop: "1px solid #222",
+            backgroundColor: "#1a1a1a",
+            display: "flex",
+            flexDirection: "column",
+            gap: "8px",
+            maxHeight: "160px",
+            overflow: "auto",
+            flexShrink: 0,
+          }}
+        >
+          <Box sx={{ display: "flex", justifyContent: "space-between", alignItems: "center" }}>
+            <Typography sx={{
+              color: "#888",
+              fontSize: "11px",
+              fontWeight: 500,
+              textTransform: "uppercase",
+              letterSpacing: "0.5px",
+            }}>
+              Files
+            </Typography>
+            <Button
+              size="small"
+              onClick={isBatchRunning ? handleCancelBatch : () => setBatchFiles([])}
+              sx={{ fontSize: "11px", textTransform: "none", color: "#64b5f6", minWidth: 0, padding: "0 6px" }}
+            >
+              {isBatchRunning ? "Cancel" : "Clear"}
+            </Button>
+          </Box>
+          {batchFiles.map((file) => (
+            <Box key={file.path} sx={{ display: "flex", flexDirection: "column", gap: "4px" }}>
+              <Box sx={{ display: "flex", justifyContent: "space-between", gap: "12px" }}>
+                <Typography noWrap sx={{ color: "#e0e0e0", fontSize: "12px" }}>
+                  {fileName(file.path)}
+                </Typography>
+                <Typography noWrap sx={{
+                  color: file.stage === "failed" ? "#e57373" : file.stage === "completed" ? "#81c784" : "#888",
+                  fontSize: "11px",
+                  flexShrink: 0,
+                }}>
+                  {file.stage === "completed"
+                    ? `Saved ${fileName(file.outputPath || "")}${file.addedKeys ? ` (+${file.addedKeys.length} keys)` : ""}`
+                    : file.stage === "failed"
+                      ? file.error
+                      : file.stage === "queued"
+                        ? "Waiting..."
+                        : `${file.completed}/${file.total} segments`}
+                </Typography>
+              </Box>
+              <LinearProgress
+                variant="determinate"
+                value={file.stage === "completed" ? 100 : file.total > 0 ? (file.completed / file.total) * 100 : 0}
+                sx={{
+                  height: "3px",
+                  backgroundColor: "#2a2a2a",
+                  "& .MuiLinearProgress-bar": {
+                    backgroundColor: file.stage === "failed" ? "#e57373" : "#64b5f6",
+                  },
+                }}
+              />
+              {file.addedKeys && file.addedKeys.length > 0 && (
+                <Box sx={{ maxHeight: "80px", overflow: "auto" }}>
+                  {file.addedKeys.map((change) => (
+                    <Typography
+                      key={change.key}
+                      noWrap
+                      title={change.source}
+                      sx={{ color: "#81c784", fontSize: "11px", fontFamily: "monospace" }}
+                    >
+                      + {change.key}: {change.translation}
+                    </Typography>
+                  ))}
+                </Box>
+              )}
+            </Box>
+          ))}
+        </Box>
+      )}
+
       <Box
         sx={{
           padding: "12px 20px",
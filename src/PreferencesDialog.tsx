@@ -1,4 +1,4 @@
-import { Dialog, DialogTitle, DialogContent, DialogActions, Button, FormControl, FormLabel, RadioGroup, FormControlLabel, Radio } from "@mui/material";
+import { Dialog, DialogTitle, DialogContent, DialogActions, Button, FormControl, FormLabel, FormHelperText, RadioGroup, FormControlLabel, Radio, TextField, Alert, Box } from "@mui/material";
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 
@@ -7,17 +7,117 @@ interface PreferencesDialogProps {
   onClose: () => void;
 }
 
+interface LanguageRule {
+  source: string;
+  target: string;
+}
+
+interface LanguageStyle {
+  language: string;
+  style: "formal" | "friendly" | "casual";
+  register: string;
+}
+
+interface NetworkSettings {
+  connect_timeout_secs: number;
+  first_byte_timeout_secs: number;
+  max_retries: number;
+  proxy_mode: "system" | "none" | "manual";
+  proxy_url: string;
+  ca_certificates: string[];
+}
+
 interface Settings {
   style: "formal" | "friendly" | "casual";
+  my_languages: string[];
+  language_rules: LanguageRule[];
+  language_styles: LanguageStyle[];
+  cache_max_entries: number;
+  network: NetworkSettings;
+  backend_url: string;
+  glossary_mode: "fix" | "flag" | "off";
+  mask_protected_text: boolean;
+  protected_patterns: string[];
+  chunk_max_chars: number;
+  max_parallel_requests: number;
+  translation_memory_enabled: boolean;
+  memory_fuzzy_threshold: number;
+  budget_limit_usd: number;
+  budget_period: "daily" | "monthly";
+  subtitle_max_line_chars: number;
+}
+
+interface UsageAggregate {
+  period: string;
+  requests: number;
+  cost_usd: number;
+}
+
+interface UsageSummary {
+  daily: UsageAggregate[];
+  monthly: UsageAggregate[];
+}
+
+const STYLES = ["formal", "friendly", "casual"];
+
+function formatUsage(label: string, period: string, aggregates: UsageAggregate[]): string {
+  const current = aggregates.find((aggregate) => aggregate.period === period);
+  const cost = current ? current.cost_usd : 0;
+  const requests = current ? current.requests : 0;
+  return `${label}: $${cost.toFixed(2)} (${requests} requests)`;
+}
+
+function formatLanguageStyles(styles: LanguageStyle[]): string {
+  return styles.map((rule) => `${rule.language}=${rule.style}`).join(", ");
+}
+
+function parseLanguageStyles(text: string, existing: LanguageStyle[]): LanguageStyle[] {
+  return text
+    .split(",")
+    .map((entry) => entry.split("=").map((part) => part.trim()))
+    .filter(([language, style]) => language && STYLES.includes(style))
+    .map(([language, style]) => ({
+      language,
+      style: style as LanguageStyle["style"],
+      register: existing.find((rule) => rule.language === language)?.register ?? "",
+    }));
 }
 
 export function PreferencesDialog({ open, onClose }: PreferencesDialogProps) {
   const [settings, setSettings] = useState<Settings>({
     style: "friendly",
+    my_languages: ["de", "en"],
+    language_rules: [],
+    language_styles: [],
+    cache_max_entries: 1000,
+    network: {
+      connect_timeout_secs: 10,
+      first_byte_timeout_secs: 30,
+      max_retries: 2,
+      proxy_mode: "system",
+      proxy_url: "",
+      ca_certificates: [],
+    },
+    backend_url: "",
+    glossary_mode: "fix",
+    mask_protected_text: true,
+    protected_patterns: [],
+    chunk_max_chars: 2000,
+    max_parallel_requests: 3,
+    translation_memory_enabled: true,
+    memory_fuzzy_threshold: 75,
+    budget_limit_usd: 0,
+    budget_period: "monthly",
+    subtitle_max_line_chars: 42,
   });
 
+  const [errorMessage, setErrorMessage] = useState("");
+  const [languageStylesText, setLanguageStylesText] = useState("");
+  const [usage, setUsage] = useState<UsageSummary | null>(null);
+
   useEffect(() => {
     if (open) {
+      setErrorMessage("");
       loadSettings();
     }
   }, [open]);
@@ -26,17 +126,23 @@ export function PreferencesDialog({ open, onClose }: PreferencesDialogProps) {
     try {
       const loadedSettings = await invoke<Settings>("get_settings");
       setSettings(loadedSettings);
+      setLanguageStylesText(formatLanguageStyles(loadedSettings.language_styles));
+      setUsage(await invoke<UsageSummary>("get_usage_summary", { days: 1, months: 1 }));
     } catch (error) {
       console.error("Failed to load settings:", error);
     }
   };
 
   const handleSave = async () => {
+    setErrorMessage("");
     try {
-      await invoke("save_settings", { settings });
+      await invoke("save_settings", {
+        settings: { ...settings, language_styles: parseLanguageStyles(languageStylesText, settings.language_styles) },
+      });
       onClose();
     } catch (error) {
       console.error("Failed to save settings:", error);
+      setErrorMessage(String(error));
     }
   };
 
@@ -119,6 +225,462 @@ export function PreferencesDialog({ open, onClose }: PreferencesDialogProps) {
             />
           </RadioGroup>
         </FormControl>
+
+        <FormControl fullWidth sx={{ mt: "20px" }}>
+          <FormLabel sx={{
+            color: "#888",
+            mb: "12px",
+            fontSize: "11px",
+            fontWeight: 500,
+            textTransform: "uppercase",
+            letterSpacing: "0.5px",
+          }}>
+            My Languages
+          </FormLabel>
+          <TextField
+            size="small"
+            value={settings.my_languages.join(", ")}
+            onChange={(e) => setSettings({
+              ...settings,
+              my_languages: e.target.value.split(",").map((lang) => lang.trim()).filter((lang) => lang.length > 0),
+            })}
+            helperText="Primary language first, then secondary (e.g. de, en, pt-BR)"
+            sx={{
+              "& .MuiInputBase-input": { fontSize: "13px", color: "#e0e0e0" },
+              "& .MuiFormHelperText-root": { color: "#666" },
+            }}
+          />
+        </FormControl>
+
+        <FormControl fullWidth sx={{ mt: "20px" }}>
+          <FormLabel sx={{
+            color: "#888",
+            mb: "12px",
+            fontSize: "11px",
+            fontWeight: 500,
+            textTransform: "uppercase",
+            letterSpacing: "0.5px",
+          }}>
+            Per-Language Style
+          </FormLabel>
+          <TextField
+            size="small"
+            value={languageStylesText}
+            onChange={(e) => setLanguageStylesText(e.target.value)}
+            onBlur={() => setSettings({ ...settings, language_styles: parseLanguageStyles(languageStylesText, settings.language_styles) })}
+            placeholder="de=formal, en=casual"
+            helperText="Overrides the translation style for a language (formal, friendly or casual)"
+            sx={{
+              "& .MuiInputBase-input": { fontSize: "13px", color: "#e0e0e0" },
+              "& .MuiFormHelperText-root": { color: "#666" },
+            }}
+          />
+        </FormControl>
+
+        <FormControl fullWidth sx={{ mt: "20px" }}>
+          <FormLabel sx={{
+            color: "#888",
+            mb: "12px",
+            fontSize: "11px",
+            fontWeight: 500,
+            textTransform: "uppercase",
+            letterSpacing: "0.5px",
+          }}>
+            Glossary Enforcement
+          </FormLabel>
+          <RadioGroup
+            value={settings.glossary_mode}
+            onChange={(e) => setSettings({ ...settings, glossary_mode: e.target.value as any })}
+            row
+            sx={{ gap: "12px" }}
+          >
+            <FormControlLabel
+              value="fix"
+              label="Fix"
+              control={<Radio size="small" sx={{ color: "#64b5f6", "&.Mui-checked": { color: "#64b5f6" } }} />}
+              sx={{
+                "& .MuiFormControlLabel-label": {
+                  fontSize: "13px",
+                  fontWeight: 400,
+                  color: "#e0e0e0",
+                }
+              }}
+            />
+            <FormControlLabel
+              value="flag"
+              label="Flag only"
+              control={<Radio size="small" sx={{ color: "#64b5f6", "&.Mui-checked": { color: "#64b5f6" } }} />}
+              sx={{
+                "& .MuiFormControlLabel-label": {
+                  fontSize: "13px",
+                  fontWeight: 400,
+                  color: "#e0e0e0",
+                }
+              }}
+            />
+            <FormControlLabel
+              value="off"
+              label="Off"
+              control={<Radio size="small" sx={{ color: "#64b5f6", "&.Mui-checked": { color: "#64b5f6" } }} />}
+              sx={{
+                "& .MuiFormControlLabel-label": {
+                  fontSize: "13px",
+                  fontWeight: 400,
+                  color: "#e0e0e0",
+                }
+              }}
+            />
+          </RadioGroup>
+          <FormHelperText sx={{ color: "#666", mx: 0 }}>
+            Fix replaces source terms left untranslated and corrects the case of target terms. Any other rendering is only flagged.
+          </FormHelperText>
+        </FormControl>
+
+        <FormControl fullWidth sx={{ mt: "20px" }}>
+          <FormLabel sx={{
+            color: "#888",
+            mb: "12px",
+            fontSize: "11px",
+            fontWeight: 500,
+            textTransform: "uppercase",
+            letterSpacing: "0.5px",
+          }}>
+            Protected Text
+          </FormLabel>
+          <RadioGroup
+            value={settings.mask_protected_text ? "on" : "off"}
+            onChange={(e) => setSettings({ ...settings, mask_protected_text: e.target.value === "on" })}
+            row
+            sx={{ gap: "12px" }}
+          >
+            <FormControlLabel
+              value="on"
+              label="Keep code, URLs and placeholders"
+              control={<Radio size="small" sx={{ color: "#64b5f6", "&.Mui-checked": { color: "#64b5f6" } }} />}
+              sx={{
+                "& .MuiFormControlLabel-label": {
+                  fontSize: "13px",
+                  fontWeight: 400,
+                  color: "#e0e0e0",
+                }
+              }}
+            />
+            <FormControlLabel
+              value="off"
+              label="Translate everything"
+              control={<Radio size="small" sx={{ color: "#64b5f6", "&.Mui-checked": { color: "#64b5f6" } }} />}
+              sx={{
+                "& .MuiFormControlLabel-label": {
+                  fontSize: "13px",
+                  fontWeight: 400,
+                  color: "#e0e0e0",
+                }
+              }}
+            />
+          </RadioGroup>
+          <TextField
+            size="small"
+            multiline
+            minRows={2}
+            value={settings.protected_patterns.join("\n")}
+            onChange={(e) => setSettings({ ...settings, protected_patterns: e.target.value.split("\n") })}
+            disabled={!settings.mask_protected_text}
+            placeholder="Additional regex patterns, one per line"
+            sx={{
+              mt: "8px",
+              "& .MuiInputBase-input": { fontSize: "13px", color: "#e0e0e0" },
+            }}
+          />
+        </FormControl>
+
+        <FormControl fullWidth sx={{ mt: "20px" }}>
+          <FormLabel sx={{
+            color: "#888",
+            mb: "12px",
+            fontSize: "11px",
+            fontWeight: 500,
+            textTransform: "uppercase",
+            letterSpacing: "0.5px",
+          }}>
+            Translation Memory
+          </FormLabel>
+          <RadioGroup
+            value={settings.translation_memory_enabled ? "on" : "off"}
+            onChange={(e) => setSettings({ ...settings, translation_memory_enabled: e.target.value === "on" })}
+            row
+            sx={{ gap: "12px" }}
+          >
+            <FormControlLabel
+              value="on"
+              label="Reuse confirmed translations"
+              control={<Radio size="small" sx={{ color: "#64b5f6", "&.Mui-checked": { color: "#64b5f6" } }} />}
+              sx={{
+                "& .MuiFormControlLabel-label": {
+                  fontSize: "13px",
+                  fontWeight: 400,
+                  color: "#e0e0e0",
+                }
+              }}
+            />
+            <FormControlLabel
+              value="off"
+              label="Off"
+              control={<Radio size="small" sx={{ color: "#64b5f6", "&.Mui-checked": { color: "#64b5f6" } }} />}
+              sx={{
+                "& .MuiFormControlLabel-label": {
+                  fontSize: "13px",
+                  fontWeight: 400,
+                  color: "#e0e0e0",
+                }
+              }}
+            />
+          </RadioGroup>
+          <TextField
+            size="small"
+            type="number"
+            label="Fuzzy match threshold (%)"
+            value={settings.memory_fuzzy_threshold}
+            onChange={(e) => setSettings({ ...settings, memory_fuzzy_threshold: Number(e.target.value) })}
+            disabled={!settings.translation_memory_enabled}
+            slotProps={{ htmlInput: { min: 50, max: 99 } }}
+            sx={{
+              mt: "8px",
+              "& .MuiInputBase-input": { fontSize: "13px", color: "#e0e0e0" },
+              "& .MuiInputLabel-root": { color: "#888" },
+            }}
+          />
+        </FormControl>
+
+        <FormControl fullWidth sx={{ mt: "20px" }}>
+          <FormLabel sx={{
+            color: "#888",
+            mb: "12px",
+            fontSize: "11px",
+            fontWeight: 500,
+            textTransform: "uppercase",
+            letterSpacing: "0.5px",
+          }}>
+            Usage Budget
+          </FormLabel>
+          <RadioGroup
+            value={settings.budget_period}
+            onChange={(e) => setSettings({ ...settings, budget_period: e.target.value as Settings["budget_period"] })}
+            row
+            sx={{ gap: "12px" }}
+          >
+            <FormControlLabel
+              value="daily"
+              label="Daily"
+              control={<Radio size="small" sx={{ color: "#64b5f6", "&.Mui-checked": { color: "#64b5f6" } }} />}
+              sx={{
+                "& .MuiFormControlLabel-label": {
+                  fontSize: "13px",
+                  fontWeight: 400,
+                  color: "#e0e0e0",
+                }
+              }}
+            />
+            <FormControlLabel
+              value="monthly"
+              label="Monthly"
+              control={<Radio size="small" sx={{ color: "#64b5f6", "&.Mui-checked": { color: "#64b5f6" } }} />}
+              sx={{
+                "& .MuiFormControlLabel-label": {
+                  fontSize: "13px",
+                  fontWeight: 400,
+                  color: "#e0e0e0",
+                }
+              }}
+            />
+          </RadioGroup>
+          <TextField
+            size="small"
+            type="number"
+            label="Spending limit (USD, 0 for no limit)"
+            value={settings.budget_limit_usd}
+            onChange={(e) => setSettings({ ...settings, budget_limit_usd: Number(e.target.value) })}
+            slotProps={{ htmlInput: { min: 0, step: 0.5 } }}
+            sx={{
+              mt: "8px",
+              "& .MuiInputBase-input": { fontSize: "13px", color: "#e0e0e0" },
+              "& .MuiInputLabel-root": { color: "#888" },
+            }}
+          />
+          {usage && (
+            <Box sx={{ mt: "8px", fontSize: "12px", color: "#888" }}>
+              {formatUsage("Today", new Date().toISOString().slice(0, 10), usage.daily)}
+              {" · "}
+              {formatUsage("This month", new Date().toISOString().slice(0, 7), usage.monthly)}
+            </Box>
+          )}
+        </FormControl>
+
+        <FormControl fullWidth sx={{ mt: "20px" }}>
+          <FormLabel sx={{
+            color: "#888",
+            mb: "12px",
+            fontSize: "11px",
+            fontWeight: 500,
+            textTransform: "uppercase",
+            letterSpacing: "0.5px",
+          }}>
+            Subtitles
+          </FormLabel>
+          <TextField
+            size="small"
+            type="number"
+            label="Maximum characters per line"
+            value={settings.subtitle_max_line_chars}
+            onChange={(e) => setSettings({ ...settings, subtitle_max_line_chars: Number(e.target.value) })}
+            slotProps={{ htmlInput: { min: 20, max: 80 } }}
+            sx={{
+              "& .MuiInputBase-input": { fontSize: "13px", color: "#e0e0e0" },
+              "& .MuiInputLabel-root": { color: "#888" },
+            }}
+          />
+        </FormControl>
+
+        <FormControl fullWidth sx={{ mt: "20px" }}>
+          <FormLabel sx={{
+            color: "#888",
+            mb: "12px",
+            fontSize: "11px",
+            fontWeight: 500,
+            textTransform: "uppercase",
+            letterSpacing: "0.5px",
+          }}>
+            Proxy
+          </FormLabel>
+          <RadioGroup
+            value={settings.network.proxy_mode}
+            onChange={(e) => setSettings({
+              ...settings,
+              network: { ...settings.network, proxy_mode: e.target.value as any },
+            })}
+            row
+            sx={{ gap: "12px" }}
+          >
+            <FormControlLabel
+              value="system"
+              label="System"
+              control={<Radio size="small" sx={{ color: "#64b5f6", "&.Mui-checked": { color: "#64b5f6" } }} />}
+              sx={{
+                "& .MuiFormControlLabel-label": {
+                  fontSize: "13px",
+                  fontWeight: 400,
+                  color: "#e0e0e0",
+                }
+              }}
+            />
+            <FormControlLabel
+              value="none"
+              label="None"
+              control={<Radio size="small" sx={{ color: "#64b5f6", "&.Mui-checked": { color: "#64b5f6" } }} />}
+              sx={{
+                "& .MuiFormControlLabel-label": {
+                  fontSize: "13px",
+                  fontWeight: 400,
+                  color: "#e0e0e0",
+                }
+              }}
+            />
+            <FormControlLabel
+              value="manual"
+              label="Manual"
+              control={<Radio size="small" sx={{ color: "#64b5f6", "&.Mui-checked": { color: "#64b5f6" } }} />}
+              sx={{
+                "& .MuiFormControlLabel-label": {
+                  fontSize: "13px",
+                  fontWeight: 400,
+                  color: "#e0e0e0",
+                }
+              }}
+            />
+          </RadioGroup>
+          {settings.network.proxy_mode === "manual" && (
+            <TextField
+              size="small"
+              value={settings.network.proxy_url}
+              onChange={(e) => setSettings({
+                ...settings,
+                network: { ...settings.network, proxy_url: e.target.value },
+              })}
+              placeholder="http://proxy.example.com:8080"
+              helperText="Supports http, https, socks5 and socks5h proxies"
+              sx={{
+                mt: "8px",
+                "& .MuiInputBase-input": { fontSize: "13px", color: "#e0e0e0" },
+                "& .MuiFormHelperText-root": { color: "#666" },
+              }}
+            />
+          )}
+        </FormControl>
+
+        <FormControl fullWidth sx={{ mt: "20px" }}>
+          <FormLabel sx={{
+            color: "#888",
+            mb: "12px",
+            fontSize: "11px",
+            fontWeight: 500,
+            textTransform: "uppercase",
+            letterSpacing: "0.5px",
+          }}>
+            Custom CA Certificates
+          </FormLabel>
+          <TextField
+            size="small"
+            value={settings.network.ca_certificates.join(", ")}
+            onChange={(e) => setSettings({
+              ...settings,
+              network: {
+                ...settings.network,
+                ca_certificates: e.target.value.split(",").map((path) => path.trim()).filter((path) => path.length > 0),
+              },
+            })}
+            helperText="Comma-separated paths to PEM or DER certificate files"
+            sx={{
+              "& .MuiInputBase-input": { fontSize: "13px", color: "#e0e0e0" },
+              "& .MuiFormHelperText-root": { color: "#666" },
+            }}
+          />
+        </FormControl>
+
+        <FormControl fullWidth sx={{ mt: "20px" }}>
+          <FormLabel sx={{
+            color: "#888",
+            mb: "12px",
+            fontSize: "11px",
+            fontWeight: 500,
+            textTransform: "uppercase",
+            letterSpacing: "0.5px",
+          }}>
+            Backend URL
+          </FormLabel>
+          <TextField
+            size="small"
+            value={settings.backend_url}
+            onChange={(e) => setSettings({ ...settings, backend_url: e.target.value })}
+            placeholder="https://langra.example.com"
+            helperText="Leave empty to use the default Langra backend. LANGRA_BACKEND_URL and --backend-url take precedence."
+            sx={{
+              "& .MuiInputBase-input": { fontSize: "13px", color: "#e0e0e0" },
+              "& .MuiFormHelperText-root": { color: "#666" },
+            }}
+          />
+        </FormControl>
+
+        {errorMessage && (
+          <Alert severity="error" sx={{
+            mt: "16px",
+            padding: "8px 12px",
+            fontSize: "11px",
+            "& .MuiAlert-icon": {
+              fontSize: "16px",
+            }
+          }}>
+            {errorMessage}
+          </Alert>
+        )}
       </DialogContent>
 
       <DialogActions sx={{ borderTop: "1px solid #333", padding: "12px 16px", gap: "12px" }}>
@@ -7,12 +7,24 @@ interface SettingsDialogProps {
   onClose: (credentialsSaved?: boolean) => void;
 }
 
+type Provider = "openai" | "azure" | "deepl" | "libretranslate";
+
 interface ApiKeySettings {
-  provider: "openai" | "azure";
+  provider: Provider;
   openai_api_key: string;
   azure_endpoint: string;
   azure_api_key: string;
   azure_deployment: string;
+  openai_base_url: string;
+  openai_model: string;
+  deepl_api_key: string;
+  libretranslate_url: string;
+  libretranslate_api_key: string;
+}
+
+interface ApiKeyStatus {
+  provider: string;
+  has_credentials: boolean;
 }
 
 export function SettingsDialog({ open, onClose }: SettingsDialogProps) {
@@ -22,6 +34,11 @@ export function SettingsDialog({ open, onClose }: SettingsDialogProps) {
     azure_endpoint: "",
     azure_api_key: "",
     azure_deployment: "gpt-4o-mini",
+    openai_base_url: "",
+    openai_model: "",
+    deepl_api_key: "",
+    libretranslate_url: "",
+    libretranslate_api_key: "",
   });
   const [saveStatus, setSaveStatus] = useState<"idle" | "saving" | "success" | "error">("idle");
   const [errorMessage, setErrorMessage] = useState("");
@@ -37,15 +54,8 @@ export function SettingsDialog({ open, onClose }: SettingsDialogProps) {
 
   const loadSettings = async () => {
     try {
-      const response = await fetch("https://white-bush-0ea25dc03.3.azurestaticapps.net/api/credentials/check", {
-        headers: {
-          "Authorization": `Bearer ${await invoke<string>("get_access_token")}`,
-        },
-      });
-      if (response.ok) {
-        const data = await response.json();
-        setHasCredentials(data.has_credentials);
-      }
+      const status = await invoke<ApiKeyStatus>("get_api_key_status");
+      setHasCredentials(status.has_credentials);
     } catch (err) {
       console.log("[SETTINGS] Could not check BYOK status:", err);
     }
@@ -58,7 +68,7 @@ export function SettingsDialog({ open, onClose }: SettingsDialogProps) {
 
     try {
       await invoke("save_api_keys", { apiSettings });
-      console.log("[SETTINGS] API keys saved successfully to backend");
+      console.log("[SETTINGS] API keys saved locally");
       setSaveStatus("idle");
       setShowApiKeyForm(false);
       setHasCredentials(true);
@@ -71,7 +81,7 @@ export function SettingsDialog({ open, onClose }: SettingsDialogProps) {
   };
 
   const handleProviderChange = (event: React.ChangeEvent<HTMLInputElement>) => {
-    setApiSettings({ ...apiSettings, provider: event.target.value as "openai" | "azure" });
+    setApiSettings({ ...apiSettings, provider: event.target.value as Provider });
   };
 
   const handleDialogClose = () => {
@@ -81,22 +91,11 @@ export function SettingsDialog({ open, onClose }: SettingsDialogProps) {
 
   const handleDeleteCredentials = async () => {
     try {
-      const token = await invoke<string>("get_access_token");
-      const response = await fetch("https://white-bush-0ea25dc03.3.azurestaticapps.net/api/credentials", {
-        method: "DELETE",
-        headers: {
-          "Authorization": `Bearer ${token}`,
-        },
-      });
-
-      if (response.ok) {
-        console.log("[SETTINGS] Credentials deleted from backend");
-        setHasCredentials(false);
-        onClose(false);
-        window.location.reload();
-      } else {
-        throw new Error("Failed to delete credentials");
-      }
+      await invoke("delete_api_keys");
+      console.log("[SETTINGS] Local credentials deleted");
+      setHasCredentials(false);
+      onClose(false);
+      window.location.reload();
     } catch (error) {
       console.error("[SETTINGS] Failed to delete credentials:", error);
       setErrorMessage("Failed to delete credentials");
@@ -253,6 +252,30 @@ export function SettingsDialog({ open, onClose }: SettingsDialogProps) {
                       }
                     }}
                   />
+                  <FormControlLabel
+                    value="deepl"
+                    label="DeepL"
+                    control={<Radio size="small" sx={{ color: "#64b5f6", "&.Mui-checked": { color: "#64b5f6" } }} />}
+                    sx={{
+                      "& .MuiFormControlLabel-label": {
+                        fontSize: "13px",
+                        fontWeight: 400,
+                        color: "#e0e0e0",
+                      }
+                    }}
+                  />
+                  <FormControlLabel
+                    value="libretranslate"
+                    label="LibreTranslate"
+                    control={<Radio size="small" sx={{ color: "#64b5f6", "&.Mui-checked": { color: "#64b5f6" } }} />}
+                    sx={{
+                      "& .MuiFormControlLabel-label": {
+                        fontSize: "13px",
+                        fontWeight: 400,
+                        color: "#e0e0e0",
+                      }
+                    }}
+                  />
                 </RadioGroup>
               </FormControl>
 
@@ -335,6 +358,89 @@ export function SettingsDialog({ open, onClose }: SettingsDialogProps) {
                     }}
                   />
                 </Box>
+              ) : apiSettings.provider === "deepl" ? (
+                <Box sx={{ display: "flex", flexDirection: "column", gap: "12px" }}>
+                  <TextField
+                    label="API Key"
+                    value={apiSettings.deepl_api_key}
+                    onChange={(e) => setApiSettings({ ...apiSettings, deepl_api_key: e.target.value })}
+                    type="password"
+                    placeholder="Your DeepL API key"
+                    fullWidth
+                    size="small"
+                    InputLabelProps={{ sx: {
+                      color: "#888",
+                      fontSize: "13px",
+                    }}}
+                    InputProps={{
+                      sx: {
+                        fontSize: "13px",
+                        color: "#e0e0e0",
+                        backgroundColor: "#1a1a1a",
+                        "& fieldset": { borderColor: "#2a2a2a" },
+                        "&:hover fieldset": { borderColor: "#444" },
+                        "&.Mui-focused fieldset": { borderColor: "#64b5f6", borderWidth: "1px" },
+                      }
+                    }}
+                    slotProps={{
+                      inputLabel: { shrink: true }
+                    }}
+                  />
+                </Box>
+              ) : apiSettings.provider === "libretranslate" ? (
+                <Box sx={{ display: "flex", flexDirection: "column", gap: "12px" }}>
+                  <TextField
+                    label="Server URL"
+                    value={apiSettings.libretranslate_url}
+                    onChange={(e) => setApiSettings({ ...apiSettings, libretranslate_url: e.target.value })}
+                    placeholder="https://libretranslate.example.com"
+                    fullWidth
+                    size="small"
+                    InputLabelProps={{ sx: {
+                      color: "#888",
+                      fontSize: "13px",
+                    }}}
+                    InputProps={{
+                      sx: {
+                        fontSize: "13px",
+                        color: "#e0e0e0",
+                        backgroundColor: "#1a1a1a",
+                        "& fieldset": { borderColor: "#2a2a2a" },
+                        "&:hover fieldset": { borderColor: "#444" },
+                        "&.Mui-focused fieldset": { borderColor: "#64b5f6", borderWidth: "1px" },
+                      }
+                    }}
+                    slotProps={{
+                      inputLabel: { shrink: true }
+                    }}
+                  />
+                  <TextField
+                    label="API Key"
+                    value={apiSettings.libretranslate_api_key}
+                    onChange={(e) => setApiSettings({ ...apiSettings, libretranslate_api_key: e.target.value })}
+                    type="password"
+                    placeholder="Optional"
+                    fullWidth
+                    size="small"
+                    InputLabelProps={{ sx: {
+                      color: "#888",
+                      fontSize: "13px",
+                    }}}
+                    InputProps={{
+                      sx: {
+                        fontSize: "13px",
+                        color: "#e0e0e0",
+                        backgroundColor: "#1a1a1a",
+                        "& fieldset": { borderColor: "#2a2a2a" },
+                        "&:hover fieldset": { borderColor: "#444" },
+                        "&.Mui-focused fieldset": { borderColor: "#64b5f6", borderWidth: "1px" },
+                      }
+                    }}
+                    slotProps={{
+                      inputLabel: { shrink: true }
+                    }}
+                  />
+                </Box>
               ) : (
                 <Box sx={{ display: "flex", flexDirection: "column", gap: "12px" }}>
                   <TextField
@@ -363,6 +469,56 @@ export function SettingsDialog({ open, onClose }: SettingsDialogProps) {
                       inputLabel: { shrink: true }
                     }}
                   />
+                  <TextField
+                    label="Base URL"
+                    value={apiSettings.openai_base_url}
+                    onChange={(e) => setApiSettings({ ...apiSettings, openai_base_url: e.target.value })}
+                    placeholder="https://api.openai.com/v1"
+                    fullWidth
+                    size="small"
+                    InputLabelProps={{ sx: {
+                      color: "#888",
+                      fontSize: "13px",
+                    }}}
+                    InputProps={{
+                      sx: {
+                        fontSize: "13px",
+                        color: "#e0e0e0",
+                        backgroundColor: "#1a1a1a",
+                        "& fieldset": { borderColor: "#2a2a2a" },
+                        "&:hover fieldset": { borderColor: "#444" },
+                        "&.Mui-focused fieldset": { borderColor: "#64b5f6", borderWidth: "1px" },
+                      }
+                    }}
+                    slotProps={{
+                      inputLabel: { shrink: true }
+                    }}
+                  />
+                  <TextField
+                    label="Model"
+                    value={apiSettings.openai_model}
+                    onChange={(e) => setApiSettings({ ...apiSettings, openai_model: e.target.value })}
+                    placeholder="gpt-4o-mini"
+                    fullWidth
+                    size="small"
+                    InputLabelProps={{ sx: {
+                      color: "#888",
+                      fontSize: "13px",
+                    }}}
+                    InputProps={{
+                      sx: {
+                        fontSize: "13px",
+                        color: "#e0e0e0",
+                        backgroundColor: "#1a1a1a",
+                        "& fieldset": { borderColor: "#2a2a2a" },
+                        "&:hover fieldset": { borderColor: "#444" },
+                        "&.Mui-focused fieldset": { borderColor: "#64b5f6", borderWidth: "1px" },
+                      }
+                    }}
+                    slotProps={{
+                      inputLabel: { shrink: true }
+                    }}
+                  />
                   <Typography sx={{ color: "#666", fontSize: "11px", fontWeight: 400 }}>
                     Get your key from platform.openai.com
                   </Typography>
@@ -8,10 +8,34 @@ import KeyIcon from "@mui/icons-material/Key";
 import TuneIcon from "@mui/icons-material/Tune";
 import AutoFixHighIcon from "@mui/icons-material/AutoFixHigh";
 import OpenInFullIcon from "@mui/icons-material/OpenInFull";
+import ListAltIcon from "@mui/icons-material/ListAlt";
+import SchoolIcon from "@mui/icons-material/School";
 import { SettingsDialog } from "./SettingsDialog";
 import { PreferencesDialog } from "./PreferencesDialog";
 import { invoke } from "@tauri-apps/api/core";
 import { StreamingText } from "./StreamingText";
+import { DiffText, TextDiff } from "./DiffText";
+
+export interface TranslationAlternative {
+  text: string;
+  note: string;
+}
+
+export interface Correction {
+  start: number;
+  end: number;
+  original: string;
+  replacement: string;
+  category: "spelling" | "grammar" | "punctuation" | "style";
+  explanation: string;
+}
+
+const CORRECTION_COLORS: Record<Correction["category"], string> = {
+  spelling: "#e57373",
+  grammar: "#ffb74d",
+  punctuation: "#64b5f6",
+  style: "#ba68c8",
+};
 
 interface PopupProps {
   translation: string;
@@ -21,6 +45,12 @@ interface PopupProps {
   mode: "translate" | "enhance";
   progress: number;
   originalText: string;
+  alternatives: TranslationAlternative[];
+  diff: TextDiff | null;
+  acceptedHunks: number[];
+  onToggleHunk: (hunk: number) => void;
+  corrections: Correction[];
+  onRequestCorrections: () => void;
   onCopy: () => void;
   onReplace: () => void;
   onClose: () => void;
@@ -28,6 +58,8 @@ interface PopupProps {
   onModeChange: (mode: "translate" | "enhance") => void;
   onClearAndStream: () => void;
   onExpandToNormal: () => void;
+  onRequestAlternatives: () => void;
+  onSelectAlternative: (alternative: TranslationAlternative) => void;
 }
 
 export function TranslationPopup({
@@ -38,6 +70,12 @@ export function TranslationPopup({
   mode,
   progress,
   originalText,
+  alternatives,
+  diff,
+  acceptedHunks,
+  onToggleHunk,
+  corrections,
+  onRequestCorrections,
   onCopy,
   onReplace,
   onClose,
@@ -45,6 +83,8 @@ export function TranslationPopup({
   onModeChange,
   onClearAndStream,
   onExpandToNormal,
+  onRequestAlternatives,
+  onSelectAlternative,
 }: PopupProps) {
   const enhanceInputRef = useRef<HTMLInputElement>(null);
   const [settingsOpen, setSettingsOpen] = useState(false);
@@ -375,7 +415,11 @@ export function TranslationPopup({
                         console.error("[POPUP] Logout failed:", error);
                       }
                     } else {
-                      window.open("https://white-bush-0ea25dc03.3.azurestaticapps.net/auth", "_blank");
+                      try {
+                        window.open(`${await invoke<string>("get_backend_url")}/auth`, "_blank");
+                      } catch (error) {
+                        console.error("[POPUP] Backend URL is invalid:", error);
+                      }
                     }
                   }}
                   sx={{
@@ -479,7 +523,11 @@ export function TranslationPopup({
                     cursor: "move",
                   }}
                 >
-                  <StreamingText text={translation} isStreaming={isStreaming} />
+                  {mode === "enhance" && !isStreaming && diff && diff.hunks.length > 0 ? (
+                    <DiffText diff={diff} accepted={acceptedHunks} onToggleHunk={onToggleHunk} />
+                  ) : (
+                    <StreamingText text={translation} isStreaming={isStreaming} />
+                  )}
                   {isStreaming && (
                     <Box
                       component="span"
@@ -503,6 +551,71 @@ export function TranslationPopup({
           </Box>
 
           <Box sx={{ flexShrink: 0 }}>
+            {mode === "translate" && !isStreaming && alternatives.length > 1 && (
+              <Stack
+                spacing="4px"
+                sx={{ padding: "6px 8px", backgroundColor: "#151515", borderTop: "1px solid #222", maxHeight: 140, overflowY: "auto" }}
+              >
+                {alternatives.map((alternative, index) => {
+                  const selected = alternative.text === translation;
+                  return (
+                    <Box
+                      key={index}
+                      onClick={() => onSelectAlternative(alternative)}
+                      sx={{
+                        padding: "4px 6px",
+                        borderRadius: "4px",
+                        cursor: "pointer",
+                        border: selected ? "1px solid #64b5f6" : "1px solid #2a2a2a",
+                        backgroundColor: selected ? "#1e2a35" : "#1a1a1a",
+                        "&:hover": { borderColor: "#64b5f6" },
+                      }}
+                    >
+                      <Typography sx={{ fontSize: "11px", color: "#e0e0e0", whiteSpace: "pre-wrap" }}>
+                        {index + 1}. {alternative.text}
+                      </Typography>
+                      {alternative.note && (
+                        <Typography sx={{ fontSize: "9px", color: "#888" }}>{alternative.note}</Typography>
+                      )}
+                    </Box>
+                  );
+                })}
+              </Stack>
+            )}
+
+            {mode === "enhance" && !isStreaming && corrections.length > 0 && (
+              <Stack
+                spacing="4px"
+                sx={{ padding: "6px 8px", backgroundColor: "#151515", borderTop: "1px solid #222", maxHeight: 140, overflowY: "auto" }}
+              >
+                {corrections.map((correction, index) => (
+                  <Box key={index} sx={{ display: "flex", alignItems: "flex-start", gap: "6px" }}>
+                    <Chip
+                      label={correction.category}
+                      size="small"
+                      sx={{
+                        height: 16,
+                        fontSize: "9px",
+                        color: "#000",
+                        backgroundColor: CORRECTION_COLORS[correction.category],
+                        flexShrink: 0,
+                      }}
+                    />
+                    <Box>
+                      <Typography sx={{ fontSize: "11px", color: "#e0e0e0" }}>
+                        <Box component="span" sx={{ color: "#e57373", textDecoration: "line-through" }}>{correction.original}</Box>
+                        {" → "}
+                        <Box component="span" sx={{ color: "#81c784" }}>{correction.replacement}</Box>
+                      </Typography>
+                      {correction.explanation && (
+                        <Typography sx={{ fontSize: "9px", color: "#888" }}>{correction.explanation}</Typography>
+                      )}
+                    </Box>
+                  </Box>
+                ))}
+              </Stack>
+            )}
+
             {showEnhanceInput && mode === "enhance" && !isStreaming && translation && (
               <Box sx={{ padding: "8px", backgroundColor: "#151515", borderTop: "1px solid #222" }}>
                 <TextField
@@ -607,6 +720,24 @@ export function TranslationPopup({
                 )}
               </Box>
 
+              {mode === "enhance" && !isStreaming && translation && (
+                <Tooltip title="Explain corrections" placement="top" arrow>
+                  <IconButton
+                    size="small"
+                    onClick={onRequestCorrections}
+                    sx={{
+                      color: corrections.length > 0 ? "#64b5f6" : "#888",
+                      padding: "3px",
+                      "&:hover": {
+                        backgroundColor: "#2a2a2a",
+                      },
+                    }}
+                  >
+                    <SchoolIcon sx={{ fontSize: 14 }} />
+                  </IconButton>
+                </Tooltip>
+              )}
+
               {mode === "enhance" && !isStreaming && translation && (
                 <Tooltip title="AI Mode (⌘I)" placement="top" arrow>
                   <IconButton
@@ -625,6 +756,24 @@ export function TranslationPopup({
                 </Tooltip>
               )}
 
+              {mode === "translate" && !isStreaming && translation && (
+                <Tooltip title="Alternatives" placement="top" arrow>
+                  <IconButton
+                    size="small"
+                    onClick={onRequestAlternatives}
+                    sx={{
+                      color: alternatives.length > 1 ? "#64b5f6" : "#888",
+                      padding: "3px",
+                      "&:hover": {
+                        backgroundColor: "#2a2a2a",
+                      },
+                    }}
+                  >
+                    <ListAltIcon sx={{ fontSize: 14 }} />
+                  </IconButton>
+                </Tooltip>
+              )}
+
               <Tooltip title="Expand to Edit Mode" placement="top" arrow>
                 <IconButton
                   size="small"
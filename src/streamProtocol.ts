@@ -0,0 +1,80 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export const STREAM_PROTOCOL_VERSION = 1;
+
+export interface StreamDeltaEvent {
+  version: number;
+  job_id: number;
+  seq: number;
+  offset: number;
+  delta: string;
+  final: boolean;
+}
+
+interface StreamSnapshot {
+  version: number;
+  job_id: number;
+  seq: number;
+  text: string;
+  final: boolean;
+}
+
+export interface StreamUpdate {
+  jobId: number;
+  text: string;
+  final: boolean;
+}
+
+export class StreamAssembler {
+  private jobId: number | null = null;
+  private seq = 0;
+  private text = "";
+
+  reset() {
+    this.jobId = null;
+    this.seq = 0;
+    this.text = "";
+  }
+
+  async apply(event: StreamDeltaEvent): Promise<StreamUpdate | null> {
+    if (event.job_id !== this.jobId) {
+      if (this.jobId !== null && event.job_id < this.jobId) {
+        return null;
+      }
+      this.jobId = event.job_id;
+      this.seq = 0;
+      this.text = "";
+    }
+
+    if (event.seq <= this.seq) {
+      return null;
+    }
+
+    const inOrder = event.version === STREAM_PROTOCOL_VERSION
+      && event.seq === this.seq + 1
+      && event.offset <= this.text.length;
+    if (!inOrder) {
+      console.warn("[STREAM] Out of order delta, resyncing job", event.job_id, "seq", event.seq);
+      return this.resync(event.job_id);
+    }
+
+    this.text = this.text.slice(0, event.offset) + event.delta;
+    this.seq = event.seq;
+    return { jobId: event.job_id, text: this.text, final: event.final };
+  }
+
+  private async resync(jobId: number): Promise<StreamUpdate | null> {
+    try {
+      const snapshot = await invoke<StreamSnapshot | null>("get_stream_snapshot", { jobId });
+      if (!snapshot || snapshot.job_id !== this.jobId || snapshot.seq < this.seq) {
+        return null;
+      }
+      this.text = snapshot.text;
+      this.seq = snapshot.seq;
+      return { jobId, text: this.text, final: snapshot.final };
+    } catch (error) {
+      console.error("[STREAM] Failed to fetch stream snapshot:", error);
+      return null;
+    }
+  }
+}
@@ -0,0 +1,47 @@
+export type TranslationErrorCode =
+  | "unauthorized"
+  | "forbidden"
+  | "quota_exceeded"
+  | "budget_exceeded"
+  | "rate_limited"
+  | "network"
+  | "timeout"
+  | "backend_error"
+  | "invalid_input"
+  | "validation_failed"
+  | "configuration";
+
+export interface TranslationErrorPayload {
+  job_id: number;
+  code: TranslationErrorCode;
+  message: string;
+  retry_after: number | null;
+  status: number | null;
+}
+
+export function describeTranslationError(error: TranslationErrorPayload): string {
+  switch (error.code) {
+    case "unauthorized":
+      return "Your session has expired. Please sign in again.";
+    case "forbidden":
+      return "Your account does not have access to this feature. Check your plan or permissions.";
+    case "quota_exceeded":
+      return "You have reached your translation quota. Upgrade your plan to continue.";
+    case "budget_exceeded":
+      return "Your usage budget is used up. Raise the limit in Preferences to continue.";
+    case "rate_limited":
+      return error.retry_after
+        ? `Too many requests. Please try again in ${error.retry_after} seconds.`
+        : "Too many requests. Please try again in a moment.";
+    case "network":
+      return "Network error. Check your connection and proxy settings.";
+    case "timeout":
+      return "The server took too long to respond.";
+    case "validation_failed":
+      return "The translation dropped protected text such as code or URLs. Please try again.";
+    case "configuration":
+      return "The translation service could not be reached at the configured address. Check the backend or provider URL in Preferences.";
+    default:
+      return error.message;
+  }
+}